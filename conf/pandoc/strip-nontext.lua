@@ -125,9 +125,6 @@ function Plain(p)
 end
 
 -- Drop real non-text structures.
-function Table(_)
-  return {}
-end
 
 function Figure(_)
   return {}
@@ -137,11 +134,138 @@ function Image(_)
   return {}
 end
 
-function Note(_)
-  return {}
+-- Keep code/preformatted blocks as sentinel-wrapped lines so the reader can
+-- show them line by line and the normalizer can apply its code-block policy.
+-- Must match `encode_preformatted_block` in src/text_utils.rs.
+local PRE_LINE_START = "\u{E010}"
+local PRE_LINE_HEADER_END = "\u{E012}"
+local PRE_LINE_END = "\u{E011}"
+
+local function preformatted_kind(el)
+  local info = (el.classes[1] or ""):gsub("^language%-", ""):lower()
+  if info == "text" or info == "txt" or info == "plain" or info == "plaintext"
+      or info == "poem" or info == "verse" then
+    return "p"
+  end
+  if info ~= "" then
+    return "c"
+  end
+
+  local lines, code_lines = 0, 0
+  for line in (el.text .. "\n"):gmatch("(.-)\n") do
+    if trim(line) ~= "" then
+      lines = lines + 1
+      if line:find("[{};=()<>%[%]]") then
+        code_lines = code_lines + 1
+      end
+    end
+  end
+  if lines > 0 and code_lines * 2 >= lines then
+    return "c"
+  end
+  return "p"
+end
+
+function CodeBlock(el)
+  local lines = {}
+  for line in (el.text .. "\n"):gmatch("(.-)\n") do
+    table.insert(lines, (line:gsub("%s+$", "")))
+  end
+  while #lines > 0 and trim(lines[1]) == "" do
+    table.remove(lines, 1)
+  end
+  while #lines > 0 and trim(lines[#lines]) == "" do
+    table.remove(lines)
+  end
+  if #lines == 0 then
+    return {}
+  end
+
+  local kind = preformatted_kind(el)
+  local out = {}
+  for idx, line in ipairs(lines) do
+    if trim(line) ~= "" then
+      local count = idx == 1 and tostring(#lines) or ""
+      table.insert(out, PRE_LINE_START .. kind .. count .. PRE_LINE_HEADER_END .. line .. PRE_LINE_END)
+    end
+  end
+  return pandoc.RawBlock("plain", table.concat(out, "\n"))
+end
+
+-- Tables become one sentinel-wrapped line per row (header row first) so each
+-- row is read and highlighted on its own. Must match `encode_table` in
+-- src/text_utils.rs.
+local TABLE_ROW_START = "\u{E018}"
+local TABLE_ROW_END = "\u{E019}"
+local TABLE_CELL_SEP = "\u{E01A}"
+local TABLE_COLUMNS_START = "\u{E01B}"
+local table_count = 0
+
+local function row_cells(row)
+  local cells = {}
+  for _, cell in ipairs(row.cells) do
+    table.insert(cells, trim(pandoc.utils.stringify(cell.contents):gsub("%s+", " ")))
+  end
+  return cells
+end
+
+local function has_text(cells)
+  for _, cell in ipairs(cells) do
+    if cell ~= "" then
+      return true
+    end
+  end
+  return false
 end
 
-function RawBlock(_)
+function Table(el)
+  local header = {}
+  if #el.head.rows > 0 then
+    header = row_cells(el.head.rows[#el.head.rows])
+  end
+  local rows = {}
+  for _, body in ipairs(el.bodies) do
+    for _, row in ipairs(body.body) do
+      table.insert(rows, row_cells(row))
+    end
+  end
+
+  local table_idx = table_count
+  table_count = table_count + 1
+  local out = {}
+  local first_tr = 0
+  local columns = ""
+  if has_text(header) then
+    local names = table.concat(header, TABLE_CELL_SEP)
+    table.insert(out, TABLE_ROW_START .. table_idx .. ":0:h" .. PRE_LINE_HEADER_END .. names .. TABLE_ROW_END)
+    first_tr = 1
+    columns = TABLE_COLUMNS_START .. names
+  end
+  for idx, cells in ipairs(rows) do
+    if has_text(cells) then
+      local ids = table_idx .. ":" .. (first_tr + idx - 1) .. ":" .. idx
+      table.insert(out, TABLE_ROW_START .. ids .. columns .. PRE_LINE_HEADER_END
+        .. table.concat(cells, TABLE_CELL_SEP) .. TABLE_ROW_END)
+    end
+  end
+  if #out == 0 then
+    return {}
+  end
+  return pandoc.RawBlock("plain", table.concat(out, "\n"))
+end
+
+local function is_preformatted_raw(el)
+  if el.format ~= "plain" then
+    return false
+  end
+  local head = el.text:sub(1, #PRE_LINE_START)
+  return head == PRE_LINE_START or head == TABLE_ROW_START
+end
+
+function RawBlock(el)
+  if is_preformatted_raw(el) then
+    return el
+  end
   return {}
 end
 
@@ -149,8 +273,10 @@ function RawInline(_)
   return {}
 end
 
-function Math(_)
-  return {}
+-- Keep TeX (MathML arrives here as TeX) so the normalizer can verbalize it.
+function Math(el)
+  local delim = el.mathtype == "DisplayMath" and "$$" or "$"
+  return pandoc.Str(delim .. el.text .. delim)
 end
 
 -- Keep visible link text and drop URL target.
@@ -163,7 +289,14 @@ function Div(d)
   return d.content
 end
 
+-- EPUB input opens every spine item with an empty span named after its file;
+-- keep that as a chapter-break sentinel for chapter-aware pagination.
+local CHAPTER_BREAK = "\u{E01C}"
+
 function Span(s)
+  if #s.content == 0 and s.identifier:match("%.x?html?$") then
+    return pandoc.RawInline("plain", CHAPTER_BREAK)
+  end
   return s.content
 end
 
@@ -175,3 +308,77 @@ function Str(el)
   end
   return el
 end
+
+-- Footnotes: replace each note with a sentinel reference and emit the note
+-- bodies at the end of the chapter that references them (before the next
+-- top-level heading). Must match `encode_note_ref`/`encode_note_body` in
+-- src/text_utils.rs. Runs last, after the typewise element filters above.
+local NOTE_REF_START = "\u{E014}"
+local NOTE_REF_END = "\u{E015}"
+local NOTE_BODY_START = "\u{E016}"
+local NOTE_BODY_END = "\u{E017}"
+
+-- Plain output drops heading and quote markup; keep them as markdown-style
+-- "#" / ">" prefixes so the reader can rebuild the block structure.
+local function mark_block(block)
+  if block.t == "Header" then
+    local content = pandoc.List({ pandoc.Str(string.rep("#", block.level)), pandoc.Space() })
+    content:extend(block.content)
+    return { pandoc.Para(content) }
+  end
+  if block.t == "BlockQuote" then
+    local marked = {}
+    for _, inner in ipairs(block.content) do
+      if inner.t == "Para" or inner.t == "Plain" then
+        local content = pandoc.List({ pandoc.Str(">"), pandoc.Space() })
+        content:extend(inner.content)
+        table.insert(marked, pandoc.Para(content))
+      else
+        table.insert(marked, inner)
+      end
+    end
+    return marked
+  end
+  return { block }
+end
+
+function Pandoc(doc)
+  local chapter_level = nil
+  for _, block in ipairs(doc.blocks) do
+    if block.t == "Header" and (chapter_level == nil or block.level < chapter_level) then
+      chapter_level = block.level
+    end
+  end
+
+  local blocks, pending, counter = {}, {}, 0
+  local function flush()
+    if #pending > 0 then
+      table.insert(blocks, pandoc.RawBlock("plain", table.concat(pending, "\n")))
+      pending = {}
+    end
+  end
+
+  for _, block in ipairs(doc.blocks) do
+    if block.t == "Header" and block.level == chapter_level then
+      flush()
+    end
+    local walked = block:walk({
+      Note = function(note)
+        counter = counter + 1
+        local label = tostring(counter)
+        local body = trim(pandoc.utils.stringify(note.content):gsub("%s+", " "))
+        if body ~= "" then
+          table.insert(pending, NOTE_BODY_START .. label .. PRE_LINE_HEADER_END .. body .. NOTE_BODY_END)
+        end
+        return pandoc.RawInline("plain", NOTE_REF_START .. label .. NOTE_REF_END)
+      end,
+    })
+    for _, marked in ipairs(mark_block(walked)) do
+      table.insert(blocks, marked)
+    end
+  end
+  flush()
+
+  doc.blocks = blocks
+  return doc
+end
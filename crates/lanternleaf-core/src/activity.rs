@@ -0,0 +1,433 @@
+//! Reading-activity log and the aggregates behind the stats dashboard.
+//!
+//! While a book is open an [`ActivityTracker`] counts forward progress and
+//! the TTS audio actually played. Every few minutes, and when the session
+//! stops, it closes an [`ActivitySegment`] that is appended to a JSON-lines
+//! log under the cache root. Queries read the whole log and group it per day
+//! and per book; days follow the caller's UTC offset so they match the user's
+//! calendar.
+
+use crate::{annotations, cache};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+use tracing::warn;
+use ts_rs::TS;
+
+/// Open segments are closed and written at least this often.
+const SEGMENT_FLUSH_SECS: u64 = 5 * 60;
+pub const DEFAULT_ACTIVITY_HISTORY_DAYS: usize = 30;
+
+/// Activity for one book over a span of one session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActivitySegment {
+    /// Unix seconds when the session started; shared by all its segments.
+    pub session_id: u64,
+    pub source_path: String,
+    pub title: String,
+    pub start_unix_secs: u64,
+    pub end_unix_secs: u64,
+    pub pages_advanced: u64,
+    pub sentences_advanced: u64,
+    pub tts_seconds: f64,
+    pub tts_words: u64,
+    /// Last segment of its session.
+    #[serde(default)]
+    pub session_ended: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ActivityTracker {
+    session_id: u64,
+    source_path: String,
+    title: String,
+    segment_start: u64,
+    /// Page and book-wide sentence index last seen.
+    position: Option<(usize, usize)>,
+    pages_advanced: u64,
+    sentences_advanced: u64,
+    tts_seconds: f64,
+    tts_words: u64,
+}
+
+impl ActivityTracker {
+    pub fn start(source_path: &Path, title: &str, now: u64) -> Self {
+        Self {
+            session_id: now,
+            source_path: source_path.to_string_lossy().to_string(),
+            title: title.to_string(),
+            segment_start: now,
+            position: None,
+            pages_advanced: 0,
+            sentences_advanced: 0,
+            tts_seconds: 0.0,
+            tts_words: 0,
+        }
+    }
+
+    /// Count forward movement from the last position. Moving backwards only
+    /// moves the baseline.
+    pub fn advance_to(&mut self, page: usize, sentence_idx: Option<usize>) {
+        if let Some((last_page, last_sentence)) = self.position {
+            self.pages_advanced += page.saturating_sub(last_page) as u64;
+            if let Some(sentence_idx) = sentence_idx {
+                self.sentences_advanced += sentence_idx.saturating_sub(last_sentence) as u64;
+            }
+        }
+        self.reposition(page, sentence_idx);
+    }
+
+    /// Move the baseline without counting progress, e.g. after a jump.
+    pub fn reposition(&mut self, page: usize, sentence_idx: Option<usize>) {
+        let last_sentence = self.position.map(|(_, sentence)| sentence).unwrap_or(0);
+        self.position = Some((page, sentence_idx.unwrap_or(last_sentence)));
+    }
+
+    pub fn add_tts(&mut self, played: Duration, words: usize) {
+        self.tts_seconds += played.as_secs_f64();
+        self.tts_words += words as u64;
+    }
+
+    pub fn needs_flush(&self, now: u64) -> bool {
+        now.saturating_sub(self.segment_start) >= SEGMENT_FLUSH_SECS
+    }
+
+    /// Close the open segment and start a new one at `now`. Idle segments
+    /// are skipped unless they end the session.
+    pub fn take_segment(&mut self, now: u64, session_ended: bool) -> Option<ActivitySegment> {
+        let idle = self.pages_advanced == 0
+            && self.sentences_advanced == 0
+            && self.tts_words == 0
+            && self.tts_seconds == 0.0;
+        let segment = ActivitySegment {
+            session_id: self.session_id,
+            source_path: self.source_path.clone(),
+            title: self.title.clone(),
+            start_unix_secs: self.segment_start,
+            end_unix_secs: now.max(self.segment_start),
+            pages_advanced: self.pages_advanced,
+            sentences_advanced: self.sentences_advanced,
+            tts_seconds: self.tts_seconds,
+            tts_words: self.tts_words,
+            session_ended,
+        };
+        self.segment_start = now;
+        self.pages_advanced = 0;
+        self.sentences_advanced = 0;
+        self.tts_seconds = 0.0;
+        self.tts_words = 0;
+        (!idle || session_ended).then_some(segment)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, TS)]
+#[ts(export)]
+pub struct ActivityTotals {
+    pub sessions: usize,
+    pub books: usize,
+    /// Time with a book open.
+    pub reading_seconds: f64,
+    pub pages_advanced: usize,
+    pub sentences_advanced: usize,
+    pub tts_seconds: f64,
+    pub tts_words: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+#[ts(export)]
+pub struct ActivityBookDay {
+    pub source_path: String,
+    pub title: String,
+    pub reading_seconds: f64,
+    pub pages_advanced: usize,
+    pub sentences_advanced: usize,
+    pub tts_seconds: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+#[ts(export)]
+pub struct ActivityDay {
+    /// `YYYY-MM-DD` in the caller's time zone.
+    pub date: String,
+    pub reading_seconds: f64,
+    pub pages_advanced: usize,
+    pub sentences_advanced: usize,
+    pub tts_seconds: f64,
+    pub tts_words: usize,
+    pub books: Vec<ActivityBookDay>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+#[ts(export)]
+pub struct ActivitySummary {
+    pub totals: ActivityTotals,
+    /// Words spoken per minute of TTS audio played.
+    pub average_listening_wpm: Option<f64>,
+    /// Consecutive active days ending today or yesterday.
+    pub current_streak_days: usize,
+    pub longest_streak_days: usize,
+}
+
+/// Day number of a timestamp in a zone `utc_offset_minutes` east of UTC.
+fn local_day(unix_secs: u64, utc_offset_minutes: i32) -> i64 {
+    (unix_secs as i64 + i64::from(utc_offset_minutes) * 60).div_euclid(86_400)
+}
+
+fn format_day(day: i64) -> String {
+    let (year, month, day) = annotations::civil_from_days(day);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+fn matches_book(segment: &ActivitySegment, source_path: Option<&str>) -> bool {
+    source_path.is_none_or(|path| segment.source_path == path)
+}
+
+pub fn totals(segments: &[ActivitySegment], source_path: Option<&str>) -> ActivityTotals {
+    let mut sessions = BTreeSet::new();
+    let mut books = BTreeSet::new();
+    let mut totals = ActivityTotals::default();
+    for segment in segments
+        .iter()
+        .filter(|segment| matches_book(segment, source_path))
+    {
+        sessions.insert((segment.session_id, segment.source_path.as_str()));
+        books.insert(segment.source_path.as_str());
+        totals.reading_seconds += segment
+            .end_unix_secs
+            .saturating_sub(segment.start_unix_secs) as f64;
+        totals.pages_advanced += segment.pages_advanced as usize;
+        totals.sentences_advanced += segment.sentences_advanced as usize;
+        totals.tts_seconds += segment.tts_seconds;
+        totals.tts_words += segment.tts_words as usize;
+    }
+    totals.sessions = sessions.len();
+    totals.books = books.len();
+    totals
+}
+
+/// Active days, newest first, limited to the `days` most recent calendar
+/// days ending at `now`. Segments count toward the day they ended.
+pub fn daily_history(
+    segments: &[ActivitySegment],
+    source_path: Option<&str>,
+    now: u64,
+    days: usize,
+    utc_offset_minutes: i32,
+) -> Vec<ActivityDay> {
+    let today = local_day(now, utc_offset_minutes);
+    let oldest = today - days.max(1) as i64 + 1;
+    let mut by_day: BTreeMap<i64, BTreeMap<&str, ActivityBookDay>> = BTreeMap::new();
+    let mut words_by_day: BTreeMap<i64, usize> = BTreeMap::new();
+    for segment in segments
+        .iter()
+        .filter(|segment| matches_book(segment, source_path))
+    {
+        let day = local_day(segment.end_unix_secs, utc_offset_minutes);
+        if day < oldest || day > today {
+            continue;
+        }
+        *words_by_day.entry(day).or_default() += segment.tts_words as usize;
+        let book = by_day
+            .entry(day)
+            .or_default()
+            .entry(segment.source_path.as_str())
+            .or_insert_with(|| ActivityBookDay {
+                source_path: segment.source_path.clone(),
+                title: segment.title.clone(),
+                reading_seconds: 0.0,
+                pages_advanced: 0,
+                sentences_advanced: 0,
+                tts_seconds: 0.0,
+            });
+        book.reading_seconds += segment
+            .end_unix_secs
+            .saturating_sub(segment.start_unix_secs) as f64;
+        book.pages_advanced += segment.pages_advanced as usize;
+        book.sentences_advanced += segment.sentences_advanced as usize;
+        book.tts_seconds += segment.tts_seconds;
+    }
+    by_day
+        .into_iter()
+        .rev()
+        .map(|(day, books)| {
+            let books: Vec<ActivityBookDay> = books.into_values().collect();
+            ActivityDay {
+                date: format_day(day),
+                reading_seconds: books.iter().map(|book| book.reading_seconds).sum(),
+                pages_advanced: books.iter().map(|book| book.pages_advanced).sum(),
+                sentences_advanced: books.iter().map(|book| book.sentences_advanced).sum(),
+                tts_seconds: books.iter().map(|book| book.tts_seconds).sum(),
+                tts_words: words_by_day.get(&day).copied().unwrap_or(0),
+                books,
+            }
+        })
+        .collect()
+}
+
+pub fn summary(
+    segments: &[ActivitySegment],
+    source_path: Option<&str>,
+    now: u64,
+    utc_offset_minutes: i32,
+) -> ActivitySummary {
+    let totals = totals(segments, source_path);
+    let average_listening_wpm =
+        (totals.tts_seconds >= 1.0).then(|| totals.tts_words as f64 / (totals.tts_seconds / 60.0));
+
+    let active_days: BTreeSet<i64> = segments
+        .iter()
+        .filter(|segment| matches_book(segment, source_path))
+        .map(|segment| local_day(segment.end_unix_secs, utc_offset_minutes))
+        .collect();
+    let mut longest_streak_days = 0;
+    let mut run = 0;
+    let mut previous: Option<i64> = None;
+    for &day in &active_days {
+        run = if previous == Some(day - 1) {
+            run + 1
+        } else {
+            1
+        };
+        longest_streak_days = longest_streak_days.max(run);
+        previous = Some(day);
+    }
+    let today = local_day(now, utc_offset_minutes);
+    let mut current_streak_days = 0;
+    if let Some(&last) = active_days.last()
+        && last >= today - 1
+    {
+        let mut day = last;
+        while active_days.contains(&day) {
+            current_streak_days += 1;
+            day -= 1;
+        }
+    }
+
+    ActivitySummary {
+        totals,
+        average_listening_wpm,
+        current_streak_days,
+        longest_streak_days,
+    }
+}
+
+pub fn append_segment(segment: &ActivitySegment) -> Result<(), String> {
+    let path = cache::activity_log_path();
+    let line = serde_json::to_string(segment)
+        .map_err(|err| format!("Failed to serialize activity segment: {err}"))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|err| format!("Failed to open {}: {err}", path.display()))?;
+    writeln!(file, "{line}").map_err(|err| format!("Failed to write {}: {err}", path.display()))
+}
+
+/// All logged segments; malformed lines (e.g. a torn final write) are skipped.
+pub fn load_segments() -> Vec<ActivitySegment> {
+    let path = cache::activity_log_path();
+    let Ok(raw) = fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+    raw.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(segment) => Some(segment),
+            Err(err) => {
+                warn!(path = %path.display(), "Skipping invalid activity log line: {err}");
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 86_400;
+
+    fn segment(source: &str, session_id: u64, end: u64, sentences: u64) -> ActivitySegment {
+        ActivitySegment {
+            session_id,
+            source_path: source.to_string(),
+            title: source.to_uppercase(),
+            start_unix_secs: end - 600,
+            end_unix_secs: end,
+            pages_advanced: 1,
+            sentences_advanced: sentences,
+            tts_seconds: 60.0,
+            tts_words: 150,
+            session_ended: true,
+        }
+    }
+
+    #[test]
+    fn tracker_counts_forward_progress_and_skips_idle_segments() {
+        let mut tracker = ActivityTracker::start(Path::new("/books/a.epub"), "A", 1_000);
+        tracker.advance_to(0, Some(0));
+        tracker.advance_to(0, Some(3));
+        tracker.advance_to(1, Some(8));
+        tracker.reposition(9, Some(90));
+        tracker.advance_to(9, Some(88));
+        tracker.advance_to(10, Some(95));
+        tracker.add_tts(Duration::from_secs(30), 70);
+
+        assert!(!tracker.needs_flush(1_000 + SEGMENT_FLUSH_SECS - 1));
+        assert!(tracker.needs_flush(1_000 + SEGMENT_FLUSH_SECS));
+        let first = tracker.take_segment(1_400, false).expect("active segment");
+        assert_eq!((first.pages_advanced, first.sentences_advanced), (2, 8 + 7));
+        assert_eq!((first.tts_seconds, first.tts_words), (30.0, 70));
+        assert_eq!((first.start_unix_secs, first.end_unix_secs), (1_000, 1_400));
+
+        assert!(tracker.take_segment(1_500, false).is_none());
+        let last = tracker
+            .take_segment(1_600, true)
+            .expect("session end is kept");
+        assert!(last.session_ended);
+        assert_eq!((last.session_id, last.start_unix_secs), (1_000, 1_500));
+    }
+
+    #[test]
+    fn aggregates_cover_totals_days_wpm_and_streaks() {
+        let now = 20 * DAY + 3_600;
+        let segments = vec![
+            segment("a", 1, 15 * DAY + 7_200, 10),
+            segment("a", 2, 17 * DAY + 7_200, 20),
+            segment("b", 3, 18 * DAY + 7_200, 5),
+            segment("a", 3, 19 * DAY + 7_200, 5),
+            segment("b", 4, 20 * DAY + 1_800, 1),
+        ];
+
+        let summary = summary(&segments, None, now, 0);
+        assert_eq!(summary.totals.sessions, 5);
+        assert_eq!(summary.totals.books, 2);
+        assert_eq!(summary.totals.sentences_advanced, 41);
+        assert_eq!(summary.average_listening_wpm, Some(150.0));
+        assert_eq!(summary.current_streak_days, 4);
+        assert_eq!(summary.longest_streak_days, 4);
+
+        let book_a = super::summary(&segments, Some("a"), now, 0);
+        assert_eq!(book_a.totals.sessions, 3);
+        assert_eq!(book_a.current_streak_days, 1);
+
+        let history = daily_history(&segments, None, now, 3, 0);
+        assert_eq!(
+            history
+                .iter()
+                .map(|day| day.date.as_str())
+                .collect::<Vec<_>>(),
+            vec!["1970-01-21", "1970-01-20", "1970-01-19"]
+        );
+        assert_eq!(history[1].books.len(), 1);
+        assert_eq!(history[1].books[0].title, "A");
+
+        // Two hours west of UTC the 00:30 segment falls on the previous day.
+        let shifted = daily_history(&segments, None, now, 1, -120);
+        assert_eq!(shifted[0].date, "1970-01-20");
+        assert_eq!(shifted[0].books.len(), 2);
+    }
+}
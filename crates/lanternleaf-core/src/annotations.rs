@@ -0,0 +1,400 @@
+//! Highlights and notes over book-wide display sentence ranges.
+//!
+//! Ranges use global sentence indices (the sum of earlier page sentence
+//! counts plus the page-local index). Those shift when pagination settings
+//! change, so each annotation also keeps the text of its first and last
+//! sentence and is re-anchored against the new sentence list after every
+//! repagination.
+//!
+//! Exports render every annotation of a book with its reading context as
+//! Markdown (Obsidian-style block quotes), JSON or CSV.
+
+use crate::{config::HighlightColor, text_utils};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use ts_rs::TS;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct Annotation {
+    #[ts(type = "number")]
+    pub id: u64,
+    /// First book-wide display sentence covered.
+    pub sentence_start: usize,
+    /// One past the last book-wide display sentence covered.
+    pub sentence_end: usize,
+    /// Text of the first covered sentence, used for re-anchoring.
+    pub start_text: String,
+    /// Text of the last covered sentence, used for re-anchoring.
+    pub end_text: String,
+    #[serde(default)]
+    pub note: Option<String>,
+    pub color: HighlightColor,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[ts(type = "number")]
+    pub created_unix_secs: u64,
+    #[ts(type = "number")]
+    pub updated_unix_secs: u64,
+}
+
+/// Fields supplied by the UI when creating or editing an annotation.
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export)]
+pub struct AnnotationInput {
+    pub sentence_start: usize,
+    pub sentence_end: usize,
+    #[serde(default)]
+    #[ts(optional)]
+    pub note: Option<String>,
+    /// Falls back to the day highlight color when omitted.
+    #[serde(default)]
+    #[ts(optional)]
+    pub color: Option<HighlightColor>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum AnnotationExportFormat {
+    Markdown,
+    Json,
+    Csv,
+}
+
+impl AnnotationExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Json => "json",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+/// Everything an export writes for one book.
+#[derive(Debug, Clone, Serialize)]
+pub struct AnnotationExport {
+    pub book_title: String,
+    pub source_path: String,
+    pub exported_unix_secs: u64,
+    pub annotations: Vec<AnnotationExportEntry>,
+}
+
+/// One annotation with the reading context around it.
+#[derive(Debug, Clone, Serialize)]
+pub struct AnnotationExportEntry {
+    pub id: u64,
+    /// 1-based page of the first covered sentence.
+    pub page: usize,
+    pub chapter: Option<String>,
+    pub text: String,
+    pub context_before: String,
+    pub context_after: String,
+    pub note: Option<String>,
+    pub tags: Vec<String>,
+    pub color: HighlightColor,
+    pub created_unix_secs: u64,
+    pub updated_unix_secs: u64,
+}
+
+/// On-disk form of a book's annotations.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnnotationSet {
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+}
+
+pub fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// `YYYY-MM-DD HH:MM UTC` for a Unix timestamp.
+pub fn format_unix_utc(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let minutes_of_day = (secs % 86_400) / 60;
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+        minutes_of_day / 60,
+        minutes_of_day % 60
+    )
+}
+
+/// Proleptic Gregorian date for a count of days since 1970-01-01.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+pub fn render_export(export: &AnnotationExport, format: AnnotationExportFormat) -> String {
+    match format {
+        AnnotationExportFormat::Markdown => render_markdown(export),
+        AnnotationExportFormat::Json => {
+            serde_json::to_string_pretty(export).unwrap_or_else(|_| "{}".to_string())
+        }
+        AnnotationExportFormat::Csv => render_csv(export),
+    }
+}
+
+/// Obsidian-friendly notes: one section per chapter, each highlight as a
+/// block quote with a `^ll-<id>` block ID so it can be linked.
+fn render_markdown(export: &AnnotationExport) -> String {
+    let mut out = format!(
+        "# {}\n\n- Source: `{}`\n- Exported: {}\n- Annotations: {}\n",
+        export.book_title,
+        export.source_path,
+        format_unix_utc(export.exported_unix_secs),
+        export.annotations.len()
+    );
+    let mut section: Option<Option<&str>> = None;
+    for entry in &export.annotations {
+        let chapter = entry.chapter.as_deref();
+        if section != Some(chapter) {
+            out.push_str(&format!("\n## {}\n", chapter.unwrap_or("Untitled section")));
+            section = Some(chapter);
+        }
+        out.push('\n');
+        for line in entry.text.lines() {
+            out.push_str(&format!("> {line}\n"));
+        }
+        out.push_str(&format!("> ^ll-{}\n\n", entry.id));
+        if !entry.context_before.is_empty() || !entry.context_after.is_empty() {
+            out.push_str(&format!(
+                "- Context: …{} **[…]** {}…\n",
+                entry.context_before, entry.context_after
+            ));
+        }
+        if let Some(note) = &entry.note {
+            out.push_str(&format!("- Note: {}\n", note.replace('\n', " ")));
+        }
+        if !entry.tags.is_empty() {
+            let tags: Vec<String> = entry
+                .tags
+                .iter()
+                .map(|tag| format!("#{}", tag.replace(char::is_whitespace, "-")))
+                .collect();
+            out.push_str(&format!("- Tags: {}\n", tags.join(" ")));
+        }
+        out.push_str(&format!(
+            "- Page: {}\n- Created: {}\n",
+            entry.page,
+            format_unix_utc(entry.created_unix_secs)
+        ));
+        if entry.updated_unix_secs != entry.created_unix_secs {
+            out.push_str(&format!(
+                "- Updated: {}\n",
+                format_unix_utc(entry.updated_unix_secs)
+            ));
+        }
+    }
+    out
+}
+
+fn render_csv(export: &AnnotationExport) -> String {
+    let mut out = String::from(
+        "book_title,chapter,page,text,context_before,context_after,note,tags,created,updated\r\n",
+    );
+    for entry in &export.annotations {
+        let fields = [
+            export.book_title.clone(),
+            entry.chapter.clone().unwrap_or_default(),
+            entry.page.to_string(),
+            entry.text.clone(),
+            entry.context_before.clone(),
+            entry.context_after.clone(),
+            entry.note.clone().unwrap_or_default(),
+            entry.tags.join(";"),
+            format_unix_utc(entry.created_unix_secs),
+            format_unix_utc(entry.updated_unix_secs),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        out.push_str(&row.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// RFC 4180 quoting: fields with commas, quotes or line breaks are wrapped in
+/// quotes with inner quotes doubled.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Trimmed tags with empties and duplicates dropped, in input order.
+pub fn clean_tags(tags: Vec<String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_string();
+        if !tag.is_empty() && !out.contains(&tag) {
+            out.push(tag);
+        }
+    }
+    out
+}
+
+/// Move `annotation` onto `sentences` (all display sentences in reading
+/// order). The stored start text is looked for nearest the old start index,
+/// then the end text nearest the old end. When a text cannot be found the
+/// old index is kept (clamped) so annotations never disappear.
+pub fn reanchor<S: AsRef<str>>(annotation: &mut Annotation, sentences: &[S]) {
+    if sentences.is_empty() {
+        annotation.sentence_start = 0;
+        annotation.sentence_end = 0;
+        return;
+    }
+    let span = annotation
+        .sentence_end
+        .saturating_sub(annotation.sentence_start)
+        .max(1);
+    let last = sentences.len() - 1;
+    let start = text_utils::find_sentence_near(
+        sentences,
+        &annotation.start_text,
+        annotation.sentence_start,
+        0,
+    )
+    .unwrap_or(annotation.sentence_start.min(last));
+    let expected_end = start + span - 1;
+    let end = text_utils::find_sentence_near(sentences, &annotation.end_text, expected_end, start)
+        .unwrap_or(expected_end.min(last));
+    annotation.sentence_start = start;
+    annotation.sentence_end = end.max(start) + 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn annotation(start: usize, end: usize, start_text: &str, end_text: &str) -> Annotation {
+        Annotation {
+            id: 1,
+            sentence_start: start,
+            sentence_end: end,
+            start_text: start_text.to_string(),
+            end_text: end_text.to_string(),
+            note: None,
+            color: HighlightColor {
+                r: 1.0,
+                g: 0.8,
+                b: 0.2,
+                a: 0.3,
+            },
+            tags: Vec::new(),
+            created_unix_secs: 0,
+            updated_unix_secs: 0,
+        }
+    }
+
+    #[test]
+    fn reanchor_follows_stored_text_after_sentences_shift() {
+        let mut marked = annotation(2, 4, "Gamma here.", "Delta  there.");
+        let sentences = [
+            "Alpha.",
+            "Inserted.",
+            "Beta.",
+            "\n\nGamma here.",
+            "Delta there.",
+            "Epsilon.",
+        ];
+        reanchor(&mut marked, &sentences);
+        assert_eq!((marked.sentence_start, marked.sentence_end), (3, 5));
+    }
+
+    #[test]
+    fn reanchor_accepts_split_sentences_and_keeps_unknown_text_in_bounds() {
+        let mut split = annotation(
+            1,
+            2,
+            "One long sentence that was split.",
+            "One long sentence that was split.",
+        );
+        let sentences = ["Intro.", "One long sentence", "that was split.", "Outro."];
+        reanchor(&mut split, &sentences);
+        assert_eq!((split.sentence_start, split.sentence_end), (1, 2));
+
+        let mut lost = annotation(9, 11, "Gone.", "Also gone.");
+        reanchor(&mut lost, &sentences);
+        assert_eq!((lost.sentence_start, lost.sentence_end), (3, 4));
+    }
+
+    fn sample_export() -> AnnotationExport {
+        AnnotationExport {
+            book_title: "Moby Dick".to_string(),
+            source_path: "/books/moby.epub".to_string(),
+            exported_unix_secs: 1_700_000_000,
+            annotations: vec![AnnotationExportEntry {
+                id: 4,
+                page: 2,
+                chapter: Some("Loomings".to_string()),
+                text: "Call me Ishmael.".to_string(),
+                context_before: "Chapter 1.".to_string(),
+                context_after: "Some years ago, never mind how long.".to_string(),
+                note: Some("Famous, \"opening\"".to_string()),
+                tags: vec!["opening line".to_string(), "quote".to_string()],
+                color: annotation(0, 1, "", "").color,
+                created_unix_secs: 0,
+                updated_unix_secs: 0,
+            }],
+        }
+    }
+
+    #[test]
+    fn markdown_export_uses_block_quotes_grouped_by_chapter() {
+        let markdown = render_export(&sample_export(), AnnotationExportFormat::Markdown);
+        assert!(markdown.starts_with("# Moby Dick\n"));
+        assert!(markdown.contains("- Exported: 2023-11-14 22:13 UTC\n"));
+        assert!(markdown.contains("## Loomings\n\n> Call me Ishmael.\n> ^ll-4\n"));
+        assert!(markdown.contains("- Tags: #opening-line #quote\n"));
+        assert!(markdown.contains("- Page: 2\n- Created: 1970-01-01 00:00 UTC\n"));
+    }
+
+    #[test]
+    fn csv_export_quotes_fields_and_json_keeps_context() {
+        let csv = render_export(&sample_export(), AnnotationExportFormat::Csv);
+        let rows: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(rows.len(), 3);
+        assert!(rows[0].starts_with("book_title,chapter,page,text"));
+        assert!(rows[1].starts_with(
+            "Moby Dick,Loomings,2,Call me Ishmael.,Chapter 1.,\"Some years ago, never mind how long.\",\"Famous, \"\"opening\"\"\",opening line;quote,"
+        ));
+
+        let json: serde_json::Value = serde_json::from_str(&render_export(
+            &sample_export(),
+            AnnotationExportFormat::Json,
+        ))
+        .expect("valid json");
+        assert_eq!(json["annotations"][0]["context_before"], "Chapter 1.");
+        assert_eq!(json["book_title"], "Moby Dick");
+    }
+
+    #[test]
+    fn clean_tags_trims_and_deduplicates() {
+        assert_eq!(
+            clean_tags(vec![
+                " idea ".into(),
+                "".into(),
+                "quote".into(),
+                "idea".into()
+            ]),
+            vec!["idea".to_string(), "quote".to_string()]
+        );
+    }
+}
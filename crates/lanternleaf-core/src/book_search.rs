@@ -0,0 +1,305 @@
+//! Whole-book search over the paginated display sentences.
+//!
+//! The reader's in-page search (`SessionCommand::SearchSetQuery`) only sees
+//! the current page. This module scans every page, optionally including the
+//! normalized audio text, and is built to run off the state lock: the session
+//! hands out a [`BookSearchCorpus`] snapshot and the scan checks a
+//! [`CancellationToken`] between pages.
+
+use crate::{cancellation::CancellationToken, normalizer, text_utils};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use ts_rs::TS;
+
+/// Upper bound on collected hits; later matches are counted as truncated.
+pub const MAX_BOOK_SEARCH_HITS: usize = 10_000;
+pub const DEFAULT_BOOK_SEARCH_PAGE_SIZE: usize = 50;
+const SNIPPET_CONTEXT_CHARS: usize = 60;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct BookSearchOptions {
+    pub case_sensitive: bool,
+    pub whole_word: bool,
+    /// Treat the query as plain text instead of a regex.
+    pub literal: bool,
+    /// Also match the normalized audio text (expanded numbers, abbreviations...).
+    pub include_audio: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct BookSearchHit {
+    pub page: usize,
+    /// Page-local display sentence index.
+    pub sentence_idx: usize,
+    /// Book-wide display sentence index.
+    pub global_idx: usize,
+    pub snippet_before: String,
+    pub snippet_match: String,
+    pub snippet_after: String,
+    /// The match was found in the normalized audio text only.
+    pub in_audio: bool,
+}
+
+/// One page of hits from a whole-book search.
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct BookSearchResults {
+    pub query: String,
+    pub options: BookSearchOptions,
+    pub total_hits: usize,
+    pub offset: usize,
+    pub hits: Vec<BookSearchHit>,
+    /// More than [`MAX_BOOK_SEARCH_HITS`] sentences matched.
+    pub truncated: bool,
+}
+
+/// Sentences (and, for audio search, the book normalizer) detached from the
+/// session so a scan can run without holding the reader.
+#[derive(Debug, Clone)]
+pub struct BookSearchCorpus {
+    pub source_path: PathBuf,
+    pub page_sentences: Vec<Vec<String>>,
+    pub normalizer: Option<normalizer::TextNormalizer>,
+}
+
+/// Completed hits for a query, kept by the session so result pages can be
+/// served without rescanning.
+#[derive(Debug, Clone)]
+pub struct BookSearchState {
+    pub query: String,
+    pub options: BookSearchOptions,
+    pub hits: Vec<BookSearchHit>,
+    pub truncated: bool,
+}
+
+impl BookSearchState {
+    pub fn results(&self, offset: usize, limit: usize) -> BookSearchResults {
+        let offset = offset.min(self.hits.len());
+        let end = (offset + limit.max(1)).min(self.hits.len());
+        BookSearchResults {
+            query: self.query.clone(),
+            options: self.options.clone(),
+            total_hits: self.hits.len(),
+            offset,
+            hits: self.hits[offset..end].to_vec(),
+            truncated: self.truncated,
+        }
+    }
+}
+
+pub fn build_query_regex(query: &str, options: &BookSearchOptions) -> Result<Regex, String> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err("Search query is empty".to_string());
+    }
+    let pattern = if options.literal {
+        regex::escape(query)
+    } else {
+        query.to_string()
+    };
+    let pattern = if options.whole_word {
+        format!(r"\b(?:{pattern})\b")
+    } else {
+        pattern
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!options.case_sensitive)
+        .build()
+        .map_err(|err| format!("Invalid search pattern: {err}"))
+}
+
+/// Scan every page in reading order. Returns `Err` on an invalid query or
+/// when `cancel` fires; callers tell the two apart with `cancel.is_cancelled()`.
+pub fn search(
+    corpus: &BookSearchCorpus,
+    query: &str,
+    options: &BookSearchOptions,
+    cancel: &CancellationToken,
+) -> Result<BookSearchState, String> {
+    let regex = build_query_regex(query, options)?;
+    let mut hits = Vec::new();
+    let mut truncated = false;
+    let mut page_base = 0;
+    'pages: for (page, sentences) in corpus.page_sentences.iter().enumerate() {
+        if cancel.is_cancelled() {
+            return Err("Book search cancelled".to_string());
+        }
+        let mut matched = vec![false; sentences.len()];
+        for (sentence_idx, sentence) in sentences.iter().enumerate() {
+            let display = text_utils::strip_display_markers(sentence);
+            if let Some(found) = regex.find(&display) {
+                matched[sentence_idx] = true;
+                if hits.len() == MAX_BOOK_SEARCH_HITS {
+                    truncated = true;
+                    break 'pages;
+                }
+                hits.push(hit(page, sentence_idx, page_base, &display, found, false));
+            }
+        }
+        if let Some(normalizer) = corpus.normalizer.as_ref() {
+            let plan = normalizer.plan_page_cached(&corpus.source_path, page, sentences);
+            let mut audio_hits = Vec::new();
+            for (audio_idx, audio) in plan.audio_sentences.iter().enumerate() {
+                let Some(&sentence_idx) = plan.audio_to_display.get(audio_idx) else {
+                    continue;
+                };
+                if matched.get(sentence_idx).copied().unwrap_or(true) {
+                    continue;
+                }
+                if let Some(found) = regex.find(audio) {
+                    matched[sentence_idx] = true;
+                    audio_hits.push(hit(page, sentence_idx, page_base, audio, found, true));
+                }
+            }
+            for audio_hit in audio_hits {
+                if hits.len() == MAX_BOOK_SEARCH_HITS {
+                    truncated = true;
+                    break 'pages;
+                }
+                let pos = hits.partition_point(|existing: &BookSearchHit| {
+                    existing.global_idx < audio_hit.global_idx
+                });
+                hits.insert(pos, audio_hit);
+            }
+        }
+        page_base += sentences.len();
+    }
+    Ok(BookSearchState {
+        query: query.trim().to_string(),
+        options: options.clone(),
+        hits,
+        truncated,
+    })
+}
+
+fn hit(
+    page: usize,
+    sentence_idx: usize,
+    page_base: usize,
+    text: &str,
+    found: regex::Match<'_>,
+    in_audio: bool,
+) -> BookSearchHit {
+    let (snippet_before, snippet_after) = snippet_context(text, found.start(), found.end());
+    BookSearchHit {
+        page,
+        sentence_idx,
+        global_idx: page_base + sentence_idx,
+        snippet_before,
+        snippet_match: found.as_str().to_string(),
+        snippet_after,
+        in_audio,
+    }
+}
+
+/// Up to `SNIPPET_CONTEXT_CHARS` of context on each side of `text[start..end]`,
+/// with an ellipsis where the sentence was cut.
+pub(crate) fn snippet_context(text: &str, start: usize, end: usize) -> (String, String) {
+    let before = &text[..start];
+    let after = &text[end..];
+    let before_chars = before.chars().count();
+    let snippet_before = if before_chars > SNIPPET_CONTEXT_CHARS {
+        let tail: String = before
+            .chars()
+            .skip(before_chars - SNIPPET_CONTEXT_CHARS)
+            .collect();
+        format!("…{}", tail.trim_start())
+    } else {
+        before.trim_start().to_string()
+    };
+    let snippet_after = if after.chars().count() > SNIPPET_CONTEXT_CHARS {
+        let head: String = after.chars().take(SNIPPET_CONTEXT_CHARS).collect();
+        format!("{}…", head.trim_end())
+    } else {
+        after.trim_end().to_string()
+    };
+    (snippet_before, snippet_after)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn corpus(pages: &[&[&str]]) -> BookSearchCorpus {
+        BookSearchCorpus {
+            source_path: PathBuf::from("/tmp/search.epub"),
+            page_sentences: pages
+                .iter()
+                .map(|page| page.iter().map(|sentence| sentence.to_string()).collect())
+                .collect(),
+            normalizer: None,
+        }
+    }
+
+    #[test]
+    fn search_scans_every_page_with_word_case_and_literal_options() {
+        let corpus = corpus(&[
+            &["The cat sat.", "Concatenate (cat) strings."],
+            &["A CAT appears.", "No match here."],
+        ]);
+        let cancel = CancellationToken::new();
+        let positions = |query: &str, options: BookSearchOptions| {
+            search(&corpus, query, &options, &cancel)
+                .expect("valid query")
+                .hits
+                .iter()
+                .map(|hit| (hit.page, hit.sentence_idx, hit.global_idx))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            positions("cat", BookSearchOptions::default()),
+            vec![(0, 0, 0), (0, 1, 1), (1, 0, 2)]
+        );
+        let whole_word = BookSearchOptions {
+            whole_word: true,
+            case_sensitive: true,
+            ..Default::default()
+        };
+        assert_eq!(positions("cat", whole_word), vec![(0, 0, 0), (0, 1, 1)]);
+        let literal = BookSearchOptions {
+            literal: true,
+            ..Default::default()
+        };
+        assert_eq!(positions("(cat)", literal), vec![(0, 1, 1)]);
+        assert!(search(&corpus, "(cat", &BookSearchOptions::default(), &cancel).is_err());
+    }
+
+    #[test]
+    fn hits_carry_trimmed_snippets_and_results_paginate() {
+        let long_prefix = "word ".repeat(30);
+        let sentence = format!("{long_prefix}needle in the haystack.");
+        let corpus = BookSearchCorpus {
+            page_sentences: vec![vec![sentence; 3]],
+            ..corpus(&[])
+        };
+        let state = search(
+            &corpus,
+            "needle",
+            &BookSearchOptions::default(),
+            &CancellationToken::new(),
+        )
+        .expect("valid query");
+        let hit = &state.hits[0];
+        assert!(hit.snippet_before.starts_with('…'));
+        assert_eq!(hit.snippet_before.chars().count(), SNIPPET_CONTEXT_CHARS + 1);
+        assert_eq!(hit.snippet_match, "needle");
+        assert_eq!(hit.snippet_after, " in the haystack.");
+
+        let page = state.results(1, 5);
+        assert_eq!((page.total_hits, page.offset, page.hits.len()), (3, 1, 2));
+        assert_eq!(page.hits[0].sentence_idx, 1);
+    }
+
+    #[test]
+    fn cancelled_search_stops_before_scanning() {
+        let corpus = corpus(&[&["The cat sat."]]);
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        assert!(search(&corpus, "cat", &BookSearchOptions::default(), &cancel).is_err());
+    }
+}
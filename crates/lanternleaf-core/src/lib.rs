@@ -1,3 +1,6 @@
+pub mod activity;
+pub mod annotations;
+pub mod book_search;
 #[path = "../../../src/cache.rs"]
 pub mod cache;
 #[path = "../../../src/calibre.rs"]
@@ -10,6 +13,11 @@ pub mod cancellation;
 pub mod config;
 #[path = "../../../src/epub_loader.rs"]
 pub mod epub_loader;
+pub mod library_index;
+#[path = "../../../src/math_speech.rs"]
+pub mod math_speech;
+#[path = "../../../src/normalization_store.rs"]
+pub mod normalization_store;
 #[path = "../../../src/normalizer.rs"]
 pub mod normalizer;
 #[path = "../../../src/pagination.rs"]
@@ -17,6 +25,7 @@ pub mod pagination;
 #[path = "../../../src/quack_check/mod.rs"]
 pub mod quack_check;
 pub mod session;
+pub mod speech_rate;
 #[path = "../../../src/text_utils.rs"]
 pub mod text_utils;
 #[path = "../../../src/tts.rs"]
@@ -0,0 +1,559 @@
+//! Library-wide full-text index over the cached `tts_text` artifacts.
+//!
+//! Every opened book leaves `content/tts-text.txt` in its cache directory.
+//! The index maps each word to the books and sentence ordinals containing it
+//! and lives in a single JSON file under the cache root. Opening a book
+//! re-indexes it; a search first syncs with the cache so books whose artifact
+//! changed or disappeared since the last index are picked up.
+
+use crate::{book_search, cache, text_utils};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+use tracing::{debug, warn};
+use ts_rs::TS;
+
+const LIBRARY_INDEX_VERSION: u32 = 1;
+const MIN_TOKEN_CHARS: usize = 2;
+const MAX_HITS_PER_BOOK: usize = 5;
+pub const DEFAULT_LIBRARY_SEARCH_BOOKS: usize = 20;
+
+/// Serializes load-modify-save cycles between book opens and searches.
+static INDEX_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct LibrarySearchHit {
+    /// Sentence ordinal within the book's `tts_text`; a hint for re-anchoring.
+    pub sentence_idx: usize,
+    pub sentence_text: String,
+    pub snippet_before: String,
+    pub snippet_match: String,
+    pub snippet_after: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct LibrarySearchBook {
+    pub source_path: String,
+    pub title: String,
+    /// Sentences containing every query word.
+    pub total_hits: usize,
+    /// Exact phrase matches first, then reading order.
+    pub hits: Vec<LibrarySearchHit>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct TextFingerprint {
+    len: u64,
+    modified_unix_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedBook {
+    id: u32,
+    source_path: PathBuf,
+    title: String,
+    tts_text_path: PathBuf,
+    fingerprint: TextFingerprint,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Posting {
+    book: u32,
+    /// Ascending sentence ordinals.
+    sentences: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryIndex {
+    version: u32,
+    next_book_id: u32,
+    books: Vec<IndexedBook>,
+    postings: BTreeMap<String, Vec<Posting>>,
+}
+
+impl Default for LibraryIndex {
+    fn default() -> Self {
+        Self {
+            version: LIBRARY_INDEX_VERSION,
+            next_book_id: 0,
+            books: Vec::new(),
+            postings: BTreeMap::new(),
+        }
+    }
+}
+
+impl LibraryIndex {
+    /// Missing, unreadable or outdated index files load as an empty index.
+    pub fn load(path: &Path) -> Self {
+        let Ok(raw) = fs::read_to_string(path) else {
+            return Self::default();
+        };
+        match serde_json::from_str::<Self>(&raw) {
+            Ok(index) if index.version == LIBRARY_INDEX_VERSION => index,
+            Ok(index) => {
+                debug!(
+                    path = %path.display(),
+                    version = index.version,
+                    "Discarding library index with an outdated version"
+                );
+                Self::default()
+            }
+            Err(err) => {
+                warn!(path = %path.display(), "Failed to parse library index: {err}");
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+        }
+        let payload = serde_json::to_string(self)
+            .map_err(|err| format!("Failed to serialize library index: {err}"))?;
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, payload)
+            .map_err(|err| format!("Failed to write {}: {err}", tmp_path.display()))?;
+        fs::rename(&tmp_path, path)
+            .map_err(|err| format!("Failed to replace {}: {err}", path.display()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.books.is_empty()
+    }
+
+    fn is_current(&self, source_path: &Path, tts_text_path: &Path) -> bool {
+        let Some(fingerprint) = fingerprint(tts_text_path) else {
+            return false;
+        };
+        self.books.iter().any(|book| {
+            book.source_path == source_path
+                && book.tts_text_path == tts_text_path
+                && book.fingerprint == fingerprint
+        })
+    }
+
+    /// Index `tts_text_path` as the text of `source_path`, replacing any
+    /// earlier entry for that source. Returns `false` when the artifact is
+    /// unchanged since it was last indexed.
+    pub fn index_book(
+        &mut self,
+        source_path: &Path,
+        title: &str,
+        tts_text_path: &Path,
+    ) -> Result<bool, String> {
+        if self.is_current(source_path, tts_text_path) {
+            return Ok(false);
+        }
+        let fingerprint = fingerprint(tts_text_path)
+            .ok_or_else(|| format!("Missing tts_text artifact {}", tts_text_path.display()))?;
+        let text = fs::read_to_string(tts_text_path)
+            .map_err(|err| format!("Failed to read {}: {err}", tts_text_path.display()))?;
+        self.remove_book(source_path);
+
+        let id = self.next_book_id;
+        self.next_book_id = self.next_book_id.wrapping_add(1);
+        let mut by_token: HashMap<String, Vec<u32>> = HashMap::new();
+        for (sentence_idx, sentence) in index_sentences(&text).iter().enumerate() {
+            let mut seen = HashSet::new();
+            for token in tokenize(sentence) {
+                if seen.insert(token.clone()) {
+                    by_token.entry(token).or_default().push(sentence_idx as u32);
+                }
+            }
+        }
+        for (token, sentences) in by_token {
+            self.postings.entry(token).or_default().push(Posting {
+                book: id,
+                sentences,
+            });
+        }
+        self.books.push(IndexedBook {
+            id,
+            source_path: source_path.to_path_buf(),
+            title: title.to_string(),
+            tts_text_path: tts_text_path.to_path_buf(),
+            fingerprint,
+        });
+        Ok(true)
+    }
+
+    pub fn remove_book(&mut self, source_path: &Path) -> bool {
+        let ids: HashSet<u32> = self
+            .books
+            .iter()
+            .filter(|book| book.source_path == source_path)
+            .map(|book| book.id)
+            .collect();
+        self.drop_books(&ids)
+    }
+
+    fn drop_books(&mut self, ids: &HashSet<u32>) -> bool {
+        if ids.is_empty() {
+            return false;
+        }
+        self.books.retain(|book| !ids.contains(&book.id));
+        self.postings.retain(|_, postings| {
+            postings.retain(|posting| !ids.contains(&posting.book));
+            !postings.is_empty()
+        });
+        true
+    }
+
+    /// Bring the index in line with the cached artifacts: drop books whose
+    /// artifact is gone and index new or changed ones. When a source has
+    /// several cache directories (its content changed), the newest wins.
+    /// Returns whether anything changed.
+    pub fn sync(
+        &mut self,
+        cached: &[cache::CachedTtsText],
+        title_for: impl Fn(&Path) -> String,
+    ) -> bool {
+        let mut newest: HashMap<&Path, (&cache::CachedTtsText, u64)> = HashMap::new();
+        for entry in cached {
+            let Some(fingerprint) = fingerprint(&entry.tts_text_path) else {
+                continue;
+            };
+            let modified = fingerprint.modified_unix_secs;
+            if newest
+                .get(entry.source_path.as_path())
+                .is_none_or(|(_, seen)| *seen < modified)
+            {
+                newest.insert(entry.source_path.as_path(), (entry, modified));
+            }
+        }
+        let live: HashSet<&Path> = newest
+            .values()
+            .map(|(entry, _)| entry.tts_text_path.as_path())
+            .collect();
+        let stale: HashSet<u32> = self
+            .books
+            .iter()
+            .filter(|book| !live.contains(book.tts_text_path.as_path()))
+            .map(|book| book.id)
+            .collect();
+        let mut changed = self.drop_books(&stale);
+        for (entry, _) in newest.values() {
+            if self.is_current(&entry.source_path, &entry.tts_text_path) {
+                continue;
+            }
+            let title = title_for(&entry.source_path);
+            match self.index_book(&entry.source_path, &title, &entry.tts_text_path) {
+                Ok(updated) => changed |= updated,
+                Err(err) => warn!(
+                    path = %entry.source_path.display(),
+                    "Failed to index cached book: {err}"
+                ),
+            }
+        }
+        changed
+    }
+
+    /// Books whose sentences contain every word of `query`, ranked by exact
+    /// phrase matches and then by hit count.
+    pub fn search(&self, query: &str, max_books: usize) -> Vec<LibrarySearchBook> {
+        let mut tokens: Vec<String> = Vec::new();
+        for token in tokenize(query) {
+            if !tokens.contains(&token) {
+                tokens.push(token);
+            }
+        }
+        let Some(candidates) = self.candidate_sentences(&tokens) else {
+            return Vec::new();
+        };
+        let (Some(phrase), Some(word)) = (phrase_regex(&tokens), word_regex(&tokens)) else {
+            return Vec::new();
+        };
+
+        let mut ranked: Vec<(usize, LibrarySearchBook)> = Vec::new();
+        for (book_id, sentence_ids) in candidates {
+            let Some(book) = self.books.iter().find(|book| book.id == book_id) else {
+                continue;
+            };
+            let Ok(text) = fs::read_to_string(&book.tts_text_path) else {
+                continue;
+            };
+            let sentences = index_sentences(&text);
+            let mut hits: Vec<(bool, LibrarySearchHit)> = sentence_ids
+                .iter()
+                .filter_map(|&sentence_idx| {
+                    let sentence = sentences.get(sentence_idx as usize)?;
+                    let (found, exact) = match phrase.find(sentence) {
+                        Some(found) => (found, true),
+                        None => (word.find(sentence)?, false),
+                    };
+                    let (snippet_before, snippet_after) =
+                        book_search::snippet_context(sentence, found.start(), found.end());
+                    Some((
+                        exact,
+                        LibrarySearchHit {
+                            sentence_idx: sentence_idx as usize,
+                            sentence_text: sentence.clone(),
+                            snippet_before,
+                            snippet_match: found.as_str().to_string(),
+                            snippet_after,
+                        },
+                    ))
+                })
+                .collect();
+            let exact_hits = hits.iter().filter(|(exact, _)| *exact).count();
+            hits.sort_by_key(|(exact, hit)| (!*exact, hit.sentence_idx));
+            ranked.push((
+                exact_hits,
+                LibrarySearchBook {
+                    source_path: book.source_path.to_string_lossy().to_string(),
+                    title: book.title.clone(),
+                    total_hits: sentence_ids.len(),
+                    hits: hits
+                        .into_iter()
+                        .take(MAX_HITS_PER_BOOK)
+                        .map(|(_, hit)| hit)
+                        .collect(),
+                },
+            ));
+        }
+        ranked.sort_by(|a, b| {
+            b.0.cmp(&a.0)
+                .then(b.1.total_hits.cmp(&a.1.total_hits))
+                .then_with(|| a.1.title.cmp(&b.1.title))
+        });
+        ranked
+            .into_iter()
+            .take(max_books.max(1))
+            .map(|(_, book)| book)
+            .collect()
+    }
+
+    /// Per book, the sentences that contain every token.
+    fn candidate_sentences(&self, tokens: &[String]) -> Option<HashMap<u32, Vec<u32>>> {
+        let mut candidates: Option<HashMap<u32, Vec<u32>>> = None;
+        for token in tokens {
+            let postings = self.postings.get(token)?;
+            let next: HashMap<u32, Vec<u32>> = match candidates.take() {
+                None => postings
+                    .iter()
+                    .map(|posting| (posting.book, posting.sentences.clone()))
+                    .collect(),
+                Some(current) => postings
+                    .iter()
+                    .filter_map(|posting| {
+                        let shared =
+                            intersect_sorted(current.get(&posting.book)?, &posting.sentences);
+                        (!shared.is_empty()).then_some((posting.book, shared))
+                    })
+                    .collect(),
+            };
+            if next.is_empty() {
+                return None;
+            }
+            candidates = Some(next);
+        }
+        candidates
+    }
+}
+
+/// Re-index one book after it was opened. Failures are logged; the index is
+/// a convenience and never blocks opening.
+pub fn index_source(source_path: &Path) {
+    let _guard = INDEX_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let index_path = cache::library_index_path();
+    let tts_text_path = cache::cached_tts_text_path(source_path);
+    let mut index = LibraryIndex::load(&index_path);
+    if index.is_current(source_path, &tts_text_path) {
+        return;
+    }
+    let title = cache::infer_recent_title(source_path);
+    match index.index_book(source_path, &title, &tts_text_path) {
+        Ok(true) => match index.save(&index_path) {
+            Ok(()) => debug!(path = %source_path.display(), "Updated library index"),
+            Err(err) => warn!("Failed to save library index: {err}"),
+        },
+        Ok(false) => {}
+        Err(err) => warn!(path = %source_path.display(), "Failed to index book: {err}"),
+    }
+}
+
+/// Sync the on-disk index with the cache, then search it.
+pub fn search_library(query: &str, max_books: usize) -> Vec<LibrarySearchBook> {
+    let _guard = INDEX_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let index_path = cache::library_index_path();
+    let mut index = LibraryIndex::load(&index_path);
+    if index.sync(&cache::list_cached_tts_texts(), cache::infer_recent_title)
+        && let Err(err) = index.save(&index_path)
+    {
+        warn!("Failed to save library index: {err}");
+    }
+    index.search(query, max_books)
+}
+
+fn fingerprint(path: &Path) -> Option<TextFingerprint> {
+    let metadata = fs::metadata(path).ok()?;
+    let modified_unix_secs = metadata
+        .modified()
+        .ok()
+        .and_then(|ts| ts.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    Some(TextFingerprint {
+        len: metadata.len(),
+        modified_unix_secs,
+    })
+}
+
+/// Sentence list shared by indexing and searching so ordinals line up.
+fn index_sentences(text: &str) -> Vec<String> {
+    text_utils::split_sentences(text)
+        .iter()
+        .map(|sentence| text_utils::anchor_text(sentence))
+        .filter(|sentence| !sentence.is_empty())
+        .collect()
+}
+
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| word.chars().count() >= MIN_TOKEN_CHARS)
+        .map(str::to_lowercase)
+}
+
+fn phrase_regex(tokens: &[String]) -> Option<Regex> {
+    let pattern = tokens
+        .iter()
+        .map(|token| regex::escape(token))
+        .collect::<Vec<_>>()
+        .join(r"\W+");
+    RegexBuilder::new(&format!(r"\b{pattern}\b"))
+        .case_insensitive(true)
+        .build()
+        .ok()
+}
+
+fn word_regex(tokens: &[String]) -> Option<Regex> {
+    let pattern = tokens
+        .iter()
+        .map(|token| regex::escape(token))
+        .collect::<Vec<_>>()
+        .join("|");
+    RegexBuilder::new(&format!(r"\b(?:{pattern})\b"))
+        .case_insensitive(true)
+        .build()
+        .ok()
+}
+
+fn intersect_sorted(left: &[u32], right: &[u32]) -> Vec<u32> {
+    let mut shared = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() && j < right.len() {
+        match left[i].cmp(&right[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                shared.push(left[i]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    shared
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+
+    fn scratch_dir() -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let dir = std::env::temp_dir().join(format!(
+            "lanternleaf-library-index-{}-{nanos}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).expect("create scratch dir");
+        dir
+    }
+
+    fn cached(dir: &Path, name: &str, text: &str) -> cache::CachedTtsText {
+        let tts_text_path = dir.join(format!("{name}.txt"));
+        fs::write(&tts_text_path, text).expect("write tts text");
+        cache::CachedTtsText {
+            source_path: dir.join(format!("{name}.epub")),
+            tts_text_path,
+        }
+    }
+
+    #[test]
+    fn search_ranks_phrase_matches_and_returns_snippets() {
+        let dir = scratch_dir();
+        let moby = cached(
+            &dir,
+            "moby",
+            "Call me Ishmael. The whale was white. A white whale again.",
+        );
+        let notes = cached(&dir, "notes", "The whale sang. It was white as snow.");
+        let mut index = LibraryIndex::default();
+        let title = |path: &Path| path.file_stem().unwrap().to_string_lossy().to_string();
+        assert!(index.sync(&[moby.clone(), notes.clone()], title));
+
+        let results = index.search("White whale", 10);
+        assert_eq!(results.len(), 1);
+        let book = &results[0];
+        assert_eq!(book.title, "moby");
+        assert_eq!(book.total_hits, 2);
+        assert_eq!(book.hits[0].sentence_idx, 2);
+        assert_eq!(book.hits[0].snippet_before, "A ");
+        assert_eq!(book.hits[0].snippet_match, "white whale");
+        assert_eq!(book.hits[1].snippet_match, "whale");
+
+        let results = index.search("whale", 10);
+        assert_eq!(
+            results
+                .iter()
+                .map(|book| book.title.as_str())
+                .collect::<Vec<_>>(),
+            vec!["moby", "notes"]
+        );
+        assert!(index.search("kraken", 10).is_empty());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn sync_skips_unchanged_books_and_drops_removed_ones() {
+        let dir = scratch_dir();
+        let book = cached(&dir, "book", "Some quiet words.");
+        let other = cached(&dir, "other", "Other quiet words.");
+        let title = |_: &Path| "Title".to_string();
+        let mut index = LibraryIndex::default();
+        assert!(index.sync(&[book.clone(), other.clone()], title));
+        assert!(!index.sync(&[book.clone(), other.clone()], title));
+
+        let index_path = dir.join("library-index.json");
+        index.save(&index_path).expect("save index");
+        let mut index = LibraryIndex::load(&index_path);
+        assert_eq!(index.search("quiet", 10).len(), 2);
+
+        assert!(index.sync(std::slice::from_ref(&book), title));
+        let results = index.search("quiet", 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].source_path, book.source_path.to_string_lossy());
+        assert_eq!(index.books.len(), 1);
+
+        assert!(index.remove_book(&book.source_path));
+        assert!(index.is_empty() && index.postings.is_empty());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
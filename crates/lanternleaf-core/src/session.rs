@@ -1,15 +1,21 @@
 use crate::{
-    cancellation::CancellationToken, config, epub_loader, normalizer, pagination, text_utils,
+    annotations, book_search, cancellation::CancellationToken, config, epub_loader, normalizer,
+    pagination, speech_rate, text_utils,
 };
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use ts_rs::TS;
 
-const BASE_WPM: f64 = 170.0;
+const TOC_MATCH_KEY_CHARS: usize = 200;
+const ANNOTATION_CONTEXT_SENTENCES: usize = 1;
+const NAV_HISTORY_LIMIT: usize = 100;
+const RECENT_POSITIONS_LIMIT: usize = 20;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, TS)]
 #[ts(export)]
@@ -61,6 +67,46 @@ pub struct ReaderTtsView {
     pub can_seek_prev: bool,
     pub can_seek_next: bool,
     pub progress_pct: f64,
+    /// Sentence range being played by `TtsPlayRange` or an A-B loop.
+    pub range: Option<TtsRange>,
+    /// Book-wide sentence marked as the A point of a pending A-B loop.
+    pub loop_start: Option<usize>,
+}
+
+/// Seconds at speed 1.0 for the current page's audio sentences and for the
+/// pages after it, so snapshots neither re-plan the page nor look up cached
+/// clips on every command.
+#[derive(Debug, Clone)]
+struct PageDurationEstimate {
+    page: usize,
+    voice: String,
+    sentence_secs: Vec<f64>,
+    later_secs: f64,
+    later_sentences: usize,
+}
+
+/// How the resume position was re-anchored when the book was opened, set
+/// when the saved sentence moved or could not be found.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, TS)]
+#[ts(export)]
+pub struct BookmarkReanchor {
+    /// Book-wide display sentence the saved index pointed at.
+    pub saved_sentence_idx: usize,
+    /// Book-wide display sentence reading resumed from.
+    pub sentence_idx: usize,
+    /// Similarity of the matched sentence, from 0 to 1. `None` when the saved
+    /// text was not found and the saved index was kept.
+    pub score: Option<f64>,
+}
+
+/// Inclusive range of book-wide display sentences for range playback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct TtsRange {
+    pub start: usize,
+    pub end: usize,
+    /// Start over from `start` instead of pausing after `end`.
+    pub repeat: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, TS)]
@@ -121,6 +167,9 @@ pub struct ReaderStats {
     pub sentences_read_up_to_page_end: usize,
     pub words_read_up_to_current_position: usize,
     pub sentences_read_up_to_current_position: usize,
+    /// Title of the deepest TOC entry at or before the current sentence.
+    pub current_chapter: Option<String>,
+    pub current_toc_entry: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -130,6 +179,8 @@ pub struct ReaderSnapshot {
     pub source_name: String,
     pub current_page: usize,
     pub total_pages: usize,
+    pub chapter: Option<pagination::PageChapter>,
+    pub toc: Vec<TocNode>,
     pub text_only_mode: bool,
     pub has_structured_markdown: bool,
     pub pretty_kind: PrettyKind,
@@ -140,7 +191,19 @@ pub struct ReaderSnapshot {
     pub page_text: String,
     pub sentences: Vec<String>,
     pub sentence_anchor_map: Vec<Option<usize>>,
+    /// Block ID of each entry in `sentences`.
+    pub sentence_block_ids: Vec<usize>,
+    pub blocks: Vec<ReaderBlock>,
+    pub annotations: Vec<ReaderAnnotation>,
+    /// User-created bookmarks for the whole book, in reading order.
+    pub bookmarks: Vec<crate::cache::NamedBookmark>,
+    pub can_navigate_back: bool,
+    pub can_navigate_forward: bool,
+    /// Positions left by recent jumps, most recent first; kept across restarts.
+    pub recent_positions: Vec<crate::cache::RecentPosition>,
+    pub bookmark_reanchor: Option<BookmarkReanchor>,
     pub highlighted_sentence_idx: Option<usize>,
+    pub active_table_row: Option<TableRowRef>,
     pub search_query: String,
     pub search_matches: Vec<usize>,
     pub selected_search_match: Option<usize>,
@@ -150,6 +213,55 @@ pub struct ReaderSnapshot {
     pub panels: PanelState,
 }
 
+/// Table row holding the highlighted sentence, for the pretty view. Tables
+/// are counted in the pretty payload shown for the current page; rows count
+/// `<tr>` elements, header row included.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, TS)]
+#[ts(export)]
+pub struct TableRowRef {
+    pub table_idx: usize,
+    pub row_idx: usize,
+}
+
+/// Structural block (paragraph, heading, list item, quote) on the current
+/// page. IDs are book-wide, so a block continued from the previous page keeps
+/// its ID.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, TS)]
+#[ts(export)]
+pub struct ReaderBlock {
+    pub id: usize,
+    pub kind: text_utils::BlockKind,
+    /// First entry of `sentences` in this block.
+    pub sentence_start: usize,
+    /// One past the last entry of `sentences` in this block.
+    pub sentence_end: usize,
+}
+
+/// Table of contents entry resolved against the paginated text. IDs follow a
+/// pre-order walk of the tree; entries that could not be located have no
+/// sentence index.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, TS)]
+#[ts(export)]
+pub struct TocNode {
+    pub id: usize,
+    pub title: String,
+    /// Book-wide display sentence index the entry points at.
+    pub sentence_idx: Option<usize>,
+    pub page: Option<usize>,
+    pub children: Vec<TocNode>,
+}
+
+/// Annotation overlapping the current page. `sentence_start`/`sentence_end`
+/// index `sentences` like [`ReaderBlock`]; the annotation itself keeps its
+/// book-wide range.
+#[derive(Debug, Clone, Serialize, PartialEq, TS)]
+#[ts(export)]
+pub struct ReaderAnnotation {
+    pub annotation: annotations::Annotation,
+    pub sentence_start: usize,
+    pub sentence_end: usize,
+}
+
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export)]
 pub struct ReaderImageRef {
@@ -166,18 +278,62 @@ pub enum PrettyKind {
     Html,
 }
 
+/// A display sentence on the current page whose audio text changed after a
+/// normalizer config reload.
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct NormalizationChange {
+    pub sentence_idx: usize,
+    pub sentence: String,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
 #[derive(Debug, Clone)]
 pub enum SessionCommand {
     GetSnapshot,
     NextPage,
     PrevPage,
-    SetPage { page: usize },
-    SentenceClick { sentence_idx: usize },
+    SetPage {
+        page: usize,
+    },
+    GoToTocEntry {
+        id: usize,
+    },
+    AddBookmark {
+        name: Option<String>,
+    },
+    RenameBookmark {
+        id: u64,
+        name: String,
+    },
+    DeleteBookmark {
+        id: u64,
+    },
+    GoToBookmark {
+        id: u64,
+    },
+    GoToSentence {
+        page: usize,
+        sentence_idx: usize,
+    },
+    GoToRecentPosition {
+        index: usize,
+    },
+    NavigateBack,
+    NavigateForward,
+    SentenceClick {
+        sentence_idx: usize,
+    },
     NextSentence,
     PrevSentence,
     ToggleTextOnly,
-    ApplySettings { patch: ReaderSettingsPatch },
-    SearchSetQuery { query: String },
+    ApplySettings {
+        patch: ReaderSettingsPatch,
+    },
+    SearchSetQuery {
+        query: String,
+    },
     SearchNext,
     SearchPrev,
     TtsPlay,
@@ -189,6 +345,12 @@ pub enum SessionCommand {
     TtsSeekPrev,
     TtsRepeatSentence,
     TtsStop,
+    TtsPlayRange {
+        start: usize,
+        end: usize,
+        repeat: bool,
+    },
+    TtsAbLoop,
 }
 
 impl SessionCommand {
@@ -198,6 +360,15 @@ impl SessionCommand {
             Self::NextPage => "reader_next_page",
             Self::PrevPage => "reader_prev_page",
             Self::SetPage { .. } => "reader_set_page",
+            Self::GoToTocEntry { .. } => "reader_go_to_toc_entry",
+            Self::AddBookmark { .. } => "reader_add_bookmark",
+            Self::RenameBookmark { .. } => "reader_rename_bookmark",
+            Self::DeleteBookmark { .. } => "reader_delete_bookmark",
+            Self::GoToBookmark { .. } => "reader_go_to_bookmark",
+            Self::GoToSentence { .. } => "reader_go_to_sentence",
+            Self::GoToRecentPosition { .. } => "reader_go_to_recent_position",
+            Self::NavigateBack => "reader_navigate_back",
+            Self::NavigateForward => "reader_navigate_forward",
             Self::SentenceClick { .. } => "reader_sentence_click",
             Self::NextSentence => "reader_next_sentence",
             Self::PrevSentence => "reader_prev_sentence",
@@ -215,8 +386,23 @@ impl SessionCommand {
             Self::TtsSeekPrev => "reader_tts_seek_prev",
             Self::TtsRepeatSentence => "reader_tts_repeat_sentence",
             Self::TtsStop => "reader_tts_stop",
+            Self::TtsPlayRange { .. } => "reader_tts_play_range",
+            Self::TtsAbLoop => "reader_tts_ab_loop",
         }
     }
+
+    /// Non-sequential jumps; the position before them goes on the back stack.
+    pub fn is_jump(&self) -> bool {
+        matches!(
+            self,
+            Self::SetPage { .. }
+                | Self::GoToTocEntry { .. }
+                | Self::GoToBookmark { .. }
+                | Self::GoToSentence { .. }
+                | Self::GoToRecentPosition { .. }
+                | Self::TtsPlayRange { .. }
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -234,13 +420,32 @@ pub struct ReaderSession {
     reading_html: Option<String>,
     has_structured_markdown: bool,
     images: Vec<SessionImage>,
+    /// Source language tag; picks sentence-boundary and abbreviation rules.
+    language: Option<String>,
     pub config: config::AppConfig,
     pages: Vec<String>,
     markdown_pages: Vec<String>,
     raw_page_sentences: Vec<Vec<String>>,
     sentence_anchor_maps: Vec<Vec<Option<usize>>>,
     page_word_counts: Vec<usize>,
+    /// Display characters per page, for estimating audio not yet normalized.
+    page_char_counts: Vec<usize>,
     page_sentence_counts: Vec<usize>,
+    /// Block structure over all display sentences in reading order.
+    blocks: text_utils::SentenceBlocks,
+    page_chapters: Vec<pagination::PageChapter>,
+    /// Table of contents from the loader, resolved into `toc` on repaginate.
+    toc_source: Vec<epub_loader::TocEntry>,
+    toc: Vec<TocNode>,
+    /// Highlights and notes, re-anchored to the current pagination.
+    annotations: Vec<annotations::Annotation>,
+    /// User-created bookmarks; the resume position is saved separately.
+    named_bookmarks: Vec<crate::cache::NamedBookmark>,
+    /// Positions before jumps, for `NavigateBack`; the last entry is the newest.
+    nav_back: Vec<crate::cache::RecentPosition>,
+    nav_forward: Vec<crate::cache::RecentPosition>,
+    recent_positions: Vec<crate::cache::RecentPosition>,
+    bookmark_reanchor: Option<BookmarkReanchor>,
     pub current_page: usize,
     highlighted_display_idx: Option<usize>,
     highlighted_audio_idx: Option<usize>,
@@ -248,9 +453,19 @@ pub struct ReaderSession {
     search_query: String,
     search_matches: Vec<usize>,
     selected_search_match: Option<usize>,
+    /// Last completed whole-book search; dropped when pagination changes.
+    book_search: Option<book_search::BookSearchState>,
     tts_state: TtsPlaybackState,
+    /// Active range playback; ends when the highlight leaves it.
+    tts_range: Option<TtsRange>,
+    tts_loop_start: Option<usize>,
     current_plan_page: Option<usize>,
     current_plan: Option<normalizer::PageNormalization>,
+    /// Learned speaking rate of the active voice, for time remaining.
+    speech_rate: speech_rate::SpeechRateModel,
+    /// Duration estimate for the current page; dropped with the plan.
+    page_duration: Option<PageDurationEstimate>,
+    normalizer_overlay: normalizer::NormalizerOverlay,
 }
 
 #[derive(Debug, Clone)]
@@ -370,12 +585,14 @@ impl ReaderSession {
             tracing::info!(
                 path = %source_path.display(),
                 mode = "chapter_section",
-                "Native HTML pagination mode configured; sentence indexing continuity remains canonical and drives page transitions"
+                "Chapter-section pagination configured; pages restart at each spine item and top-level heading"
             );
         }
         let reading_markdown = loaded.reading_markdown;
         let reading_html = loaded.reading_html;
         let has_structured_markdown = loaded.has_structured_markdown;
+        let language = loaded.language;
+        let toc_source = loaded.toc;
 
         let mut session = Self {
             source_path,
@@ -395,13 +612,25 @@ impl ReaderSession {
                     }
                 })
                 .collect(),
+            language,
             config,
             pages: Vec::new(),
             markdown_pages: Vec::new(),
             raw_page_sentences: Vec::new(),
             sentence_anchor_maps: Vec::new(),
             page_word_counts: Vec::new(),
+            page_char_counts: Vec::new(),
             page_sentence_counts: Vec::new(),
+            blocks: text_utils::SentenceBlocks::default(),
+            page_chapters: Vec::new(),
+            toc_source,
+            toc: Vec::new(),
+            annotations: Vec::new(),
+            named_bookmarks: Vec::new(),
+            nav_back: Vec::new(),
+            nav_forward: Vec::new(),
+            recent_positions: Vec::new(),
+            bookmark_reanchor: None,
             current_page: 0,
             highlighted_display_idx: None,
             highlighted_audio_idx: None,
@@ -409,9 +638,15 @@ impl ReaderSession {
             search_query: String::new(),
             search_matches: Vec::new(),
             selected_search_match: None,
+            book_search: None,
             tts_state: TtsPlaybackState::Idle,
+            tts_range: None,
+            tts_loop_start: None,
             current_plan_page: None,
             current_plan: None,
+            speech_rate: speech_rate::SpeechRateModel::default(),
+            page_duration: None,
+            normalizer_overlay: normalizer::NormalizerOverlay::default(),
         };
 
         if let Some(bookmark) = bookmark.as_ref() {
@@ -461,6 +696,9 @@ impl ReaderSession {
     ) -> ReaderSnapshot {
         let sentences = self.current_sentences(normalizer);
         let sentence_anchor_map = self.current_sentence_anchor_map();
+        let sentence_block_ids = self.current_sentence_block_ids(normalizer);
+        let blocks = self.current_blocks(&sentence_block_ids);
+        let annotations = self.current_annotations(normalizer);
         let anchor_hits = sentence_anchor_map
             .iter()
             .filter(|value| value.is_some())
@@ -472,7 +710,7 @@ impl ReaderSession {
         let tts_text_page = self
             .pages
             .get(self.current_page)
-            .cloned()
+            .map(|page| text_utils::strip_display_markers(page).into_owned())
             .unwrap_or_else(String::new);
         let reading_markdown_page = self
             .markdown_pages
@@ -513,6 +751,8 @@ impl ReaderSession {
             source_name: self.source_name.clone(),
             current_page: self.current_page,
             total_pages: self.pages.len(),
+            chapter: self.page_chapters.get(self.current_page).cloned(),
+            toc: self.toc.clone(),
             text_only_mode: self.text_only_mode,
             has_structured_markdown: self.has_structured_markdown,
             pretty_kind,
@@ -523,7 +763,16 @@ impl ReaderSession {
             page_text: tts_text_page,
             sentences,
             sentence_anchor_map,
+            sentence_block_ids,
+            blocks,
+            annotations,
+            bookmarks: self.named_bookmarks.clone(),
+            can_navigate_back: !self.nav_back.is_empty(),
+            can_navigate_forward: !self.nav_forward.is_empty(),
+            recent_positions: self.recent_positions.clone(),
+            bookmark_reanchor: self.bookmark_reanchor,
             highlighted_sentence_idx,
+            active_table_row: self.active_table_row(pretty_kind),
             search_query: self.search_query.clone(),
             search_matches: self.search_matches.clone(),
             selected_search_match: self.selected_search_match,
@@ -541,11 +790,31 @@ impl ReaderSession {
         normalizer: &normalizer::TextNormalizer,
     ) -> SessionEvent {
         let action = command.action();
+        let jump_origin = if command.is_jump() {
+            self.current_position()
+        } else {
+            None
+        };
         match command {
             SessionCommand::GetSnapshot => {}
             SessionCommand::NextPage => self.next_page(normalizer),
             SessionCommand::PrevPage => self.prev_page(normalizer),
             SessionCommand::SetPage { page } => self.set_page(page, normalizer),
+            SessionCommand::GoToTocEntry { id } => self.go_to_toc_entry(id, normalizer),
+            SessionCommand::AddBookmark { name } => self.add_named_bookmark(name),
+            SessionCommand::RenameBookmark { id, name } => self.rename_named_bookmark(id, name),
+            SessionCommand::DeleteBookmark { id } => {
+                self.named_bookmarks.retain(|bookmark| bookmark.id != id)
+            }
+            SessionCommand::GoToBookmark { id } => self.go_to_named_bookmark(id, normalizer),
+            SessionCommand::GoToSentence { page, sentence_idx } => {
+                self.go_to_sentence(page, sentence_idx, normalizer)
+            }
+            SessionCommand::GoToRecentPosition { index } => {
+                self.go_to_recent_position(index, normalizer)
+            }
+            SessionCommand::NavigateBack => self.navigate_back(normalizer),
+            SessionCommand::NavigateForward => self.navigate_forward(normalizer),
             SessionCommand::SentenceClick { sentence_idx } => {
                 self.sentence_click(sentence_idx, normalizer)
             }
@@ -565,7 +834,13 @@ impl ReaderSession {
             SessionCommand::TtsSeekPrev => self.tts_seek_prev(normalizer),
             SessionCommand::TtsRepeatSentence => self.tts_repeat_current_sentence(normalizer),
             SessionCommand::TtsStop => self.tts_stop(),
+            SessionCommand::TtsPlayRange { start, end, repeat } => {
+                self.tts_play_range(start, end, repeat, normalizer)
+            }
+            SessionCommand::TtsAbLoop => self.tts_ab_loop(normalizer),
         }
+        self.leave_tts_range_if_outside();
+        self.record_jump(jump_origin);
         SessionEvent {
             action,
             snapshot: self.snapshot(panels, normalizer),
@@ -698,6 +973,7 @@ impl ReaderSession {
         self.highlighted_audio_idx = None;
         self.current_plan_page = None;
         self.current_plan = None;
+        self.page_duration = None;
         if self.text_only_mode {
             self.highlighted_audio_idx = self
                 .highlighted_display_idx
@@ -715,6 +991,7 @@ impl ReaderSession {
         self.highlighted_audio_idx = None;
         self.current_plan_page = None;
         self.current_plan = None;
+        self.page_duration = None;
         if self.text_only_mode {
             self.highlighted_audio_idx = self
                 .highlighted_display_idx
@@ -733,6 +1010,83 @@ impl ReaderSession {
         self.highlighted_audio_idx = None;
         self.current_plan_page = None;
         self.current_plan = None;
+        self.page_duration = None;
+        if self.text_only_mode {
+            self.highlighted_audio_idx = self
+                .highlighted_display_idx
+                .and_then(|idx| self.map_display_to_audio_idx(normalizer, idx));
+        }
+        self.update_search_matches(normalizer);
+    }
+
+    pub fn go_to_toc_entry(&mut self, id: usize, normalizer: &normalizer::TextNormalizer) {
+        let target = flatten_toc(&self.toc)
+            .into_iter()
+            .find(|node| node.id == id)
+            .and_then(|node| node.sentence_idx);
+        if let Some(global_idx) = target {
+            self.go_to_global_sentence(global_idx, normalizer);
+        }
+    }
+
+    pub fn go_to_sentence(
+        &mut self,
+        page: usize,
+        sentence_idx: usize,
+        normalizer: &normalizer::TextNormalizer,
+    ) {
+        if self.page_sentence_counts.is_empty() {
+            return;
+        }
+        let page = page.min(self.page_sentence_counts.len() - 1);
+        let page_base: usize = self.page_sentence_counts[..page].iter().sum();
+        let sentence_idx = sentence_idx.min(self.page_sentence_counts[page].saturating_sub(1));
+        self.go_to_global_sentence(page_base + sentence_idx, normalizer);
+    }
+
+    /// Jump to the sentence matching `text`, searching outward from the
+    /// book-wide index `around` and then the whole book. Returns whether a
+    /// match was found.
+    pub fn go_to_sentence_text(
+        &mut self,
+        text: &str,
+        around: usize,
+        normalizer: &normalizer::TextNormalizer,
+    ) -> bool {
+        let anchor = text_utils::anchor_text(text);
+        let sentences: Vec<&String> = self.raw_page_sentences.iter().flatten().collect();
+        if anchor.is_empty() || sentences.is_empty() {
+            return false;
+        }
+        let target = text_utils::find_sentence_near(&sentences, &anchor, around, 0).or_else(|| {
+            sentences
+                .iter()
+                .position(|sentence| text_utils::anchor_text(sentence).contains(&anchor))
+        });
+        let Some(global_idx) = target else {
+            return false;
+        };
+        let origin = self.current_position();
+        self.go_to_global_sentence(global_idx, normalizer);
+        self.record_jump(origin);
+        true
+    }
+
+    fn go_to_global_sentence(
+        &mut self,
+        global_idx: usize,
+        normalizer: &normalizer::TextNormalizer,
+    ) {
+        if self.page_sentence_counts.is_empty() {
+            return;
+        }
+        let (page, idx) = self.page_idx_for_global_sentence(global_idx);
+        self.current_page = page;
+        self.highlighted_display_idx = Some(idx).filter(|_| self.current_display_len() > 0);
+        self.highlighted_audio_idx = None;
+        self.current_plan_page = None;
+        self.current_plan = None;
+        self.page_duration = None;
         if self.text_only_mode {
             self.highlighted_audio_idx = self
                 .highlighted_display_idx
@@ -841,6 +1195,14 @@ impl ReaderSession {
     }
 
     pub fn tts_seek_next(&mut self, normalizer: &normalizer::TextNormalizer) {
+        if let Some(end_idx) = self.tts_range_end_audio_idx(normalizer)
+            && self
+                .current_audio_highlight_idx(normalizer)
+                .is_some_and(|idx| idx >= end_idx)
+        {
+            self.finish_tts_range(normalizer);
+            return;
+        }
         if self.move_highlight_relative(1, normalizer) {
             return;
         }
@@ -862,27 +1224,693 @@ impl ReaderSession {
 
     pub fn tts_stop(&mut self) {
         self.tts_state = TtsPlaybackState::Idle;
+        self.tts_range = None;
+        self.tts_loop_start = None;
+    }
+
+    /// Play book-wide display sentences `start..=end`, across pages if
+    /// needed, then pause or (with `repeat`) start over from `start`.
+    pub fn tts_play_range(
+        &mut self,
+        start: usize,
+        end: usize,
+        repeat: bool,
+        normalizer: &normalizer::TextNormalizer,
+    ) {
+        let total: usize = self.page_sentence_counts.iter().sum();
+        if total == 0 {
+            return;
+        }
+        let end = start.max(end).min(total - 1);
+        let start = start.min(end);
+        self.tts_range = Some(TtsRange { start, end, repeat });
+        self.go_to_global_sentence(start, normalizer);
+        self.tts_state = TtsPlaybackState::Playing;
+    }
+
+    pub fn tts_loop_start(&self) -> Option<usize> {
+        self.tts_loop_start
+    }
+
+    /// A-B loop button: the first press marks A at the highlighted sentence,
+    /// the second marks B and loops A..=B, and a press while looping ends
+    /// the loop.
+    pub fn tts_ab_loop(&mut self, normalizer: &normalizer::TextNormalizer) {
+        if self.tts_range.is_some_and(|range| range.repeat) {
+            self.tts_range = None;
+            self.tts_loop_start = None;
+            return;
+        }
+        let Some(here) = self.global_display_idx() else {
+            return;
+        };
+        match self.tts_loop_start.take() {
+            None => self.tts_loop_start = Some(here),
+            Some(start) => self.tts_play_range(start.min(here), start.max(here), true, normalizer),
+        }
+    }
+
+    /// Last audio sentence to play on this page when the active range ends
+    /// here.
+    fn tts_range_end_audio_idx(
+        &mut self,
+        normalizer: &normalizer::TextNormalizer,
+    ) -> Option<usize> {
+        let range = self.tts_range?;
+        let (page, end_idx) = self.page_idx_for_global_sentence(range.end);
+        if page != self.current_page {
+            return None;
+        }
+        self.ensure_current_plan(normalizer)
+            .audio_to_display
+            .iter()
+            .rposition(|&display_idx| display_idx <= end_idx)
+    }
+
+    fn finish_tts_range(&mut self, normalizer: &normalizer::TextNormalizer) {
+        match self.tts_range {
+            Some(range) if range.repeat => self.go_to_global_sentence(range.start, normalizer),
+            _ => {
+                self.tts_range = None;
+                if self.tts_state == TtsPlaybackState::Playing {
+                    self.tts_state = TtsPlaybackState::Paused;
+                }
+            }
+        }
+    }
+
+    /// Moving the highlight out of the active range (paging, clicking,
+    /// jumping) ends range playback.
+    fn leave_tts_range_if_outside(&mut self) {
+        if let Some(range) = self.tts_range
+            && !self
+                .global_display_idx()
+                .is_some_and(|idx| (range.start..=range.end).contains(&idx))
+        {
+            self.tts_range = None;
+        }
+    }
+
+    pub fn named_bookmarks(&self) -> &[crate::cache::NamedBookmark] {
+        &self.named_bookmarks
+    }
+
+    /// Replace the user-created bookmarks (e.g. from the cache) and re-anchor
+    /// them to the current pagination.
+    pub fn set_named_bookmarks(&mut self, bookmarks: Vec<crate::cache::NamedBookmark>) {
+        self.named_bookmarks = bookmarks;
+        self.reanchor_named_bookmarks();
+    }
+
+    /// Bookmark the current sentence. Without a name the bookmark is named
+    /// after the current chapter and page.
+    fn add_named_bookmark(&mut self, name: Option<String>) {
+        let Some(sentence_idx) = self.global_display_idx() else {
+            return;
+        };
+        let name = name
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| match self.current_toc_node() {
+                Some(node) => format!("{}, page {}", node.title, self.current_page + 1),
+                None => format!("Page {}", self.current_page + 1),
+            });
+        let id = self
+            .named_bookmarks
+            .iter()
+            .map(|bookmark| bookmark.id + 1)
+            .max()
+            .unwrap_or(1);
+        let sentence_text = self.current_anchor_text();
+        self.named_bookmarks.push(crate::cache::NamedBookmark {
+            id,
+            name,
+            sentence_idx,
+            sentence_text,
+            created_unix_secs: annotations::now_unix_secs(),
+        });
+        self.named_bookmarks
+            .sort_by_key(|bookmark| (bookmark.sentence_idx, bookmark.id));
+    }
+
+    fn rename_named_bookmark(&mut self, id: u64, name: String) {
+        let name = name.trim();
+        if name.is_empty() {
+            return;
+        }
+        if let Some(bookmark) = self
+            .named_bookmarks
+            .iter_mut()
+            .find(|bookmark| bookmark.id == id)
+        {
+            bookmark.name = name.to_string();
+        }
+    }
+
+    fn go_to_named_bookmark(&mut self, id: u64, normalizer: &normalizer::TextNormalizer) {
+        let target = self
+            .named_bookmarks
+            .iter()
+            .find(|bookmark| bookmark.id == id)
+            .map(|bookmark| bookmark.sentence_idx);
+        if let Some(global_idx) = target {
+            self.go_to_global_sentence(global_idx, normalizer);
+        }
+    }
+
+    fn current_anchor_text(&self) -> String {
+        self.raw_page_sentences
+            .get(self.current_page)
+            .and_then(|sentences| sentences.get(self.highlighted_display_idx?))
+            .map(|sentence| text_utils::anchor_text(sentence))
+            .unwrap_or_default()
+    }
+
+    fn current_position(&self) -> Option<crate::cache::RecentPosition> {
+        Some(crate::cache::RecentPosition {
+            sentence_idx: self.global_display_idx()?,
+            sentence_text: self.current_anchor_text(),
+            left_unix_secs: annotations::now_unix_secs(),
+        })
+    }
+
+    pub fn recent_positions(&self) -> &[crate::cache::RecentPosition] {
+        &self.recent_positions
+    }
+
+    /// Replace the recent positions (e.g. from the cache) and re-anchor them to
+    /// the current pagination.
+    pub fn set_recent_positions(&mut self, positions: Vec<crate::cache::RecentPosition>) {
+        self.recent_positions = positions;
+        self.recent_positions.truncate(RECENT_POSITIONS_LIMIT);
+        self.reanchor_positions();
+    }
+
+    /// Remember where a jump started, unless it did not move. A new jump
+    /// discards the forward history, as in a browser.
+    fn record_jump(&mut self, origin: Option<crate::cache::RecentPosition>) {
+        let Some(origin) = origin else {
+            return;
+        };
+        if self.global_display_idx() == Some(origin.sentence_idx) {
+            return;
+        }
+        self.nav_forward.clear();
+        if self.nav_back.last().map(|last| last.sentence_idx) != Some(origin.sentence_idx) {
+            self.nav_back.push(origin.clone());
+            if self.nav_back.len() > NAV_HISTORY_LIMIT {
+                self.nav_back.remove(0);
+            }
+        }
+        self.recent_positions
+            .retain(|position| position.sentence_idx != origin.sentence_idx);
+        self.recent_positions.insert(0, origin);
+        self.recent_positions.truncate(RECENT_POSITIONS_LIMIT);
+    }
+
+    fn navigate_back(&mut self, normalizer: &normalizer::TextNormalizer) {
+        let Some(target) = self.nav_back.pop() else {
+            return;
+        };
+        if let Some(here) = self.current_position() {
+            self.nav_forward.push(here);
+        }
+        self.go_to_global_sentence(target.sentence_idx, normalizer);
+    }
+
+    fn navigate_forward(&mut self, normalizer: &normalizer::TextNormalizer) {
+        let Some(target) = self.nav_forward.pop() else {
+            return;
+        };
+        if let Some(here) = self.current_position() {
+            self.nav_back.push(here);
+        }
+        self.go_to_global_sentence(target.sentence_idx, normalizer);
+    }
+
+    fn go_to_recent_position(&mut self, index: usize, normalizer: &normalizer::TextNormalizer) {
+        if let Some(global_idx) = self
+            .recent_positions
+            .get(index)
+            .map(|position| position.sentence_idx)
+        {
+            self.go_to_global_sentence(global_idx, normalizer);
+        }
+    }
+
+    fn reanchor_positions(&mut self) {
+        let sentences: Vec<&String> = self.raw_page_sentences.iter().flatten().collect();
+        for position in self
+            .nav_back
+            .iter_mut()
+            .chain(self.nav_forward.iter_mut())
+            .chain(self.recent_positions.iter_mut())
+        {
+            position.sentence_idx = if sentences.is_empty() {
+                0
+            } else {
+                text_utils::find_sentence_near(
+                    &sentences,
+                    &position.sentence_text,
+                    position.sentence_idx,
+                    0,
+                )
+                .unwrap_or(position.sentence_idx.min(sentences.len() - 1))
+            };
+        }
+    }
+
+    fn reanchor_named_bookmarks(&mut self) {
+        if self.named_bookmarks.is_empty() {
+            return;
+        }
+        let sentences: Vec<&String> = self.raw_page_sentences.iter().flatten().collect();
+        let last = sentences.len().saturating_sub(1);
+        for bookmark in &mut self.named_bookmarks {
+            bookmark.sentence_idx = if sentences.is_empty() {
+                0
+            } else {
+                text_utils::find_sentence_near(
+                    &sentences,
+                    &bookmark.sentence_text,
+                    bookmark.sentence_idx,
+                    0,
+                )
+                .unwrap_or(bookmark.sentence_idx.min(last))
+            };
+        }
+        self.named_bookmarks
+            .sort_by_key(|bookmark| (bookmark.sentence_idx, bookmark.id));
+    }
+
+    pub fn annotations(&self) -> &[annotations::Annotation] {
+        &self.annotations
+    }
+
+    /// Replace the annotations (e.g. from the cache) and re-anchor them to the
+    /// current pagination.
+    pub fn set_annotations(&mut self, annotations: Vec<annotations::Annotation>) {
+        self.annotations = annotations;
+        self.reanchor_annotations();
+        self.reanchor_named_bookmarks();
+    }
+
+    pub fn add_annotation(
+        &mut self,
+        input: annotations::AnnotationInput,
+    ) -> Result<annotations::Annotation, String> {
+        let id = self
+            .annotations
+            .iter()
+            .map(|annotation| annotation.id + 1)
+            .max()
+            .unwrap_or(1);
+        let now = annotations::now_unix_secs();
+        let mut annotation = annotations::Annotation {
+            id,
+            sentence_start: 0,
+            sentence_end: 0,
+            start_text: String::new(),
+            end_text: String::new(),
+            note: None,
+            color: self.config.day_highlight,
+            tags: Vec::new(),
+            created_unix_secs: now,
+            updated_unix_secs: now,
+        };
+        self.fill_annotation(&mut annotation, input)?;
+        self.annotations.push(annotation.clone());
+        self.annotations
+            .sort_by_key(|annotation| (annotation.sentence_start, annotation.id));
+        Ok(annotation)
+    }
+
+    pub fn update_annotation(
+        &mut self,
+        id: u64,
+        input: annotations::AnnotationInput,
+    ) -> Result<annotations::Annotation, String> {
+        let pos = self
+            .annotations
+            .iter()
+            .position(|annotation| annotation.id == id)
+            .ok_or_else(|| format!("No annotation with id {id}"))?;
+        let mut annotation = self.annotations[pos].clone();
+        self.fill_annotation(&mut annotation, input)?;
+        annotation.updated_unix_secs = annotations::now_unix_secs();
+        self.annotations[pos] = annotation.clone();
+        self.annotations
+            .sort_by_key(|annotation| (annotation.sentence_start, annotation.id));
+        Ok(annotation)
+    }
+
+    pub fn delete_annotation(&mut self, id: u64) -> Result<(), String> {
+        let before = self.annotations.len();
+        self.annotations.retain(|annotation| annotation.id != id);
+        if self.annotations.len() == before {
+            return Err(format!("No annotation with id {id}"));
+        }
+        Ok(())
+    }
+
+    /// Every annotation with its page, chapter and the sentences around it,
+    /// ready for [`annotations::render_export`].
+    pub fn annotation_export(&self) -> annotations::AnnotationExport {
+        let sentences: Vec<&String> = self.raw_page_sentences.iter().flatten().collect();
+        let joined = |range: std::ops::Range<usize>| {
+            sentences
+                .get(range)
+                .unwrap_or_default()
+                .iter()
+                .map(|sentence| text_utils::anchor_text(sentence))
+                .filter(|sentence| !sentence.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let entries = self
+            .annotations
+            .iter()
+            .map(|annotation| {
+                let start = annotation.sentence_start.min(sentences.len());
+                let end = annotation.sentence_end.clamp(start, sentences.len());
+                let page = self.page_idx_for_global_sentence(start).0;
+                let chapter = self
+                    .toc_node_at(start)
+                    .map(|node| node.title.clone())
+                    .or_else(|| {
+                        self.page_chapters
+                            .get(page)
+                            .and_then(|chapter| chapter.title.clone())
+                    });
+                annotations::AnnotationExportEntry {
+                    id: annotation.id,
+                    page: page + 1,
+                    chapter,
+                    text: joined(start..end),
+                    context_before: joined(
+                        start.saturating_sub(ANNOTATION_CONTEXT_SENTENCES)..start,
+                    ),
+                    context_after: joined(
+                        end..(end + ANNOTATION_CONTEXT_SENTENCES).min(sentences.len()),
+                    ),
+                    note: annotation.note.clone(),
+                    tags: annotation.tags.clone(),
+                    color: annotation.color,
+                    created_unix_secs: annotation.created_unix_secs,
+                    updated_unix_secs: annotation.updated_unix_secs,
+                }
+            })
+            .collect();
+        annotations::AnnotationExport {
+            book_title: crate::cache::infer_recent_title(&self.source_path),
+            source_path: self.source_path_str(),
+            exported_unix_secs: annotations::now_unix_secs(),
+            annotations: entries,
+        }
+    }
+
+    fn fill_annotation(
+        &self,
+        annotation: &mut annotations::Annotation,
+        input: annotations::AnnotationInput,
+    ) -> Result<(), String> {
+        let total: usize = self.page_sentence_counts.iter().sum();
+        if input.sentence_start >= input.sentence_end || input.sentence_end > total {
+            return Err(format!(
+                "Invalid annotation range {}..{} for {total} sentences",
+                input.sentence_start, input.sentence_end
+            ));
+        }
+        let sentence_text = |global_idx: usize| {
+            let (page, idx) = self.page_idx_for_global_sentence(global_idx);
+            self.raw_page_sentences
+                .get(page)
+                .and_then(|sentences| sentences.get(idx))
+                .map(|sentence| text_utils::anchor_text(sentence))
+                .unwrap_or_default()
+        };
+        annotation.sentence_start = input.sentence_start;
+        annotation.sentence_end = input.sentence_end;
+        annotation.start_text = sentence_text(input.sentence_start);
+        annotation.end_text = sentence_text(input.sentence_end - 1);
+        annotation.note = input
+            .note
+            .map(|note| note.trim().to_string())
+            .filter(|note| !note.is_empty());
+        if let Some(color) = input.color {
+            annotation.color = color;
+        }
+        annotation.tags = annotations::clean_tags(input.tags);
+        Ok(())
+    }
+
+    fn reanchor_annotations(&mut self) {
+        if self.annotations.is_empty() {
+            return;
+        }
+        let sentences: Vec<&String> = self.raw_page_sentences.iter().flatten().collect();
+        for annotation in &mut self.annotations {
+            annotations::reanchor(annotation, &sentences);
+        }
+        self.annotations
+            .sort_by_key(|annotation| (annotation.sentence_start, annotation.id));
+    }
+
+    fn current_annotations(
+        &mut self,
+        normalizer: &normalizer::TextNormalizer,
+    ) -> Vec<ReaderAnnotation> {
+        if self.annotations.is_empty() {
+            return Vec::new();
+        }
+        let page_base: usize = self
+            .page_sentence_counts
+            .iter()
+            .take(self.current_page)
+            .sum();
+        let count = self
+            .page_sentence_counts
+            .get(self.current_page)
+            .copied()
+            .unwrap_or(0);
+        let global_ids: Vec<usize> = if self.text_only_mode {
+            self.ensure_current_plan(normalizer)
+                .audio_to_display
+                .iter()
+                .map(|display_idx| page_base + display_idx)
+                .collect()
+        } else {
+            (page_base..page_base + count).collect()
+        };
+        self.annotations
+            .iter()
+            .filter_map(|annotation| {
+                let covered = |global: &usize| {
+                    (annotation.sentence_start..annotation.sentence_end).contains(global)
+                };
+                let start = global_ids.iter().position(covered)?;
+                let end = global_ids.iter().rposition(covered)? + 1;
+                Some(ReaderAnnotation {
+                    annotation: annotation.clone(),
+                    sentence_start: start,
+                    sentence_end: end,
+                })
+            })
+            .collect()
+    }
+
+    pub fn normalizer_overlay(&self) -> &normalizer::NormalizerOverlay {
+        &self.normalizer_overlay
+    }
+
+    /// Replace the per-book normalizer overlay and drop the cached page plan so
+    /// the next TTS request re-normalizes with the new rules.
+    pub fn set_normalizer_overlay(&mut self, overlay: normalizer::NormalizerOverlay) {
+        self.normalizer_overlay = overlay;
+        self.current_plan_page = None;
+        self.current_plan = None;
+        self.page_duration = None;
+    }
+
+    /// Trace how a display sentence on the current page is normalized for TTS,
+    /// defaulting to the highlighted sentence.
+    pub fn explain_sentence(
+        &self,
+        sentence_idx: Option<usize>,
+        normalizer: &normalizer::TextNormalizer,
+    ) -> Option<normalizer::NormalizationTrace> {
+        let idx = sentence_idx.or(self.highlighted_display_idx)?;
+        let sentence = self.raw_page_sentences.get(self.current_page)?.get(idx)?;
+        Some(self.book_normalizer(normalizer).explain(sentence))
+    }
+
+    /// Re-split and re-plan the book after the global normalizer or abbreviation
+    /// config was reloaded, keeping the highlighted sentence in place. Returns
+    /// the current-page sentences whose audio text changed so callers can offer
+    /// to re-synthesize only those.
+    pub fn reload_normalizer(
+        &mut self,
+        previous: &normalizer::TextNormalizer,
+        current: &normalizer::TextNormalizer,
+    ) -> Vec<NormalizationChange> {
+        let old_display = self
+            .raw_page_sentences
+            .get(self.current_page)
+            .cloned()
+            .unwrap_or_default();
+        let old_plan = self.book_normalizer(previous).plan_page(&old_display);
+        let before_by_text: HashMap<&str, Vec<String>> = old_display
+            .iter()
+            .enumerate()
+            .map(|(idx, sentence)| (sentence.trim(), audio_chunks_for_display(&old_plan, idx)))
+            .collect();
+
+        let preserve = self.global_display_idx();
+        self.repaginate(current, preserve);
+        let new_plan = self.ensure_current_plan(current);
+        let new_display = self
+            .raw_page_sentences
+            .get(self.current_page)
+            .cloned()
+            .unwrap_or_default();
+
+        new_display
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, sentence)| {
+                let after = audio_chunks_for_display(&new_plan, idx);
+                let before = before_by_text.get(sentence.trim()).cloned();
+                if before.as_ref() == Some(&after) {
+                    return None;
+                }
+                Some(NormalizationChange {
+                    sentence_idx: idx,
+                    sentence: sentence.clone(),
+                    before: before.unwrap_or_default(),
+                    after,
+                })
+            })
+            .collect()
+    }
+
+    /// Audio chunks for the given display sentences on the current page.
+    /// TTS inputs for the audio sentences of the given display sentences,
+    /// exactly as playback sends them (phoneme markup included), so a
+    /// re-synthesized clip lands under the cache key playback reads.
+    pub fn tts_inputs_for_display(
+        &mut self,
+        sentence_indices: &[usize],
+        normalizer: &normalizer::TextNormalizer,
+    ) -> Vec<String> {
+        let plan = self.ensure_current_plan(normalizer);
+        sentence_indices
+            .iter()
+            .flat_map(|idx| audio_chunks_for_display(&plan, *idx))
+            .collect()
+    }
+
+    fn book_normalizer<'a>(
+        &self,
+        normalizer: &'a normalizer::TextNormalizer,
+    ) -> Cow<'a, normalizer::TextNormalizer> {
+        if self.normalizer_overlay.is_empty() {
+            Cow::Borrowed(normalizer)
+        } else {
+            Cow::Owned(normalizer.with_overlay(&self.normalizer_overlay))
+        }
+    }
+
+    /// Detach the display sentences (and, with `include_audio`, the book's
+    /// normalizer) so a whole-book search can run without the session lock.
+    pub fn book_search_corpus(
+        &self,
+        normalizer: &normalizer::TextNormalizer,
+        include_audio: bool,
+    ) -> book_search::BookSearchCorpus {
+        book_search::BookSearchCorpus {
+            source_path: self.source_path.clone(),
+            page_sentences: self.raw_page_sentences.clone(),
+            normalizer: include_audio.then(|| self.book_normalizer(normalizer).into_owned()),
+        }
+    }
+
+    /// Keep a finished search unless the book or its pagination changed while
+    /// it ran. Returns whether the results were accepted.
+    pub fn accept_book_search(
+        &mut self,
+        corpus: &book_search::BookSearchCorpus,
+        state: book_search::BookSearchState,
+    ) -> bool {
+        let same_layout = corpus.source_path == self.source_path
+            && corpus.page_sentences.len() == self.page_sentence_counts.len()
+            && corpus
+                .page_sentences
+                .iter()
+                .zip(&self.page_sentence_counts)
+                .all(|(sentences, count)| sentences.len() == *count);
+        if same_layout {
+            self.book_search = Some(state);
+        }
+        same_layout
+    }
+
+    pub fn book_search_results(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Option<book_search::BookSearchResults> {
+        self.book_search
+            .as_ref()
+            .map(|state| state.results(offset, limit))
     }
 
     pub fn to_bookmark(&self) -> crate::cache::Bookmark {
+        let global_idx = self.global_display_idx();
         crate::cache::Bookmark {
             page: self.current_page,
             sentence_idx: self.current_highlight_idx(),
-            sentence_text: None,
+            global_sentence_idx: global_idx,
+            sentence_text: Some(self.current_anchor_text()).filter(|text| !text.is_empty()),
+            context_before: global_idx
+                .and_then(|idx| idx.checked_sub(1))
+                .and_then(|idx| self.anchor_text_at(idx)),
+            context_after: global_idx.and_then(|idx| self.anchor_text_at(idx + 1)),
             scroll_y: 0.0,
         }
     }
 
+    /// Anchor text of a book-wide display sentence.
+    fn anchor_text_at(&self, global_idx: usize) -> Option<String> {
+        if global_idx >= self.page_sentence_counts.iter().sum() {
+            return None;
+        }
+        let (page, idx) = self.page_idx_for_global_sentence(global_idx);
+        self.raw_page_sentences
+            .get(page)?
+            .get(idx)
+            .map(|sentence| text_utils::anchor_text(sentence))
+            .filter(|text| !text.is_empty())
+    }
+
     fn repaginate(
         &mut self,
         normalizer: &normalizer::TextNormalizer,
         preserve_global_idx: Option<usize>,
     ) {
-        self.pages = pagination::paginate(
-            &self.tts_text,
+        let laid_out = text_utils::layout_footnotes(&self.tts_text, normalizer.footnote_mode());
+        let language = self.language.as_deref();
+        let paginated = pagination::paginate_structured(
+            &laid_out,
             self.config.font_size,
             self.config.lines_per_page,
+            language,
+            matches!(
+                self.config.native_html_pagination_mode,
+                config::NativeHtmlPaginationMode::ChapterSection
+            ),
         );
+        self.pages = paginated.pages;
+        self.page_chapters = paginated.page_chapters;
         if self.pages.is_empty() {
             self.pages.push(String::new());
         }
@@ -890,7 +1918,12 @@ impl ReaderSession {
             .reading_markdown
             .as_ref()
             .map(|markdown| {
-                pagination::paginate(markdown, self.config.font_size, self.config.lines_per_page)
+                pagination::paginate_with_language(
+                    markdown,
+                    self.config.font_size,
+                    self.config.lines_per_page,
+                    language,
+                )
             })
             .unwrap_or_default();
         if !self.markdown_pages.is_empty() && self.markdown_pages.len() < self.pages.len() {
@@ -899,7 +1932,7 @@ impl ReaderSession {
         self.raw_page_sentences = self
             .pages
             .iter()
-            .map(|page| text_utils::split_sentences(page))
+            .map(|page| text_utils::split_sentences_with_language(page, language))
             .collect();
         self.sentence_anchor_maps = self
             .raw_page_sentences
@@ -917,11 +1950,30 @@ impl ReaderSession {
             .iter()
             .map(|page| page.split_whitespace().count())
             .collect();
+        self.page_char_counts = self
+            .raw_page_sentences
+            .iter()
+            .map(|sentences| display_char_count(sentences))
+            .collect();
         self.page_sentence_counts = self.raw_page_sentences.iter().map(Vec::len).collect();
+        self.speech_rate.clear_pages();
+        self.blocks = paginated.blocks;
+        let total_sentences: usize = self.page_sentence_counts.iter().sum();
+        if self.blocks.sentence_blocks.len() != total_sentences {
+            let sentences: Vec<&String> = self.raw_page_sentences.iter().flatten().collect();
+            self.blocks = text_utils::assign_blocks(&sentences);
+        }
+        self.toc = self.resolve_toc();
+        self.reanchor_annotations();
+        self.reanchor_positions();
+        self.book_search = None;
+        self.tts_range = None;
+        self.tts_loop_start = None;
 
         self.current_page = self.current_page.min(self.pages.len().saturating_sub(1));
         self.current_plan_page = None;
         self.current_plan = None;
+        self.page_duration = None;
 
         if let Some(global_idx) = preserve_global_idx {
             let (page, idx) = self.page_idx_for_global_sentence(global_idx);
@@ -940,7 +1992,99 @@ impl ReaderSession {
         self.update_search_matches(normalizer);
     }
 
+    /// Locate each TOC entry in reading order: the first block at or after the
+    /// previous match whose text starts with the entry title, preferring
+    /// headings. Entries that cannot be found keep `sentence_idx: None` and do
+    /// not move the search cursor.
+    fn resolve_toc(&self) -> Vec<TocNode> {
+        let sentences: Vec<&String> = self.raw_page_sentences.iter().flatten().collect();
+        let mut block_starts: Vec<(usize, bool, String)> = Vec::new();
+        for (sentence_idx, sentence) in sentences.iter().enumerate() {
+            let Some(&block_id) = self.blocks.sentence_blocks.get(sentence_idx) else {
+                break;
+            };
+            let key = toc_match_key(sentence);
+            match block_starts.get_mut(block_id) {
+                Some((_, _, block_key)) => {
+                    if block_key.len() < TOC_MATCH_KEY_CHARS {
+                        block_key.push_str(&key);
+                    }
+                }
+                None => {
+                    let is_heading = matches!(
+                        self.blocks.kinds.get(block_id),
+                        Some(text_utils::BlockKind::Heading { .. })
+                    );
+                    block_starts.push((sentence_idx, is_heading, key));
+                }
+            }
+        }
+        let mut cursor = 0;
+        let mut next_id = 0;
+        self.resolve_toc_level(&self.toc_source, &block_starts, &mut cursor, &mut next_id)
+    }
+
+    fn resolve_toc_level(
+        &self,
+        entries: &[epub_loader::TocEntry],
+        block_starts: &[(usize, bool, String)],
+        cursor: &mut usize,
+        next_id: &mut usize,
+    ) -> Vec<TocNode> {
+        entries
+            .iter()
+            .map(|entry| {
+                let id = *next_id;
+                *next_id += 1;
+                let title_key = toc_match_key(&entry.title);
+                let remaining = block_starts.get(*cursor..).unwrap_or_default();
+                let matches = |(_, is_heading, key): &(usize, bool, String), heading_only: bool| {
+                    !title_key.is_empty()
+                        && (!heading_only || *is_heading)
+                        && (key.starts_with(&title_key)
+                            || (*is_heading && !key.is_empty() && title_key.starts_with(key)))
+                };
+                let found = remaining
+                    .iter()
+                    .position(|block| matches(block, true))
+                    .or_else(|| remaining.iter().position(|block| matches(block, false)));
+                let sentence_idx = found.map(|offset| {
+                    *cursor += offset + 1;
+                    block_starts[*cursor - 1].0
+                });
+                TocNode {
+                    id,
+                    title: entry.title.clone(),
+                    sentence_idx,
+                    page: sentence_idx.map(|idx| self.page_idx_for_global_sentence(idx).0),
+                    children: self.resolve_toc_level(
+                        &entry.children,
+                        block_starts,
+                        cursor,
+                        next_id,
+                    ),
+                }
+            })
+            .collect()
+    }
+
+    /// Deepest TOC entry at or before the current sentence.
+    fn current_toc_node(&self) -> Option<&TocNode> {
+        self.toc_node_at(self.global_display_idx().unwrap_or(0))
+    }
+
+    /// Deepest TOC entry at or before `global_idx`.
+    fn toc_node_at(&self, global_idx: usize) -> Option<&TocNode> {
+        flatten_toc(&self.toc)
+            .into_iter()
+            .filter(|node| node.sentence_idx.is_some_and(|idx| idx <= global_idx))
+            .max_by_key(|node| node.sentence_idx)
+    }
+
     fn global_idx_for_bookmark(&self, bookmark: &crate::cache::Bookmark) -> Option<usize> {
+        if bookmark.global_sentence_idx.is_some() {
+            return bookmark.global_sentence_idx;
+        }
         let sentence_idx = bookmark.sentence_idx?;
         let page = bookmark
             .page
@@ -966,14 +2110,20 @@ impl ReaderSession {
             .min(self.page_sentence_counts.len().saturating_sub(1));
         self.current_page = clamped_page;
 
-        self.highlighted_display_idx =
-            if let Some(global_idx) = self.global_idx_for_bookmark(bookmark) {
-                let (page, idx) = self.page_idx_for_global_sentence(global_idx);
-                self.current_page = page;
-                Some(idx)
-            } else {
-                Some(0).filter(|_| self.current_display_len() > 0)
-            };
+        let saved_idx = self.global_idx_for_bookmark(bookmark);
+        let resolved_idx = match bookmark.sentence_text.as_deref() {
+            Some(text) if !text.trim().is_empty() => {
+                self.reanchor_bookmark(bookmark, text, saved_idx)
+            }
+            _ => saved_idx,
+        };
+        self.highlighted_display_idx = if let Some(global_idx) = resolved_idx {
+            let (page, idx) = self.page_idx_for_global_sentence(global_idx);
+            self.current_page = page;
+            Some(idx)
+        } else {
+            Some(0).filter(|_| self.current_display_len() > 0)
+        };
 
         self.highlighted_audio_idx = None;
         if self.text_only_mode {
@@ -983,6 +2133,55 @@ impl ReaderSession {
         }
     }
 
+    /// Look for the saved sentence text near its old index, so edits to the
+    /// source or normalizer changes that shift sentence indices still resume
+    /// in the right place. Keeps the saved index when nothing matches well
+    /// enough, and records a [`BookmarkReanchor`] unless the index was right.
+    fn reanchor_bookmark(
+        &mut self,
+        bookmark: &crate::cache::Bookmark,
+        text: &str,
+        saved_idx: Option<usize>,
+    ) -> Option<usize> {
+        let total: usize = self.page_sentence_counts.iter().sum();
+        if total == 0 {
+            return saved_idx;
+        }
+        let around = saved_idx
+            .unwrap_or_else(|| {
+                self.page_sentence_counts
+                    .iter()
+                    .take(self.current_page)
+                    .sum()
+            })
+            .min(total - 1);
+        let anchor = text_utils::SentenceAnchor {
+            text,
+            before: bookmark.context_before.as_deref(),
+            after: bookmark.context_after.as_deref(),
+        };
+        let sentences: Vec<&String> = self.raw_page_sentences.iter().flatten().collect();
+        let found = text_utils::find_sentence_fuzzy(&sentences, &anchor, around);
+        let (sentence_idx, score) = match found {
+            Some((idx, _)) if saved_idx == Some(idx) => return saved_idx,
+            Some((idx, score)) => (idx, Some(score.clamp(0.0, 1.0))),
+            None => (around, None),
+        };
+        tracing::info!(
+            path = %self.source_path.display(),
+            saved_sentence_idx = around,
+            sentence_idx,
+            score = ?score,
+            "Re-anchored resume position"
+        );
+        self.bookmark_reanchor = Some(BookmarkReanchor {
+            saved_sentence_idx: around,
+            sentence_idx,
+            score,
+        });
+        found.map(|(idx, _)| idx).or(saved_idx)
+    }
+
     fn page_idx_for_global_sentence(&self, global_idx: usize) -> (usize, usize) {
         if self.page_sentence_counts.is_empty() {
             return (0, 0);
@@ -1032,7 +2231,11 @@ impl ReaderSession {
                 .get(self.current_page)
                 .cloned()
                 .unwrap_or_default();
-            let plan = normalizer.plan_page_cached(&self.source_path, self.current_page, &display);
+            let plan = self.book_normalizer(normalizer).plan_page_cached(
+                &self.source_path,
+                self.current_page,
+                &display,
+            );
             self.current_plan_page = Some(self.current_page);
             self.current_plan = Some(plan);
         }
@@ -1043,6 +2246,7 @@ impl ReaderSession {
                 audio_sentences: Vec::new(),
                 display_to_audio: Vec::new(),
                 audio_to_display: Vec::new(),
+                audio_phonemes: Vec::new(),
             })
     }
 
@@ -1129,22 +2333,118 @@ impl ReaderSession {
                     "Audio->display mapping fallback frequency is elevated"
                 );
             }
-            telemetry.missing.fetch_add(1, Ordering::Relaxed);
-        } else {
-            telemetry.hits.fetch_add(1, Ordering::Relaxed);
+            telemetry.missing.fetch_add(1, Ordering::Relaxed);
+        } else {
+            telemetry.hits.fetch_add(1, Ordering::Relaxed);
+        }
+        maybe_log_mapping_summary(&self.source_path);
+        mapped
+    }
+
+    fn current_sentences(&mut self, normalizer: &normalizer::TextNormalizer) -> Vec<String> {
+        if self.text_only_mode {
+            return self
+                .ensure_current_plan(normalizer)
+                .audio_sentences
+                .iter()
+                .map(|sentence| normalizer::strip_speech_markup(sentence))
+                .collect();
+        }
+        self.raw_page_sentences
+            .get(self.current_page)
+            .map(|sentences| {
+                sentences
+                    .iter()
+                    .map(|sentence| text_utils::strip_display_markers(sentence).into_owned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn current_sentence_block_ids(
+        &mut self,
+        normalizer: &normalizer::TextNormalizer,
+    ) -> Vec<usize> {
+        let page_base: usize = self
+            .page_sentence_counts
+            .iter()
+            .take(self.current_page)
+            .sum();
+        let count = self
+            .page_sentence_counts
+            .get(self.current_page)
+            .copied()
+            .unwrap_or(0);
+        let display_ids = self
+            .blocks
+            .sentence_blocks
+            .get(page_base..page_base + count)
+            .unwrap_or_default()
+            .to_vec();
+        if !self.text_only_mode {
+            return display_ids;
+        }
+        self.ensure_current_plan(normalizer)
+            .audio_to_display
+            .iter()
+            .map(|display_idx| {
+                display_ids
+                    .get(*display_idx)
+                    .or(display_ids.last())
+                    .copied()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+
+    fn current_blocks(&self, sentence_block_ids: &[usize]) -> Vec<ReaderBlock> {
+        let mut blocks: Vec<ReaderBlock> = Vec::new();
+        for (idx, id) in sentence_block_ids.iter().copied().enumerate() {
+            match blocks.last_mut() {
+                Some(block) if block.id == id => block.sentence_end = idx + 1,
+                _ => blocks.push(ReaderBlock {
+                    id,
+                    kind: self
+                        .blocks
+                        .kinds
+                        .get(id)
+                        .copied()
+                        .unwrap_or(text_utils::BlockKind::Paragraph),
+                    sentence_start: idx,
+                    sentence_end: idx + 1,
+                }),
+            }
         }
-        maybe_log_mapping_summary(&self.source_path);
-        mapped
+        blocks
     }
 
-    fn current_sentences(&mut self, normalizer: &normalizer::TextNormalizer) -> Vec<String> {
+    fn active_table_row(&self, pretty_kind: PrettyKind) -> Option<TableRowRef> {
         if self.text_only_mode {
-            return self.ensure_current_plan(normalizer).audio_sentences;
+            return None;
         }
-        self.raw_page_sentences
-            .get(self.current_page)
-            .cloned()
-            .unwrap_or_default()
+        let sentence = self
+            .raw_page_sentences
+            .get(self.current_page)?
+            .get(self.highlighted_display_idx?)?;
+        let row = text_utils::parse_table_row(sentence)?;
+        // Markdown pretty pages only render their own slice of the document.
+        let tables_before: usize = match pretty_kind {
+            PrettyKind::Markdown => self
+                .markdown_pages
+                .iter()
+                .take(self.current_page)
+                .map(|page| {
+                    page.lines()
+                        .filter(|line| text_utils::is_markdown_table_delimiter(line))
+                        .count()
+                })
+                .sum(),
+            PrettyKind::Html | PrettyKind::None => 0,
+        };
+        Some(TableRowRef {
+            table_idx: row.table.checked_sub(tables_before)?,
+            row_idx: row.row,
+        })
     }
 
     fn current_sentence_anchor_map(&self) -> Vec<Option<usize>> {
@@ -1254,7 +2554,8 @@ impl ReaderSession {
         }
     }
 
-    fn global_display_idx(&self) -> Option<usize> {
+    /// Book-wide index of the highlighted display sentence.
+    pub fn global_display_idx(&self) -> Option<usize> {
         let page_base: usize = self
             .page_sentence_counts
             .iter()
@@ -1302,6 +2603,8 @@ impl ReaderSession {
             can_seek_prev,
             can_seek_next,
             progress_pct: (progress_pct * 1000.0).round() / 1000.0,
+            range: self.tts_range,
+            loop_start: self.tts_loop_start,
         }
     }
 
@@ -1405,7 +2708,7 @@ impl ReaderSession {
         &mut self,
         normalizer: &normalizer::TextNormalizer,
     ) -> (Vec<String>, usize) {
-        let audio = self.current_audio_sentences(normalizer);
+        let mut audio = self.ensure_current_plan(normalizer).tts_inputs();
         if audio.is_empty() {
             return (audio, 0);
         }
@@ -1413,6 +2716,9 @@ impl ReaderSession {
             .current_audio_highlight_idx(normalizer)
             .unwrap_or(0)
             .min(audio.len().saturating_sub(1));
+        if let Some(end_idx) = self.tts_range_end_audio_idx(normalizer) {
+            audio.truncate(end_idx.max(start) + 1);
+        }
         (audio, start)
     }
 
@@ -1527,16 +2833,14 @@ impl ReaderSession {
                     .min(sentence_progress_total.max(1)),
             );
 
-        let effective_wpm = (BASE_WPM * self.config.tts_speed as f64).max(40.0);
-        let page_total_secs = (page_word_count as f64 / effective_wpm) * 60.0;
-        let page_time_remaining_secs = page_total_secs * (1.0 - progress_fraction);
-        let book_total_secs = (total_words as f64 / effective_wpm) * 60.0;
+        let (page_time_remaining_secs, book_time_remaining_secs) =
+            self.time_remaining_secs(normalizer);
         let global_word_progress =
             (words_up_to_current_position as f64 / total_words as f64).clamp(0.0, 1.0);
-        let book_time_remaining_secs = book_total_secs * (1.0 - global_word_progress);
 
         let page_start_percent = (words_before_page as f64 / total_words as f64) * 100.0;
         let page_end_percent = (words_up_to_page_end as f64 / total_words as f64) * 100.0;
+        let current_toc = self.current_toc_node();
 
         ReaderStats {
             page_index: self.current_page + 1,
@@ -1555,6 +2859,115 @@ impl ReaderSession {
             sentences_read_up_to_page_end: sentences_up_to_page_end,
             words_read_up_to_current_position: words_up_to_current_position,
             sentences_read_up_to_current_position: sentences_up_to_current_position,
+            current_chapter: current_toc.map(|node| node.title.clone()),
+            current_toc_entry: current_toc.map(|node| node.id),
+        }
+    }
+
+    /// Seconds left on the current page and in the book at the configured
+    /// speed, including the pause after each sentence. Sentences past the
+    /// highlight on this page use their cached clips where available; later
+    /// pages are estimated from their display length.
+    fn time_remaining_secs(&mut self, normalizer: &normalizer::TextNormalizer) -> (f64, f64) {
+        let stale = self.page_duration.as_ref().is_none_or(|estimate| {
+            estimate.page != self.current_page || estimate.voice != self.config.tts_model_path
+        });
+        if stale {
+            self.page_duration = Some(self.estimate_page_duration(normalizer));
+        }
+        let first_remaining = self
+            .current_audio_highlight_idx(normalizer)
+            .map_or(0, |idx| idx + 1);
+        let Some(estimate) = self.page_duration.as_ref() else {
+            return (0.0, 0.0);
+        };
+
+        let speed = if self.config.tts_speed <= f32::EPSILON {
+            1.0
+        } else {
+            self.config.tts_speed as f64
+        };
+        let pause = self.config.pause_after_sentence.max(0.0) as f64;
+        let remaining = estimate
+            .sentence_secs
+            .get(first_remaining..)
+            .unwrap_or_default();
+        let page_secs = remaining.iter().sum::<f64>() / speed + pause * remaining.len() as f64;
+        let later_secs = estimate.later_secs / speed + pause * estimate.later_sentences as f64;
+        (page_secs, page_secs + later_secs)
+    }
+
+    /// Plan the current page, learn from its cached clips, and time every
+    /// audio sentence on it and the display text after it.
+    fn estimate_page_duration(
+        &mut self,
+        normalizer: &normalizer::TextNormalizer,
+    ) -> PageDurationEstimate {
+        let plan = self.ensure_current_plan(normalizer);
+        self.learn_speech_rate(&plan);
+        let sentence_secs = plan
+            .audio_sentences
+            .iter()
+            .enumerate()
+            .map(|(idx, audio)| {
+                let input = plan.tts_input(idx).unwrap_or_default();
+                self.speech_rate
+                    .sentence_secs(&input, audio.chars().count())
+            })
+            .collect();
+        let later_chars: usize = self
+            .page_char_counts
+            .iter()
+            .skip(self.current_page + 1)
+            .sum();
+        PageDurationEstimate {
+            page: self.current_page,
+            voice: self.config.tts_model_path.clone(),
+            sentence_secs,
+            later_secs: self.speech_rate.display_secs(later_chars),
+            later_sentences: self
+                .page_sentence_counts
+                .iter()
+                .skip(self.current_page + 1)
+                .sum(),
+        }
+    }
+
+    /// Feed the speech-rate model this page's normalization expansion and
+    /// the durations of clips the active voice already has in the TTS cache.
+    fn learn_speech_rate(&mut self, plan: &normalizer::PageNormalization) {
+        self.speech_rate.set_voice(&self.config.tts_model_path);
+        let display_chars = self
+            .page_char_counts
+            .get(self.current_page)
+            .copied()
+            .unwrap_or(0);
+        let audio_chars = plan
+            .audio_sentences
+            .iter()
+            .map(|sentence| sentence.chars().count())
+            .sum();
+        self.speech_rate
+            .observe_page(self.current_page, display_chars, audio_chars);
+
+        let tts_dir = crate::cache::tts_dir(&self.source_path);
+        let model_path = Path::new(&self.config.tts_model_path);
+        for (idx, audio) in plan.audio_sentences.iter().enumerate() {
+            let Some(input) = plan.tts_input(idx) else {
+                continue;
+            };
+            if self.speech_rate.knows_clip(&input) {
+                continue;
+            }
+            if let Some(duration) =
+                crate::tts::cached_sentence_duration(&tts_dir, model_path, &input)
+            {
+                self.speech_rate.observe_clip(
+                    &input,
+                    audio.chars().count(),
+                    duration.as_secs_f64(),
+                );
+            }
         }
     }
 
@@ -1620,6 +3033,46 @@ impl ReaderSession {
     }
 }
 
+fn display_char_count(sentences: &[String]) -> usize {
+    sentences
+        .iter()
+        .map(|sentence| text_utils::strip_display_markers(sentence).chars().count())
+        .sum()
+}
+
+/// Lowercase alphanumeric text used to match TOC titles against blocks, so
+/// heading markers, punctuation and spacing differences are ignored.
+fn toc_match_key(text: &str) -> String {
+    text_utils::strip_display_markers(text)
+        .chars()
+        .filter(|ch| ch.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .take(TOC_MATCH_KEY_CHARS)
+        .collect()
+}
+
+/// TOC nodes in pre-order, so positions match node IDs.
+fn flatten_toc(nodes: &[TocNode]) -> Vec<&TocNode> {
+    let mut out = Vec::new();
+    for node in nodes {
+        out.push(node);
+        out.extend(flatten_toc(&node.children));
+    }
+    out
+}
+
+fn audio_chunks_for_display(
+    plan: &normalizer::PageNormalization,
+    display_idx: usize,
+) -> Vec<String> {
+    plan.audio_to_display
+        .iter()
+        .enumerate()
+        .filter(|(_, owner)| **owner == display_idx)
+        .filter_map(|(audio_idx, _)| plan.tts_input(audio_idx))
+        .collect()
+}
+
 fn count_markdown_anchors(markdown: &str) -> usize {
     markdown
         .lines()
@@ -1739,15 +3192,25 @@ pub fn load_session_for_source_with_cancel(
         effective_config = overrides;
     }
     let bookmark = crate::cache::load_bookmark(&source_path);
+    let annotations = crate::cache::load_annotations(&source_path);
+    let named_bookmarks = crate::cache::load_named_bookmarks(&source_path);
+    let recent_positions = crate::cache::load_recent_positions(&source_path);
+    let normalizer_overlay =
+        crate::cache::load_normalizer_overlay(&source_path).unwrap_or_default();
+    let book_normalizer = normalizer.with_overlay(&normalizer_overlay);
     let normalizer_threads = effective_config.normalizer_threads.max(1);
-    let session = ReaderSession::load_with_cancel(
+    let mut session = ReaderSession::load_with_cancel(
         source_path,
         effective_config,
-        normalizer,
+        &book_normalizer,
         bookmark,
         cancel,
     )?;
-    session.precompute_normalization_cache(normalizer, normalizer_threads, cancel)?;
+    session.normalizer_overlay = normalizer_overlay;
+    session.set_annotations(annotations);
+    session.set_named_bookmarks(named_bookmarks);
+    session.set_recent_positions(recent_positions);
+    session.precompute_normalization_cache(&book_normalizer, normalizer_threads, cancel)?;
     Ok(session)
 }
 
@@ -1779,7 +3242,12 @@ mod tests {
             .iter()
             .map(|page| page.split_whitespace().count())
             .collect();
+        let page_char_counts: Vec<usize> = raw_page_sentences
+            .iter()
+            .map(|sentences| display_char_count(sentences))
+            .collect();
         let page_sentence_counts: Vec<usize> = raw_page_sentences.iter().map(Vec::len).collect();
+        let blocks = text_utils::assign_blocks(&raw_page_sentences.concat());
 
         ReaderSession {
             source_path: PathBuf::from("/tmp/test.epub"),
@@ -1789,13 +3257,25 @@ mod tests {
             reading_html: None,
             has_structured_markdown: false,
             images: Vec::new(),
+            language: None,
             config: config::AppConfig::default(),
             pages,
             markdown_pages: Vec::new(),
             raw_page_sentences,
             sentence_anchor_maps: Vec::new(),
             page_word_counts,
+            page_char_counts,
             page_sentence_counts,
+            blocks,
+            page_chapters: Vec::new(),
+            toc_source: Vec::new(),
+            toc: Vec::new(),
+            annotations: Vec::new(),
+            named_bookmarks: Vec::new(),
+            nav_back: Vec::new(),
+            nav_forward: Vec::new(),
+            recent_positions: Vec::new(),
+            bookmark_reanchor: None,
             current_page: 0,
             highlighted_display_idx: Some(0),
             highlighted_audio_idx: None,
@@ -1803,9 +3283,15 @@ mod tests {
             search_query: String::new(),
             search_matches: Vec::new(),
             selected_search_match: None,
+            book_search: None,
             tts_state: TtsPlaybackState::Paused,
+            tts_range: None,
+            tts_loop_start: None,
             current_plan_page: None,
             current_plan: None,
+            speech_rate: speech_rate::SpeechRateModel::default(),
+            page_duration: None,
+            normalizer_overlay: normalizer::NormalizerOverlay::default(),
         }
     }
 
@@ -1941,7 +3427,10 @@ mod tests {
         let bookmark = crate::cache::Bookmark {
             page: 1,
             sentence_idx: Some(2),
+            global_sentence_idx: None,
             sentence_text: None,
+            context_before: None,
+            context_after: None,
             scroll_y: 0.0,
         };
         session.restore_bookmark_position(&bookmark, &normalizer);
@@ -1950,6 +3439,55 @@ mod tests {
         assert_eq!(session.highlighted_display_idx, Some(2));
     }
 
+    #[test]
+    fn restore_bookmark_position_reanchors_shifted_sentence_text() {
+        let normalizer = normalizer::TextNormalizer::default();
+        let mut session = build_test_session(&[
+            &["Alpha one.", "Bravo two."],
+            &["Charlie three.", "Delta four.", "Echo five."],
+        ]);
+        session.current_page = 1;
+        session.highlighted_display_idx = Some(1);
+        let saved = session.to_bookmark();
+        assert_eq!(saved.global_sentence_idx, Some(3));
+        assert_eq!(saved.sentence_text.as_deref(), Some("Delta four."));
+        assert_eq!(saved.context_before.as_deref(), Some("Charlie three."));
+        assert_eq!(saved.context_after.as_deref(), Some("Echo five."));
+
+        // A sentence inserted before the bookmark shifts it by one, and the
+        // saved sentence itself was lightly edited.
+        let mut session = build_test_session(&[
+            &["A new preface.", "Alpha one.", "Bravo two."],
+            &["Charlie three.", "Delta, four!", "Echo five."],
+        ]);
+        session.restore_bookmark_position(&saved, &normalizer);
+        assert_eq!(session.current_page, 1);
+        assert_eq!(session.highlighted_display_idx, Some(1));
+        let reanchor = session
+            .snapshot(PanelState::default(), &normalizer)
+            .bookmark_reanchor
+            .expect("moved anchor should be reported");
+        assert_eq!((reanchor.saved_sentence_idx, reanchor.sentence_idx), (3, 4));
+        assert!(reanchor.score.is_some_and(|score| score > 0.6));
+
+        // Text that is gone keeps the saved index and says so.
+        let mut session = build_test_session(&[&["Alpha one.", "Bravo two."], &["Zulu."]]);
+        let missing = crate::cache::Bookmark {
+            page: 0,
+            sentence_idx: Some(1),
+            global_sentence_idx: Some(1),
+            ..saved
+        };
+        session.restore_bookmark_position(&missing, &normalizer);
+        assert_eq!(
+            (session.current_page, session.highlighted_display_idx),
+            (0, Some(1))
+        );
+        let reanchor = session.bookmark_reanchor.expect("missing anchor reported");
+        assert_eq!(reanchor.sentence_idx, 1);
+        assert_eq!(reanchor.score, None);
+    }
+
     #[test]
     fn session_command_dispatch_emits_expected_action_and_snapshot() {
         let normalizer = normalizer::TextNormalizer::default();
@@ -2026,6 +3564,537 @@ mod tests {
         assert_eq!(session.current_highlight_idx(), Some(2));
     }
 
+    #[test]
+    fn active_table_row_tracks_highlight_and_markdown_page_offsets() {
+        let table = text_utils::encode_table(
+            1,
+            &["Country".to_string()],
+            &[vec!["Spain".to_string()], vec!["France".to_string()]],
+        );
+        let rows = text_utils::split_sentences(&table);
+        let rows: Vec<&str> = rows.iter().map(String::as_str).collect();
+        let mut session = build_test_session(&[&["Intro."], &rows]);
+        session.current_page = 1;
+        session.highlighted_display_idx = Some(2);
+        assert_eq!(
+            session.active_table_row(PrettyKind::Html),
+            Some(TableRowRef {
+                table_idx: 1,
+                row_idx: 2
+            })
+        );
+
+        session.markdown_pages = vec!["| A |\n| - |\n| x |".to_string(), String::new()];
+        assert_eq!(
+            session.active_table_row(PrettyKind::Markdown),
+            Some(TableRowRef {
+                table_idx: 0,
+                row_idx: 2
+            })
+        );
+
+        session.highlighted_display_idx = Some(0);
+        assert_eq!(
+            session.active_table_row(PrettyKind::Html),
+            Some(TableRowRef {
+                table_idx: 1,
+                row_idx: 0
+            })
+        );
+        session.current_page = 0;
+        assert_eq!(session.active_table_row(PrettyKind::Html), None);
+    }
+
+    #[test]
+    fn blocks_keep_book_wide_ids_across_pages() {
+        let normalizer = normalizer::TextNormalizer::default();
+        let mut session = build_test_session(&[
+            &["# Title", "\n\nFirst paragraph.", " Still first."],
+            &["Continued here.", "\n\n> Quoted line."],
+        ]);
+        session.current_page = 1;
+        let ids = session.current_sentence_block_ids(&normalizer);
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(
+            session.current_blocks(&ids),
+            vec![
+                ReaderBlock {
+                    id: 1,
+                    kind: text_utils::BlockKind::Paragraph,
+                    sentence_start: 0,
+                    sentence_end: 1,
+                },
+                ReaderBlock {
+                    id: 2,
+                    kind: text_utils::BlockKind::BlockQuote,
+                    sentence_start: 1,
+                    sentence_end: 2,
+                },
+            ]
+        );
+
+        session.current_page = 0;
+        let ids = session.current_sentence_block_ids(&normalizer);
+        assert_eq!(ids, vec![0, 1, 1]);
+        assert_eq!(
+            session.current_blocks(&ids)[0].kind,
+            text_utils::BlockKind::Heading { level: 1 }
+        );
+    }
+
+    #[test]
+    fn toc_entries_resolve_to_headings_and_track_the_current_chapter() {
+        let normalizer = normalizer::TextNormalizer::default();
+        let mut session = build_test_session(&[
+            &[
+                "Contents.",
+                "\n\n# Chapter 1.",
+                " The Start",
+                "\n\nBody one.",
+            ],
+            &[
+                "\n\n## Scene",
+                "\n\nBody two.",
+                "\n\n# Chapter 2",
+                "\n\nBody three.",
+            ],
+        ]);
+        let entry = |title: &str, children| epub_loader::TocEntry {
+            title: title.to_string(),
+            children,
+        };
+        session.toc_source = vec![
+            entry(
+                "Chapter 1: The Start",
+                vec![entry("Scene", Vec::new()), entry("Missing", Vec::new())],
+            ),
+            entry("Chapter 2", Vec::new()),
+        ];
+        session.toc = session.resolve_toc();
+
+        let resolved: Vec<_> = flatten_toc(&session.toc)
+            .into_iter()
+            .map(|node| (node.id, node.sentence_idx, node.page))
+            .collect();
+        assert_eq!(
+            resolved,
+            vec![
+                (0, Some(1), Some(0)),
+                (1, Some(4), Some(1)),
+                (2, None, None),
+                (3, Some(6), Some(1)),
+            ]
+        );
+
+        let event = session.apply_command(
+            SessionCommand::GoToTocEntry { id: 1 },
+            PanelState::default(),
+            &normalizer,
+        );
+        assert_eq!(event.action, "reader_go_to_toc_entry");
+        assert_eq!(event.snapshot.current_page, 1);
+        assert_eq!(event.snapshot.highlighted_sentence_idx, Some(0));
+        assert_eq!(
+            event.snapshot.stats.current_chapter.as_deref(),
+            Some("Scene")
+        );
+        assert_eq!(event.snapshot.stats.current_toc_entry, Some(1));
+
+        session.sentence_click(3, &normalizer);
+        let stats = session.stats(&normalizer);
+        assert_eq!(stats.current_chapter.as_deref(), Some("Chapter 2"));
+    }
+
+    #[test]
+    fn resynthesis_inputs_match_playback_inputs_with_phonemes() {
+        let normalizer = normalizer::TextNormalizer::default();
+        let mut session = build_test_session(&[&["Cato met Plato.", "Then left."]]);
+        session.current_plan_page = Some(0);
+        session.current_plan = Some(normalizer::PageNormalization {
+            audio_sentences: vec!["Cato met Plato.".to_string(), "Then left.".to_string()],
+            display_to_audio: vec![Some(0), Some(1)],
+            audio_to_display: vec![0, 1],
+            audio_phonemes: vec![
+                vec![normalizer::PhonemeSpan {
+                    start: 0,
+                    end: 4,
+                    phonemes: "ˈkeɪtoʊ".to_string(),
+                }],
+                Vec::new(),
+            ],
+        });
+
+        let resynthesized = session.tts_inputs_for_display(&[0, 1], &normalizer);
+        let (playback, _) = session.current_tts_audio_slice(&normalizer);
+        assert_eq!(resynthesized, playback);
+        assert!(resynthesized[0].starts_with("<phoneme ph=\"ˈkeɪtoʊ\">Cato</phoneme>"));
+    }
+
+    #[test]
+    fn time_remaining_uses_cached_clips_speed_and_pauses() {
+        let normalizer = normalizer::TextNormalizer::default();
+        let mut session =
+            build_test_session(&[&["Alpha beta.", "Gamma delta."], &["Epsilon zeta eta."]]);
+        session.config.tts_speed = 2.0;
+        session.config.pause_after_sentence = 0.5;
+        let plan = session.ensure_current_plan(&normalizer);
+        session
+            .speech_rate
+            .set_voice(&session.config.tts_model_path);
+        for (input, audio) in plan.tts_inputs().iter().zip(&plan.audio_sentences) {
+            session
+                .speech_rate
+                .observe_clip(input, audio.chars().count(), 4.0);
+        }
+
+        let stats = session.stats(&normalizer);
+        let pauses = 0.5 * plan.audio_sentences.len() as f64;
+        let page_secs = 4.0 * plan.audio_sentences.len() as f64 / 2.0 + pauses;
+        assert!((stats.page_time_remaining_secs - page_secs).abs() < 1e-9);
+        let later_secs = session.speech_rate.display_secs(17) / 2.0 + 0.5;
+        assert!((stats.book_time_remaining_secs - (page_secs + later_secs)).abs() < 1e-9);
+
+        session.sentence_click(1, &normalizer);
+        let stats = session.stats(&normalizer);
+        assert!(stats.page_time_remaining_secs.abs() < 1e-9);
+
+        // The estimate is kept per page and voice, not rebuilt per snapshot.
+        assert!(session.page_duration.as_ref().is_some_and(|e| e.page == 0));
+        session.config.tts_model_path = "other-voice.onnx".to_string();
+        session.sentence_click(0, &normalizer);
+        let stats = session.stats(&normalizer);
+        let one_clip = 4.0 / 2.0 + 0.5;
+        assert!((stats.page_time_remaining_secs - one_clip).abs() > 1e-6);
+        assert!(
+            session
+                .page_duration
+                .as_ref()
+                .is_some_and(|e| e.voice == "other-voice.onnx")
+        );
+    }
+
+    #[test]
+    fn tts_range_plays_across_pages_then_pauses_or_loops() {
+        let normalizer = normalizer::TextNormalizer::default();
+        let mut session = build_test_session(&[&["One.", "Two.", "Three."], &["Four.", "Five."]]);
+        let run = |session: &mut ReaderSession, command: SessionCommand| {
+            session
+                .apply_command(command, PanelState::default(), &normalizer)
+                .snapshot
+        };
+
+        let snapshot = run(
+            &mut session,
+            SessionCommand::TtsPlayRange {
+                start: 1,
+                end: 3,
+                repeat: false,
+            },
+        );
+        assert_eq!(snapshot.tts.state, TtsPlaybackState::Playing);
+        assert_eq!(snapshot.highlighted_sentence_idx, Some(1));
+        assert_eq!(session.current_tts_audio_slice(&normalizer).0.len(), 3);
+
+        run(&mut session, SessionCommand::TtsSeekNext);
+        let snapshot = run(&mut session, SessionCommand::TtsSeekNext);
+        assert_eq!(
+            (snapshot.current_page, snapshot.highlighted_sentence_idx),
+            (1, Some(0))
+        );
+        assert_eq!(session.current_tts_audio_slice(&normalizer).0.len(), 1);
+        let snapshot = run(&mut session, SessionCommand::TtsSeekNext);
+        assert_eq!(snapshot.tts.state, TtsPlaybackState::Paused);
+        assert_eq!(snapshot.highlighted_sentence_idx, Some(0));
+        assert_eq!(snapshot.tts.range, None);
+
+        // A-B loop: mark A on "Two.", B on "Four.", then wrap at the end.
+        session.go_to_sentence(0, 1, &normalizer);
+        let snapshot = run(&mut session, SessionCommand::TtsAbLoop);
+        assert_eq!(snapshot.tts.loop_start, Some(1));
+        session.go_to_sentence(1, 0, &normalizer);
+        let snapshot = run(&mut session, SessionCommand::TtsAbLoop);
+        assert_eq!(
+            snapshot.tts.range,
+            Some(TtsRange {
+                start: 1,
+                end: 3,
+                repeat: true
+            })
+        );
+        assert_eq!(snapshot.tts.loop_start, None);
+        assert_eq!(
+            (snapshot.current_page, snapshot.highlighted_sentence_idx),
+            (0, Some(1))
+        );
+        run(&mut session, SessionCommand::TtsSeekNext);
+        run(&mut session, SessionCommand::TtsSeekNext);
+        let snapshot = run(&mut session, SessionCommand::TtsSeekNext);
+        assert_eq!(snapshot.tts.state, TtsPlaybackState::Playing);
+        assert_eq!(
+            (snapshot.current_page, snapshot.highlighted_sentence_idx),
+            (0, Some(1))
+        );
+
+        // Leaving the range ends the loop.
+        let snapshot = run(
+            &mut session,
+            SessionCommand::SentenceClick { sentence_idx: 0 },
+        );
+        assert_eq!(snapshot.tts.range, None);
+    }
+
+    #[test]
+    fn annotations_report_page_ranges_and_survive_repagination() {
+        let normalizer = normalizer::TextNormalizer::default();
+        let mut session = build_test_session(&[&["One.", "Two.", "Three."], &["Four.", "Five."]]);
+        let input = |start, end| annotations::AnnotationInput {
+            sentence_start: start,
+            sentence_end: end,
+            note: Some("  across pages ".to_string()),
+            color: None,
+            tags: vec!["idea".to_string()],
+        };
+        assert!(session.add_annotation(input(3, 3)).is_err());
+        assert!(session.add_annotation(input(4, 6)).is_err());
+        let added = session.add_annotation(input(2, 4)).expect("valid range");
+        assert_eq!(added.id, 1);
+        assert_eq!(added.note.as_deref(), Some("across pages"));
+        assert_eq!(
+            (added.start_text.as_str(), added.end_text.as_str()),
+            ("Three.", "Four.")
+        );
+
+        let ranges = |session: &mut ReaderSession| {
+            session
+                .current_annotations(&normalizer)
+                .iter()
+                .map(|entry| (entry.sentence_start, entry.sentence_end))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ranges(&mut session), vec![(2, 3)]);
+        session.current_page = 1;
+        assert_eq!(ranges(&mut session), vec![(0, 1)]);
+
+        // Simulate a layout change that moves the sentences onto new pages.
+        session.raw_page_sentences = vec![
+            vec!["One.".to_string()],
+            vec![
+                "Two.".to_string(),
+                "Three.".to_string(),
+                "Four.".to_string(),
+            ],
+            vec!["Five.".to_string()],
+        ];
+        session.page_sentence_counts = vec![1, 3, 1];
+        session.annotations[0].sentence_start = 0;
+        session.annotations[0].sentence_end = 2;
+        session.reanchor_annotations();
+        assert_eq!(
+            (
+                session.annotations[0].sentence_start,
+                session.annotations[0].sentence_end
+            ),
+            (2, 4)
+        );
+        assert_eq!(ranges(&mut session), vec![(1, 3)]);
+
+        let export = session.annotation_export();
+        assert_eq!(export.book_title, "test");
+        let entry = &export.annotations[0];
+        assert_eq!(entry.page, 2);
+        assert_eq!(entry.text, "Three. Four.");
+        assert_eq!(
+            (entry.context_before.as_str(), entry.context_after.as_str()),
+            ("Two.", "Five.")
+        );
+
+        session
+            .update_annotation(1, input(0, 1))
+            .expect("annotation exists");
+        assert_eq!(session.annotations()[0].start_text, "One.");
+        session.delete_annotation(1).expect("annotation exists");
+        assert!(session.annotations().is_empty());
+        assert!(session.delete_annotation(1).is_err());
+    }
+
+    #[test]
+    fn named_bookmarks_add_rename_jump_and_delete_without_moving_resume_position() {
+        let normalizer = normalizer::TextNormalizer::default();
+        let mut session = build_test_session(&[&["One.", "Two.", "Three."], &["Four.", "Five."]]);
+        session.current_page = 1;
+        session.sentence_click(1, &normalizer);
+        let panels = PanelState::default();
+        let event = session.apply_command(
+            SessionCommand::AddBookmark { name: None },
+            panels,
+            &normalizer,
+        );
+        assert_eq!(event.action, "reader_add_bookmark");
+        assert_eq!(event.snapshot.bookmarks.len(), 1);
+        let bookmark = &event.snapshot.bookmarks[0];
+        assert_eq!(
+            (bookmark.id, bookmark.name.as_str(), bookmark.sentence_idx),
+            (1, "Page 2", 4)
+        );
+        assert_eq!(bookmark.sentence_text, "Five.");
+
+        session.apply_command(
+            SessionCommand::RenameBookmark {
+                id: 1,
+                name: "  Ending ".to_string(),
+            },
+            panels,
+            &normalizer,
+        );
+        session.apply_command(
+            SessionCommand::RenameBookmark {
+                id: 1,
+                name: " ".to_string(),
+            },
+            panels,
+            &normalizer,
+        );
+        assert_eq!(session.named_bookmarks()[0].name, "Ending");
+
+        session.set_page(0, &normalizer);
+        let resume = session.to_bookmark();
+        let event =
+            session.apply_command(SessionCommand::GoToBookmark { id: 1 }, panels, &normalizer);
+        assert_eq!(event.snapshot.current_page, 1);
+        assert_eq!(event.snapshot.highlighted_sentence_idx, Some(1));
+        assert_eq!((resume.page, resume.sentence_idx), (0, Some(0)));
+
+        // Repagination that shifts sentences keeps the bookmark on its text.
+        session.raw_page_sentences = vec![
+            vec!["Zero.".to_string(), "One.".to_string()],
+            vec!["Two.".to_string(), "Three.".to_string()],
+            vec!["Four.".to_string(), "Five.".to_string()],
+        ];
+        session.page_sentence_counts = vec![2, 2, 2];
+        session.reanchor_named_bookmarks();
+        assert_eq!(session.named_bookmarks()[0].sentence_idx, 5);
+
+        let event = session.apply_command(
+            SessionCommand::DeleteBookmark { id: 1 },
+            panels,
+            &normalizer,
+        );
+        assert!(event.snapshot.bookmarks.is_empty());
+    }
+
+    #[test]
+    fn book_search_results_survive_until_repagination_and_hits_are_jumpable() {
+        let normalizer = normalizer::TextNormalizer::default();
+        let mut session = build_test_session(&[&["A cat.", "A dog."], &["Another cat."]]);
+        let corpus = session.book_search_corpus(&normalizer, false);
+        let state = book_search::search(
+            &corpus,
+            "cat",
+            &book_search::BookSearchOptions::default(),
+            &CancellationToken::new(),
+        )
+        .expect("valid query");
+        let stored = state.clone();
+        assert!(session.accept_book_search(&corpus, state));
+
+        let results = session.book_search_results(1, 10).expect("search stored");
+        assert_eq!(results.total_hits, 2);
+        let hit = &results.hits[0];
+        assert_eq!((hit.page, hit.sentence_idx, hit.global_idx), (1, 0, 2));
+
+        let event = session.apply_command(
+            SessionCommand::GoToSentence {
+                page: hit.page,
+                sentence_idx: hit.sentence_idx,
+            },
+            PanelState::default(),
+            &normalizer,
+        );
+        assert_eq!(event.action, "reader_go_to_sentence");
+        assert_eq!(event.snapshot.current_page, 1);
+        assert_eq!(event.snapshot.highlighted_sentence_idx, Some(0));
+
+        session.repaginate(&normalizer, None);
+        assert!(session.book_search_results(0, 10).is_none());
+        session.page_sentence_counts.push(1);
+        assert!(!session.accept_book_search(&corpus, stored));
+    }
+
+    #[test]
+    fn go_to_sentence_text_finds_library_hits_away_from_the_hint() {
+        let normalizer = normalizer::TextNormalizer::default();
+        let mut session =
+            build_test_session(&[&["One.", "Two."], &["Three  words here.", "Four."]]);
+        assert!(session.go_to_sentence_text("Three words here.", 0, &normalizer));
+        assert_eq!(
+            (session.current_page, session.highlighted_display_idx),
+            (1, Some(0))
+        );
+        assert!(!session.go_to_sentence_text("Missing.", 3, &normalizer));
+        assert!(!session.go_to_sentence_text("  ", 3, &normalizer));
+        assert_eq!(session.current_page, 1);
+    }
+
+    #[test]
+    fn jumps_feed_back_forward_history_and_recent_positions() {
+        let normalizer = normalizer::TextNormalizer::default();
+        let mut session = build_test_session(&[&["A.", "B."], &["C.", "D."], &["E.", "F."]]);
+        let panels = PanelState::default();
+        let apply = |session: &mut ReaderSession, command: SessionCommand| {
+            session.apply_command(command, panels, &normalizer).snapshot
+        };
+
+        // Sequential moves are not history.
+        let snapshot = apply(&mut session, SessionCommand::NextSentence);
+        assert!(!snapshot.can_navigate_back);
+
+        apply(&mut session, SessionCommand::SetPage { page: 2 });
+        let snapshot = apply(
+            &mut session,
+            SessionCommand::GoToSentence {
+                page: 1,
+                sentence_idx: 1,
+            },
+        );
+        assert!(snapshot.can_navigate_back && !snapshot.can_navigate_forward);
+        assert_eq!(
+            snapshot
+                .recent_positions
+                .iter()
+                .map(|position| position.sentence_idx)
+                .collect::<Vec<_>>(),
+            vec![4, 1]
+        );
+        assert_eq!(snapshot.recent_positions[1].sentence_text, "B.");
+
+        let snapshot = apply(&mut session, SessionCommand::NavigateBack);
+        assert_eq!(snapshot.current_page, 2);
+        let snapshot = apply(&mut session, SessionCommand::NavigateBack);
+        assert_eq!(
+            (snapshot.current_page, snapshot.highlighted_sentence_idx),
+            (0, Some(1))
+        );
+        assert!(!snapshot.can_navigate_back && snapshot.can_navigate_forward);
+
+        let snapshot = apply(&mut session, SessionCommand::NavigateForward);
+        assert_eq!(snapshot.current_page, 2);
+
+        // A new jump drops the forward history.
+        let snapshot = apply(
+            &mut session,
+            SessionCommand::GoToRecentPosition { index: 1 },
+        );
+        assert_eq!(snapshot.highlighted_sentence_idx, Some(1));
+        assert!(!snapshot.can_navigate_forward);
+        assert_eq!(snapshot.recent_positions[0].sentence_idx, 4);
+
+        // Jumping to where we already are records nothing.
+        let before = session.nav_back.len();
+        apply(&mut session, SessionCommand::SetPage { page: 0 });
+        apply(&mut session, SessionCommand::SetPage { page: 0 });
+        assert_eq!(session.nav_back.len(), before + 1);
+    }
+
     #[test]
     fn markdown_anchor_count_detects_blocks() {
         let markdown = "# Title\n\nParagraph one.\n\n- Item one\n- Item two\n\n## Next";
@@ -2071,4 +4140,24 @@ mod tests {
             (0..sentence_count).map(Some).collect::<Vec<_>>()
         );
     }
+
+    #[test]
+    fn reload_normalizer_reports_only_sentences_whose_audio_changed() {
+        let previous = normalizer::TextNormalizer::default();
+        let mut overlay = normalizer::NormalizerOverlay::default();
+        overlay
+            .custom_pronunciations
+            .insert("Arya".to_string(), "Ar ya".to_string());
+        let current = previous.with_overlay(&overlay);
+        let mut session = build_test_session(&[&["Arya left.", "Jon stayed."]]);
+        session.highlighted_display_idx = Some(1);
+
+        let changes = session.reload_normalizer(&previous, &current);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].sentence_idx, 0);
+        assert_eq!(changes[0].before, vec!["Arya left.".to_string()]);
+        assert_eq!(changes[0].after, vec!["Ar ya left.".to_string()]);
+        assert_eq!(session.current_highlight_idx(), Some(1));
+    }
 }
@@ -0,0 +1,137 @@
+//! Speaking-rate model behind the time-remaining estimates.
+//!
+//! The session feeds it the durations of clips already in the TTS cache for
+//! the active voice. Sentences with a cached clip are timed exactly; the
+//! rest are estimated from their character count using the learned seconds
+//! per audio character, scaled by how much normalization expands display
+//! text. Until enough audio has been seen the rate leans on [`BASE_WPM`].
+//! Durations are kept at speed 1.0; callers apply `tts_speed` and pauses.
+
+use std::collections::{HashMap, HashSet};
+
+/// Prior speaking rate for a voice with no cached clips yet.
+pub const BASE_WPM: f64 = 170.0;
+/// Audio characters per spoken word, used to turn [`BASE_WPM`] into a
+/// per-character prior.
+const PRIOR_CHARS_PER_WORD: f64 = 6.0;
+/// Weight of the prior, in audio characters of measured clips.
+const PRIOR_WEIGHT_CHARS: f64 = 400.0;
+
+#[derive(Debug, Clone, Default)]
+pub struct SpeechRateModel {
+    /// TTS model path the measurements belong to.
+    voice: String,
+    /// Clip seconds by TTS input text.
+    clips: HashMap<String, f64>,
+    measured_chars: usize,
+    measured_secs: f64,
+    /// Pages whose display/audio lengths were already counted.
+    sampled_pages: HashSet<usize>,
+    display_chars: usize,
+    audio_chars: usize,
+}
+
+impl SpeechRateModel {
+    /// Drop everything learned for another voice.
+    pub fn set_voice(&mut self, voice: &str) {
+        if self.voice != voice {
+            *self = Self {
+                voice: voice.to_string(),
+                ..Self::default()
+            };
+        }
+    }
+
+    pub fn knows_clip(&self, tts_input: &str) -> bool {
+        self.clips.contains_key(tts_input)
+    }
+
+    /// Record a cached clip for a sentence with `audio_chars` spoken characters.
+    pub fn observe_clip(&mut self, tts_input: &str, audio_chars: usize, secs: f64) {
+        if secs <= 0.0 || self.clips.contains_key(tts_input) {
+            return;
+        }
+        self.clips.insert(tts_input.to_string(), secs);
+        self.measured_chars += audio_chars;
+        self.measured_secs += secs;
+    }
+
+    /// Record how long a page's normalized audio text is relative to its
+    /// display text. Each page counts once until [`Self::clear_pages`].
+    pub fn observe_page(&mut self, page: usize, display_chars: usize, audio_chars: usize) {
+        if display_chars == 0 || !self.sampled_pages.insert(page) {
+            return;
+        }
+        self.display_chars += display_chars;
+        self.audio_chars += audio_chars;
+    }
+
+    /// Forget which pages were sampled, e.g. after repagination. The
+    /// expansion ratio learned so far is kept.
+    pub fn clear_pages(&mut self) {
+        self.sampled_pages.clear();
+    }
+
+    /// Seconds per audio character, blending measured clips with the prior.
+    pub fn secs_per_audio_char(&self) -> f64 {
+        let prior = 60.0 / (BASE_WPM * PRIOR_CHARS_PER_WORD);
+        (self.measured_secs + prior * PRIOR_WEIGHT_CHARS)
+            / (self.measured_chars as f64 + PRIOR_WEIGHT_CHARS)
+    }
+
+    /// Audio characters per display character.
+    pub fn expansion(&self) -> f64 {
+        if self.display_chars == 0 {
+            1.0
+        } else {
+            self.audio_chars as f64 / self.display_chars as f64
+        }
+    }
+
+    /// Seconds for one audio sentence: its clip if cached, else an estimate.
+    pub fn sentence_secs(&self, tts_input: &str, audio_chars: usize) -> f64 {
+        self.clips
+            .get(tts_input)
+            .copied()
+            .unwrap_or_else(|| audio_chars as f64 * self.secs_per_audio_char())
+    }
+
+    /// Estimated seconds for display text that has not been normalized yet.
+    pub fn display_secs(&self, display_chars: usize) -> f64 {
+        display_chars as f64 * self.expansion() * self.secs_per_audio_char()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn learned_rate_replaces_prior_and_voice_change_resets() {
+        let mut model = SpeechRateModel::default();
+        model.set_voice("voice-a.onnx");
+        let prior = model.secs_per_audio_char();
+        assert!((prior - 60.0 / (BASE_WPM * PRIOR_CHARS_PER_WORD)).abs() < 1e-9);
+
+        // A slow voice: 0.2 s per character over plenty of audio.
+        for idx in 0..100 {
+            model.observe_clip(&format!("sentence {idx}"), 50, 10.0);
+        }
+        model.observe_clip("sentence 0", 50, 99.0);
+        assert!((model.secs_per_audio_char() - 0.2).abs() < 0.015);
+        assert_eq!(model.sentence_secs("sentence 0", 50), 10.0);
+        assert!((model.sentence_secs("uncached", 10) - 2.0).abs() < 0.15);
+
+        model.observe_page(3, 100, 150);
+        model.observe_page(3, 100, 150);
+        assert_eq!(model.expansion(), 1.5);
+        assert!((model.display_secs(10) - 3.0).abs() < 0.2);
+
+        model.set_voice("voice-a.onnx");
+        assert!(model.knows_clip("sentence 1"));
+        model.set_voice("voice-b.onnx");
+        assert!(!model.knows_clip("sentence 1"));
+        assert_eq!(model.expansion(), 1.0);
+        assert_eq!(model.secs_per_audio_char(), prior);
+    }
+}
@@ -18,6 +18,45 @@ except Exception:
 GARBAGE_RE = re.compile(r"[\uFFFD]")
 
 
+def read_outline(reader, doc) -> list:
+    """Flatten the PDF outline into {title, level, page} entries."""
+    items = []
+    if reader is not None:
+
+        def walk(entries, level):
+            for entry in entries:
+                if isinstance(entry, list):
+                    walk(entry, level + 1)
+                    continue
+                title = str(getattr(entry, "title", "") or "").strip()
+                if not title:
+                    continue
+                try:
+                    page = reader.get_destination_page_number(entry)
+                except Exception:
+                    page = None
+                items.append({"title": title, "level": level, "page": page})
+
+        try:
+            walk(reader.outline, 0)
+        except Exception:
+            return []
+    elif doc is not None:
+        try:
+            for entry in doc.get_toc():
+                title = getattr(entry, "title", None)
+                if title is None and hasattr(entry, "get_title"):
+                    title = entry.get_title()
+                title = str(title or "").strip()
+                if not title:
+                    continue
+                page = getattr(entry, "page_index", None)
+                items.append({"title": title, "level": int(entry.level), "page": page})
+        except Exception:
+            return []
+    return items
+
+
 def main() -> None:
     req = json.loads(sys.stdin.read().strip() or "{}")
     input_pdf = Path(req["input_pdf"])
@@ -121,6 +160,7 @@ def main() -> None:
         avg_chars_per_page=avg,
         garbage_ratio=garbage_ratio,
         whitespace_ratio=whitespace_ratio,
+        outline=read_outline(reader, doc),
     )
     print(json.dumps(out))
     if doc is not None:
@@ -18,12 +18,13 @@ pub use lanternleaf_core::{
     browser_tabs, cache, calibre, config, epub_loader, normalizer, pagination, quack_check,
     text_utils, tts,
 };
-use lanternleaf_core::{cancellation, session};
+use lanternleaf_core::{activity, annotations, book_search, cancellation, library_index, session};
 
 const MAX_RECENT_LIMIT: usize = 512;
 const DEFAULT_RECENT_LIMIT: usize = 64;
 const TTS_PROGRESS_POLL_INTERVAL: Duration = Duration::from_millis(8);
 const TTS_PREPARE_SENTENCE_WINDOW: usize = 8;
+const NORMALIZER_CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
 static TRACING_LOG_GUARD: OnceLock<WorkerGuard> = OnceLock::new();
 
@@ -84,6 +85,13 @@ struct OpenSourceResult {
     reader: session::ReaderSnapshot,
 }
 
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+struct AnnotationExportResult {
+    path: String,
+    count: usize,
+}
+
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export)]
 struct RecentBook {
@@ -175,6 +183,16 @@ struct ReaderStateEvent {
     reader: session::ReaderSnapshot,
 }
 
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+struct NormalizerReloadEvent {
+    #[ts(type = "number")]
+    request_id: u64,
+    changed_sentences: Vec<session::NormalizationChange>,
+    /// Why the edited config was rejected; the previous config stays active.
+    error: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
 struct BridgeError {
@@ -220,6 +238,8 @@ struct BackendState {
     open_cancel_token: Option<cancellation::CancellationToken>,
     calibre_load_request: Option<u64>,
     calibre_cancel_token: Option<cancellation::CancellationToken>,
+    book_search_request: Option<u64>,
+    book_search_cancel_token: Option<cancellation::CancellationToken>,
     tts_request: Option<TtsRequestRuntime>,
     next_request_id: u64,
     panels: session::PanelState,
@@ -228,6 +248,8 @@ struct BackendState {
     reader: Option<session::ReaderSession>,
     calibre_config: calibre::CalibreConfig,
     calibre_books: Vec<calibre::CalibreBook>,
+    /// Reading activity for the open book, logged in segments.
+    activity: Option<activity::ActivityTracker>,
 }
 
 impl BackendState {
@@ -244,6 +266,8 @@ impl BackendState {
             open_cancel_token: None,
             calibre_load_request: None,
             calibre_cancel_token: None,
+            book_search_request: None,
+            book_search_cancel_token: None,
             tts_request: None,
             next_request_id: 1,
             panels,
@@ -252,6 +276,7 @@ impl BackendState {
             reader: None,
             calibre_config: calibre::CalibreConfig::load_default(),
             calibre_books: Vec::new(),
+            activity: None,
         }
     }
 }
@@ -734,15 +759,43 @@ pub fn export_ts_bindings(out_dir: &Path) -> Result<(), String> {
     export_single_type::<LogLevelEvent>(out_dir)?;
     export_single_type::<SessionStateEvent>(out_dir)?;
     export_single_type::<ReaderStateEvent>(out_dir)?;
+    export_single_type::<NormalizerReloadEvent>(out_dir)?;
     export_single_type::<BridgeError>(out_dir)?;
     export_single_type::<session::PanelState>(out_dir)?;
     export_single_type::<session::ReaderSettingsView>(out_dir)?;
     export_single_type::<session::ReaderTtsView>(out_dir)?;
+    export_single_type::<session::TtsRange>(out_dir)?;
+    export_single_type::<session::BookmarkReanchor>(out_dir)?;
     export_single_type::<session::ReaderSettingsPatch>(out_dir)?;
     export_single_type::<session::ReaderStats>(out_dir)?;
+    export_single_type::<session::TableRowRef>(out_dir)?;
+    export_single_type::<text_utils::BlockKind>(out_dir)?;
+    export_single_type::<session::ReaderBlock>(out_dir)?;
+    export_single_type::<pagination::PageChapter>(out_dir)?;
+    export_single_type::<session::TocNode>(out_dir)?;
+    export_single_type::<cache::NamedBookmark>(out_dir)?;
+    export_single_type::<cache::RecentPosition>(out_dir)?;
+    export_single_type::<annotations::Annotation>(out_dir)?;
+    export_single_type::<annotations::AnnotationInput>(out_dir)?;
+    export_single_type::<session::ReaderAnnotation>(out_dir)?;
+    export_single_type::<annotations::AnnotationExportFormat>(out_dir)?;
+    export_single_type::<AnnotationExportResult>(out_dir)?;
+    export_single_type::<book_search::BookSearchOptions>(out_dir)?;
+    export_single_type::<book_search::BookSearchHit>(out_dir)?;
+    export_single_type::<book_search::BookSearchResults>(out_dir)?;
+    export_single_type::<library_index::LibrarySearchHit>(out_dir)?;
+    export_single_type::<library_index::LibrarySearchBook>(out_dir)?;
+    export_single_type::<activity::ActivityTotals>(out_dir)?;
+    export_single_type::<activity::ActivityBookDay>(out_dir)?;
+    export_single_type::<activity::ActivityDay>(out_dir)?;
+    export_single_type::<activity::ActivitySummary>(out_dir)?;
     export_single_type::<session::ReaderSnapshot>(out_dir)?;
     export_single_type::<session::PrettyKind>(out_dir)?;
     export_single_type::<session::TtsPlaybackState>(out_dir)?;
+    export_single_type::<normalizer::NormalizerOverlay>(out_dir)?;
+    export_single_type::<normalizer::NormalizationStage>(out_dir)?;
+    export_single_type::<normalizer::NormalizationTrace>(out_dir)?;
+    export_single_type::<session::NormalizationChange>(out_dir)?;
     export_single_type::<config::ThemeMode>(out_dir)?;
     export_single_type::<config::FontFamily>(out_dir)?;
     export_single_type::<config::FontWeight>(out_dir)?;
@@ -762,15 +815,43 @@ export type { PdfTranscriptionEvent } from "./PdfTranscriptionEvent";
 export type { LogLevelEvent } from "./LogLevelEvent";
 export type { SessionStateEvent } from "./SessionStateEvent";
 export type { ReaderStateEvent } from "./ReaderStateEvent";
+export type { NormalizerReloadEvent } from "./NormalizerReloadEvent";
 export type { BridgeError } from "./BridgeError";
 export type { PanelState } from "./PanelState";
 export type { ReaderSettingsView } from "./ReaderSettingsView";
 export type { ReaderTtsView } from "./ReaderTtsView";
+export type { TtsRange } from "./TtsRange";
+export type { BookmarkReanchor } from "./BookmarkReanchor";
 export type { ReaderSettingsPatch } from "./ReaderSettingsPatch";
 export type { ReaderStats } from "./ReaderStats";
+export type { TableRowRef } from "./TableRowRef";
+export type { BlockKind } from "./BlockKind";
+export type { ReaderBlock } from "./ReaderBlock";
+export type { PageChapter } from "./PageChapter";
+export type { TocNode } from "./TocNode";
+export type { NamedBookmark } from "./NamedBookmark";
+export type { RecentPosition } from "./RecentPosition";
+export type { Annotation } from "./Annotation";
+export type { AnnotationInput } from "./AnnotationInput";
+export type { ReaderAnnotation } from "./ReaderAnnotation";
+export type { AnnotationExportFormat } from "./AnnotationExportFormat";
+export type { AnnotationExportResult } from "./AnnotationExportResult";
+export type { BookSearchOptions } from "./BookSearchOptions";
+export type { BookSearchHit } from "./BookSearchHit";
+export type { BookSearchResults } from "./BookSearchResults";
+export type { LibrarySearchHit } from "./LibrarySearchHit";
+export type { LibrarySearchBook } from "./LibrarySearchBook";
+export type { ActivityTotals } from "./ActivityTotals";
+export type { ActivityBookDay } from "./ActivityBookDay";
+export type { ActivityDay } from "./ActivityDay";
+export type { ActivitySummary } from "./ActivitySummary";
 export type { ReaderSnapshot } from "./ReaderSnapshot";
 export type { PrettyKind } from "./PrettyKind";
 export type { TtsPlaybackState } from "./TtsPlaybackState";
+export type { NormalizerOverlay } from "./NormalizerOverlay";
+export type { NormalizationStage } from "./NormalizationStage";
+export type { NormalizationTrace } from "./NormalizationTrace";
+export type { NormalizationChange } from "./NormalizationChange";
 export type { ThemeMode } from "./ThemeMode";
 export type { FontFamily } from "./FontFamily";
 export type { FontWeight } from "./FontWeight";
@@ -799,6 +880,13 @@ fn cancel_tts_request(state: &mut BackendState) {
     }
 }
 
+fn cancel_book_search(state: &mut BackendState) {
+    state.book_search_request = None;
+    if let Some(token) = state.book_search_cancel_token.take() {
+        token.cancel();
+    }
+}
+
 fn cleanup_for_shutdown(state: &mut BackendState) -> Option<u64> {
     let cancelled_open_request = if state.open_in_flight {
         state.active_open_request
@@ -811,12 +899,14 @@ fn cleanup_for_shutdown(state: &mut BackendState) -> Option<u64> {
     if let Some(token) = state.calibre_cancel_token.take() {
         token.cancel();
     }
+    cancel_book_search(state);
     cancel_tts_request(state);
     state.calibre_load_request = None;
     if let Some(reader) = state.reader.as_mut() {
         reader.tts_stop();
     }
     persist_active_reader(state);
+    flush_activity(state, true);
     state.reader = None;
     state.mode = UiMode::Starter;
     state.active_source_path = None;
@@ -1009,7 +1099,14 @@ fn collect_tts_playback_plan(
     build_tts_playback_plan(&mut guard)
 }
 
-fn advance_tts_runtime_cursor(app: &tauri::AppHandle, runtime_request_id: u64) -> bool {
+/// Move the highlight past a sentence that finished playing and credit its
+/// audio to the activity log.
+fn advance_tts_runtime_cursor(
+    app: &tauri::AppHandle,
+    runtime_request_id: u64,
+    played: Duration,
+    words: usize,
+) -> bool {
     let state = app.state::<Mutex<BackendState>>();
     let maybe_emit = {
         let mut guard = match state.lock() {
@@ -1034,6 +1131,10 @@ fn advance_tts_runtime_cursor(app: &tauri::AppHandle, runtime_request_id: u64) -
         }
 
         let event = reader.apply_command(session::SessionCommand::TtsSeekNext, panels, &normalizer);
+        if let Some(tracker) = guard.activity.as_mut() {
+            tracker.add_tts(played, words);
+        }
+        track_reader_activity(&mut guard, false);
         let emit_request_id = allocate_request_id(&mut guard);
         Some((emit_request_id, event.snapshot))
     };
@@ -1228,7 +1329,7 @@ fn run_tts_runtime_loop(
 
         let sentence_durations = playback.sentence_durations().to_vec();
         let mut continue_playback = true;
-        for duration in sentence_durations {
+        for (offset, duration) in sentence_durations.into_iter().enumerate() {
             let mut remaining = duration.saturating_add(plan.pause_after);
             let mut last_tick = Instant::now();
             loop {
@@ -1269,7 +1370,16 @@ fn run_tts_runtime_loop(
                 return;
             }
 
-            if !advance_tts_runtime_cursor(&app, runtime_request_id) {
+            let words = plan
+                .sentences
+                .get(plan.start_idx + offset)
+                .map(|sentence| {
+                    normalizer::strip_speech_markup(sentence)
+                        .split_whitespace()
+                        .count()
+                })
+                .unwrap_or(0);
+            if !advance_tts_runtime_cursor(&app, runtime_request_id, duration, words) {
                 continue_playback = false;
                 break;
             }
@@ -1387,7 +1497,10 @@ fn sync_tts_runtime_after_reader_change(
 
 fn should_sync_tts_after_reader_command(command: &session::SessionCommand) -> bool {
     match command {
-        session::SessionCommand::GetSnapshot => false,
+        session::SessionCommand::GetSnapshot
+        | session::SessionCommand::AddBookmark { .. }
+        | session::SessionCommand::RenameBookmark { .. }
+        | session::SessionCommand::DeleteBookmark { .. } => false,
         session::SessionCommand::ApplySettings { patch } => {
             patch.font_size.is_some()
                 || patch.lines_per_page.is_some()
@@ -1406,6 +1519,18 @@ fn apply_reader_command_with_sync(
     should_sync_tts: bool,
 ) -> Result<session::ReaderSnapshot, BridgeError> {
     let action = command.action();
+    let edits_bookmarks = matches!(
+        command,
+        session::SessionCommand::AddBookmark { .. }
+            | session::SessionCommand::RenameBookmark { .. }
+            | session::SessionCommand::DeleteBookmark { .. }
+    );
+    let records_position = command.is_jump();
+    let jumped = records_position
+        || matches!(
+            command,
+            session::SessionCommand::NavigateBack | session::SessionCommand::NavigateForward
+        );
     let (snapshot, request_id) = {
         let mut guard = state
             .lock()
@@ -1418,6 +1543,14 @@ fn apply_reader_command_with_sync(
             .as_mut()
             .ok_or_else(|| bridge_error("no_reader", "No active reader session"))?;
         let event = reader.apply_command(command, panels, &normalizer);
+        if edits_bookmarks {
+            cache::save_named_bookmarks(&reader.source_path, reader.named_bookmarks())
+                .map_err(|err| bridge_error("io_error", err))?;
+        }
+        if records_position {
+            save_recent_positions(reader);
+        }
+        track_reader_activity(&mut guard, jumped);
         (event.snapshot, request_id)
     };
     emit_reader_state(app, request_id, action, &snapshot);
@@ -1428,6 +1561,78 @@ fn apply_reader_command_with_sync(
     Ok(snapshot)
 }
 
+/// Recent positions only help recover from a mis-jump, so failing to save
+/// them is logged rather than surfaced.
+fn save_recent_positions(reader: &session::ReaderSession) {
+    if let Err(err) = cache::save_recent_positions(&reader.source_path, reader.recent_positions()) {
+        warn!(
+            path = %reader.source_path.display(),
+            error = %err,
+            "Failed to save recent positions"
+        );
+    }
+}
+
+/// Close the open activity segment and append it to the log. The log only
+/// feeds the stats dashboard, so write failures are logged, not surfaced.
+fn flush_activity(state: &mut BackendState, session_ended: bool) {
+    let now = annotations::now_unix_secs();
+    let segment = if session_ended {
+        state
+            .activity
+            .take()
+            .and_then(|mut tracker| tracker.take_segment(now, true))
+    } else {
+        state
+            .activity
+            .as_mut()
+            .and_then(|tracker| tracker.take_segment(now, false))
+    };
+    if let Some(segment) = segment
+        && let Err(err) = activity::append_segment(&segment)
+    {
+        warn!(
+            path = %segment.source_path,
+            error = %err,
+            "Failed to append reading activity"
+        );
+    }
+}
+
+/// Start logging activity for the reader that was just opened, closing the
+/// previous book's session first.
+fn start_activity(state: &mut BackendState) {
+    flush_activity(state, true);
+    let Some(reader) = state.reader.as_ref() else {
+        return;
+    };
+    let title = cache::infer_recent_title(&reader.source_path);
+    let mut tracker =
+        activity::ActivityTracker::start(&reader.source_path, &title, annotations::now_unix_secs());
+    tracker.reposition(reader.current_page, reader.global_display_idx());
+    state.activity = Some(tracker);
+}
+
+/// Feed the reader position to the activity tracker. Jumps move the baseline
+/// instead of counting as progress.
+fn track_reader_activity(state: &mut BackendState, jumped: bool) {
+    let Some(reader) = state.reader.as_ref() else {
+        return;
+    };
+    let (page, sentence_idx) = (reader.current_page, reader.global_display_idx());
+    let Some(tracker) = state.activity.as_mut() else {
+        return;
+    };
+    if jumped {
+        tracker.reposition(page, sentence_idx);
+    } else {
+        tracker.advance_to(page, sentence_idx);
+    }
+    if tracker.needs_flush(annotations::now_unix_secs()) {
+        flush_activity(state, false);
+    }
+}
+
 fn apply_reader_command(
     app: &tauri::AppHandle,
     state: &State<'_, Mutex<BackendState>>,
@@ -1480,6 +1685,22 @@ async fn open_resolved_source(
     app: &tauri::AppHandle,
     state: &State<'_, Mutex<BackendState>>,
     source_path: PathBuf,
+) -> Result<OpenSourceResult, BridgeError> {
+    open_resolved_source_at(app, state, source_path, None).await
+}
+
+/// Sentence to land on once a source finishes opening, e.g. a library search hit.
+#[derive(Debug, Clone)]
+struct OpenLanding {
+    sentence_text: String,
+    sentence_idx: usize,
+}
+
+async fn open_resolved_source_at(
+    app: &tauri::AppHandle,
+    state: &State<'_, Mutex<BackendState>>,
+    source_path: PathBuf,
+    landing: Option<OpenLanding>,
 ) -> Result<OpenSourceResult, BridgeError> {
     let (request_id, cancel_token, started_session): (
         u64,
@@ -1545,12 +1766,16 @@ async fn open_resolved_source(
     let normalizer_for_task = normalizer.clone();
     let open_cancel_for_task = cancel_token.clone();
     let reader_result = tauri::async_runtime::spawn_blocking(move || {
-        session::load_session_for_source_with_cancel(
+        let result = session::load_session_for_source_with_cancel(
             source_path_for_task,
             &base_config,
             &normalizer_for_task,
             Some(&open_cancel_for_task),
-        )
+        );
+        if let Ok(reader) = result.as_ref() {
+            library_index::index_source(&reader.source_path);
+        }
+        result
     })
     .await;
 
@@ -1637,6 +1862,21 @@ async fn open_resolved_source(
 
     match reader_result {
         Ok(mut reader) => {
+            if let Some(landing) = landing.as_ref() {
+                if reader.go_to_sentence_text(
+                    &landing.sentence_text,
+                    landing.sentence_idx,
+                    &normalizer,
+                ) {
+                    save_recent_positions(&reader);
+                } else {
+                    warn!(
+                        request_id,
+                        path = %source_path.display(),
+                        "Library search hit not found after open; keeping saved position"
+                    );
+                }
+            }
             let reader_panels = panels_from_config(&reader.config);
             guard.panels = reader_panels;
             let snapshot = reader.snapshot(reader_panels, &normalizer);
@@ -1644,6 +1884,7 @@ async fn open_resolved_source(
             guard.mode = UiMode::Reader;
             guard.active_source_path = Some(source_path.clone());
             guard.reader = Some(reader);
+            start_activity(&mut guard);
             let session = to_session_state(&guard);
             let result = OpenSourceResult {
                 session: session.clone(),
@@ -1940,6 +2181,88 @@ async fn source_open_path(
     open_resolved_source(&app, &state, source).await
 }
 
+#[tauri::command]
+async fn library_search(
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<library_index::LibrarySearchBook>, BridgeError> {
+    let query = query.trim().to_string();
+    if query.is_empty() {
+        return Err(bridge_error(
+            "invalid_input",
+            "Search query cannot be empty",
+        ));
+    }
+    let limit = limit.unwrap_or(library_index::DEFAULT_LIBRARY_SEARCH_BOOKS);
+    tauri::async_runtime::spawn_blocking(move || library_index::search_library(&query, limit))
+        .await
+        .map_err(|err| {
+            bridge_error(
+                "task_join_error",
+                format!("Failed to join library search task: {err}"),
+            )
+        })
+}
+
+#[tauri::command]
+async fn library_open_hit(
+    app: tauri::AppHandle,
+    state: State<'_, Mutex<BackendState>>,
+    path: String,
+    sentence_idx: usize,
+    sentence_text: String,
+) -> Result<OpenSourceResult, BridgeError> {
+    let source = resolve_source_path(&path)?;
+    let landing = OpenLanding {
+        sentence_text,
+        sentence_idx,
+    };
+    open_resolved_source_at(&app, &state, source, Some(landing)).await
+}
+
+/// Write out the open segment so queries include the current session.
+fn flush_activity_for_query(state: &State<'_, Mutex<BackendState>>) -> Result<(), BridgeError> {
+    let mut guard = state
+        .lock()
+        .map_err(|_| bridge_error("lock_poisoned", "Backend state lock poisoned"))?;
+    flush_activity(&mut guard, false);
+    Ok(())
+}
+
+#[tauri::command]
+fn activity_summary(
+    state: State<'_, Mutex<BackendState>>,
+    source_path: Option<String>,
+    utc_offset_minutes: Option<i32>,
+) -> Result<activity::ActivitySummary, BridgeError> {
+    flush_activity_for_query(&state)?;
+    let segments = activity::load_segments();
+    Ok(activity::summary(
+        &segments,
+        source_path.as_deref(),
+        annotations::now_unix_secs(),
+        utc_offset_minutes.unwrap_or(0),
+    ))
+}
+
+#[tauri::command]
+fn activity_daily_history(
+    state: State<'_, Mutex<BackendState>>,
+    days: Option<usize>,
+    source_path: Option<String>,
+    utc_offset_minutes: Option<i32>,
+) -> Result<Vec<activity::ActivityDay>, BridgeError> {
+    flush_activity_for_query(&state)?;
+    let segments = activity::load_segments();
+    Ok(activity::daily_history(
+        &segments,
+        source_path.as_deref(),
+        annotations::now_unix_secs(),
+        days.unwrap_or(activity::DEFAULT_ACTIVITY_HISTORY_DAYS),
+        utc_offset_minutes.unwrap_or(0),
+    ))
+}
+
 #[tauri::command]
 async fn source_open_clipboard_text(
     app: tauri::AppHandle,
@@ -2257,6 +2580,99 @@ fn reader_set_page(
     apply_reader_command(&app, &state, session::SessionCommand::SetPage { page })
 }
 
+#[tauri::command]
+fn reader_go_to_toc_entry(
+    app: tauri::AppHandle,
+    state: State<'_, Mutex<BackendState>>,
+    id: usize,
+) -> Result<session::ReaderSnapshot, BridgeError> {
+    apply_reader_command(&app, &state, session::SessionCommand::GoToTocEntry { id })
+}
+
+#[tauri::command]
+fn reader_add_bookmark(
+    app: tauri::AppHandle,
+    state: State<'_, Mutex<BackendState>>,
+    name: Option<String>,
+) -> Result<session::ReaderSnapshot, BridgeError> {
+    apply_reader_command(&app, &state, session::SessionCommand::AddBookmark { name })
+}
+
+#[tauri::command]
+fn reader_rename_bookmark(
+    app: tauri::AppHandle,
+    state: State<'_, Mutex<BackendState>>,
+    id: u64,
+    name: String,
+) -> Result<session::ReaderSnapshot, BridgeError> {
+    apply_reader_command(
+        &app,
+        &state,
+        session::SessionCommand::RenameBookmark { id, name },
+    )
+}
+
+#[tauri::command]
+fn reader_delete_bookmark(
+    app: tauri::AppHandle,
+    state: State<'_, Mutex<BackendState>>,
+    id: u64,
+) -> Result<session::ReaderSnapshot, BridgeError> {
+    apply_reader_command(&app, &state, session::SessionCommand::DeleteBookmark { id })
+}
+
+#[tauri::command]
+fn reader_go_to_bookmark(
+    app: tauri::AppHandle,
+    state: State<'_, Mutex<BackendState>>,
+    id: u64,
+) -> Result<session::ReaderSnapshot, BridgeError> {
+    apply_reader_command(&app, &state, session::SessionCommand::GoToBookmark { id })
+}
+
+#[tauri::command]
+fn reader_go_to_sentence(
+    app: tauri::AppHandle,
+    state: State<'_, Mutex<BackendState>>,
+    page: usize,
+    sentence_idx: usize,
+) -> Result<session::ReaderSnapshot, BridgeError> {
+    apply_reader_command(
+        &app,
+        &state,
+        session::SessionCommand::GoToSentence { page, sentence_idx },
+    )
+}
+
+#[tauri::command]
+fn reader_go_to_recent_position(
+    app: tauri::AppHandle,
+    state: State<'_, Mutex<BackendState>>,
+    index: usize,
+) -> Result<session::ReaderSnapshot, BridgeError> {
+    apply_reader_command(
+        &app,
+        &state,
+        session::SessionCommand::GoToRecentPosition { index },
+    )
+}
+
+#[tauri::command]
+fn reader_navigate_back(
+    app: tauri::AppHandle,
+    state: State<'_, Mutex<BackendState>>,
+) -> Result<session::ReaderSnapshot, BridgeError> {
+    apply_reader_command(&app, &state, session::SessionCommand::NavigateBack)
+}
+
+#[tauri::command]
+fn reader_navigate_forward(
+    app: tauri::AppHandle,
+    state: State<'_, Mutex<BackendState>>,
+) -> Result<session::ReaderSnapshot, BridgeError> {
+    apply_reader_command(&app, &state, session::SessionCommand::NavigateForward)
+}
+
 #[tauri::command]
 fn reader_sentence_click(
     app: tauri::AppHandle,
@@ -2336,6 +2752,119 @@ fn reader_search_prev(
     apply_reader_command(&app, &state, session::SessionCommand::SearchPrev)
 }
 
+#[tauri::command]
+async fn reader_search_book(
+    state: State<'_, Mutex<BackendState>>,
+    query: String,
+    options: Option<book_search::BookSearchOptions>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<book_search::BookSearchResults, BridgeError> {
+    let options = options.unwrap_or_default();
+    let offset = offset.unwrap_or(0);
+    let limit = limit.unwrap_or(book_search::DEFAULT_BOOK_SEARCH_PAGE_SIZE);
+    let (request_id, cancel_token, corpus) = {
+        let mut guard = state
+            .lock()
+            .map_err(|_| bridge_error("lock_poisoned", "Backend state lock poisoned"))?;
+        let reader = guard
+            .reader
+            .as_ref()
+            .ok_or_else(|| bridge_error("no_reader", "No active reader session"))?;
+        let corpus = reader.book_search_corpus(&guard.normalizer, options.include_audio);
+        // A new query supersedes whatever search is still running.
+        cancel_book_search(&mut guard);
+        let request_id = allocate_request_id(&mut guard);
+        let cancel_token = cancellation::CancellationToken::new();
+        guard.book_search_request = Some(request_id);
+        guard.book_search_cancel_token = Some(cancel_token.clone());
+        (request_id, cancel_token, corpus)
+    };
+
+    info!(
+        request_id,
+        include_audio = options.include_audio,
+        "Starting book search"
+    );
+    let cancel_for_task = cancel_token.clone();
+    let (corpus, search_result) = tauri::async_runtime::spawn_blocking(move || {
+        let result = book_search::search(&corpus, &query, &options, &cancel_for_task);
+        (corpus, result)
+    })
+    .await
+    .map_err(|err| {
+        bridge_error(
+            "task_join_error",
+            format!("Failed to join book search task: {err}"),
+        )
+    })?;
+
+    let mut guard = state
+        .lock()
+        .map_err(|_| bridge_error("lock_poisoned", "Backend state lock poisoned"))?;
+    let stale_or_cancelled = guard.book_search_request != Some(request_id);
+    if !stale_or_cancelled {
+        guard.book_search_request = None;
+        guard.book_search_cancel_token = None;
+    }
+    if stale_or_cancelled || cancel_token.is_cancelled() {
+        info!(request_id, "Book search cancelled");
+        return Err(bridge_error(
+            "operation_cancelled",
+            "Book search was cancelled",
+        ));
+    }
+    let search_state = search_result.map_err(|err| bridge_error("invalid_search_query", err))?;
+    let reader = guard
+        .reader
+        .as_mut()
+        .ok_or_else(|| bridge_error("no_reader", "No active reader session"))?;
+    let results = search_state.results(offset, limit);
+    if !reader.accept_book_search(&corpus, search_state) {
+        return Err(bridge_error(
+            "operation_cancelled",
+            "Book layout changed during search",
+        ));
+    }
+    info!(
+        request_id,
+        total_hits = results.total_hits,
+        truncated = results.truncated,
+        "Book search completed"
+    );
+    Ok(results)
+}
+
+#[tauri::command]
+fn reader_search_book_results(
+    state: State<'_, Mutex<BackendState>>,
+    offset: usize,
+    limit: Option<usize>,
+) -> Result<book_search::BookSearchResults, BridgeError> {
+    let guard = state
+        .lock()
+        .map_err(|_| bridge_error("lock_poisoned", "Backend state lock poisoned"))?;
+    let reader = guard
+        .reader
+        .as_ref()
+        .ok_or_else(|| bridge_error("no_reader", "No active reader session"))?;
+    reader
+        .book_search_results(
+            offset,
+            limit.unwrap_or(book_search::DEFAULT_BOOK_SEARCH_PAGE_SIZE),
+        )
+        .ok_or_else(|| bridge_error("no_search", "No book search results available"))
+}
+
+#[tauri::command]
+fn reader_search_book_cancel(state: State<'_, Mutex<BackendState>>) -> Result<(), BridgeError> {
+    let mut guard = state
+        .lock()
+        .map_err(|_| bridge_error("lock_poisoned", "Backend state lock poisoned"))?;
+    cancel_book_search(&mut guard);
+    Ok(())
+}
+
 #[tauri::command]
 fn reader_tts_play(
     app: tauri::AppHandle,
@@ -2492,6 +3021,49 @@ fn reader_tts_repeat_sentence(
     apply_reader_command(&app, &state, session::SessionCommand::TtsRepeatSentence)
 }
 
+#[tauri::command]
+fn reader_tts_play_range(
+    app: tauri::AppHandle,
+    state: State<'_, Mutex<BackendState>>,
+    start: usize,
+    end: usize,
+    repeat: Option<bool>,
+) -> Result<session::ReaderSnapshot, BridgeError> {
+    apply_reader_command(
+        &app,
+        &state,
+        session::SessionCommand::TtsPlayRange {
+            start,
+            end,
+            repeat: repeat.unwrap_or(false),
+        },
+    )
+}
+
+#[tauri::command]
+fn reader_tts_ab_loop(
+    app: tauri::AppHandle,
+    state: State<'_, Mutex<BackendState>>,
+) -> Result<session::ReaderSnapshot, BridgeError> {
+    // Marking the A point leaves playback alone; only the B press (which
+    // starts the loop from A) needs the runtime restarted.
+    let starts_loop = {
+        let guard = state
+            .lock()
+            .map_err(|_| bridge_error("lock_poisoned", "Backend state lock poisoned"))?;
+        guard
+            .reader
+            .as_ref()
+            .is_some_and(|reader| reader.tts_loop_start().is_some())
+    };
+    apply_reader_command_with_sync(
+        &app,
+        &state,
+        session::SessionCommand::TtsAbLoop,
+        starts_loop,
+    )
+}
+
 #[tauri::command]
 fn reader_tts_precompute_page(
     app: tauri::AppHandle,
@@ -2543,6 +3115,28 @@ fn reader_tts_precompute_page(
         return Ok(snapshot);
     }
 
+    spawn_tts_prepare_job(
+        request_id,
+        source_path,
+        sentences,
+        threads,
+        progress_log_interval,
+        model_path,
+        espeak_path,
+    );
+
+    Ok(snapshot)
+}
+
+fn spawn_tts_prepare_job(
+    request_id: u64,
+    source_path: PathBuf,
+    sentences: Vec<String>,
+    threads: usize,
+    progress_log_interval: Duration,
+    model_path: PathBuf,
+    espeak_path: PathBuf,
+) {
     std::thread::spawn(move || {
         let cache_root = cache::hash_dir(&source_path).join("tts");
         let engine = match tts::TtsEngine::new(model_path, espeak_path) {
@@ -2574,9 +3168,339 @@ fn reader_tts_precompute_page(
             }
         }
     });
+}
+
+#[tauri::command]
+fn reader_tts_resynthesize_sentences(
+    state: State<'_, Mutex<BackendState>>,
+    sentence_indices: Vec<usize>,
+) -> Result<usize, BridgeError> {
+    let (
+        request_id,
+        source_path,
+        sentences,
+        threads,
+        progress_log_interval,
+        model_path,
+        espeak_path,
+    ) = {
+        let mut guard = state
+            .lock()
+            .map_err(|_| bridge_error("lock_poisoned", "Backend state lock poisoned"))?;
+        let normalizer = guard.normalizer.clone();
+        let request_id = allocate_request_id(&mut guard);
+        let reader = guard
+            .reader
+            .as_mut()
+            .ok_or_else(|| bridge_error("no_reader", "No active reader session"))?;
+        let sentences = reader.tts_inputs_for_display(&sentence_indices, &normalizer);
+        (
+            request_id,
+            reader.source_path.clone(),
+            sentences,
+            reader.config.tts_threads.max(1).min(2),
+            Duration::from_secs_f64(reader.config.tts_progress_log_interval_secs.max(0.1) as f64),
+            PathBuf::from(reader.config.tts_model_path.clone()),
+            PathBuf::from(reader.config.tts_espeak_path.clone()),
+        )
+    };
+
+    let queued = sentences.len();
+    info!(
+        request_id,
+        display_sentences = sentence_indices.len(),
+        audio_sentences = queued,
+        "Re-synthesizing sentences changed by normalizer reload"
+    );
+    if queued > 0 {
+        spawn_tts_prepare_job(
+            request_id,
+            source_path,
+            sentences,
+            threads,
+            progress_log_interval,
+            model_path,
+            espeak_path,
+        );
+    }
+    Ok(queued)
+}
+
+fn reload_normalizer_config(
+    app: &tauri::AppHandle,
+    action: &str,
+) -> Result<Vec<session::NormalizationChange>, BridgeError> {
+    let state = app.state::<Mutex<BackendState>>();
+    let reloaded = match normalizer::TextNormalizer::try_load_default() {
+        Ok(reloaded) => reloaded,
+        Err(message) => {
+            let request_id = {
+                let mut guard = state
+                    .lock()
+                    .map_err(|_| bridge_error("lock_poisoned", "Backend state lock poisoned"))?;
+                allocate_request_id(&mut guard)
+            };
+            warn!(request_id, error = %message, "Keeping previous normalizer config");
+            let _ = app.emit(
+                "normalizer-reload",
+                NormalizerReloadEvent {
+                    request_id,
+                    changed_sentences: Vec::new(),
+                    error: Some(message.clone()),
+                },
+            );
+            return Err(bridge_error("normalizer_config_invalid", message));
+        }
+    };
+    // Only swap the splitter tokens once the config is known to parse.
+    text_utils::reload_abbreviation_tokens();
+    let (request_id, snapshot, changes) = {
+        let mut guard = state
+            .lock()
+            .map_err(|_| bridge_error("lock_poisoned", "Backend state lock poisoned"))?;
+        let request_id = allocate_request_id(&mut guard);
+        let previous = std::mem::replace(&mut guard.normalizer, reloaded.clone());
+        let panels = guard.panels;
+        match guard.reader.as_mut() {
+            Some(reader) => {
+                let changes = reader.reload_normalizer(&previous, &reloaded);
+                (
+                    request_id,
+                    Some(reader.snapshot(panels, &reloaded)),
+                    changes,
+                )
+            }
+            None => (request_id, None, Vec::new()),
+        }
+    };
+
+    info!(
+        request_id,
+        changed_sentences = changes.len(),
+        "Reloaded normalizer and abbreviation config"
+    );
+    let _ = app.emit(
+        "normalizer-reload",
+        NormalizerReloadEvent {
+            request_id,
+            changed_sentences: changes.clone(),
+            error: None,
+        },
+    );
+    if let Some(snapshot) = snapshot {
+        emit_reader_state(app, request_id, action, &snapshot);
+        emit_tts_state(app, request_id, action, &snapshot.tts);
+        sync_tts_runtime_after_reader_change(app, &state);
+    }
+    Ok(changes)
+}
+
+fn spawn_normalizer_config_watcher(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        let mut last_stamp = normalizer::ConfigFileStamp::capture();
+        info!(
+            paths = ?last_stamp.paths().collect::<Vec<_>>(),
+            "Watching normalizer config files for changes"
+        );
+        loop {
+            std::thread::sleep(NORMALIZER_CONFIG_POLL_INTERVAL);
+            let stamp = normalizer::ConfigFileStamp::capture();
+            if stamp == last_stamp {
+                continue;
+            }
+            last_stamp = stamp;
+            if let Err(err) = reload_normalizer_config(&app, "normalizer_config_changed") {
+                warn!(error = %err.message, "Failed to hot-reload normalizer config");
+            }
+        }
+    });
+}
+
+#[tauri::command]
+fn normalizer_reload(
+    app: tauri::AppHandle,
+) -> Result<Vec<session::NormalizationChange>, BridgeError> {
+    reload_normalizer_config(&app, "normalizer_reload")
+}
 
+#[tauri::command]
+fn reader_get_normalizer_overlay(
+    state: State<'_, Mutex<BackendState>>,
+) -> Result<normalizer::NormalizerOverlay, BridgeError> {
+    let guard = state
+        .lock()
+        .map_err(|_| bridge_error("lock_poisoned", "Backend state lock poisoned"))?;
+    let reader = guard
+        .reader
+        .as_ref()
+        .ok_or_else(|| bridge_error("no_reader", "No active reader session"))?;
+    Ok(reader.normalizer_overlay().clone())
+}
+
+#[tauri::command]
+fn reader_set_normalizer_overlay(
+    app: tauri::AppHandle,
+    state: State<'_, Mutex<BackendState>>,
+    overlay: normalizer::NormalizerOverlay,
+) -> Result<session::ReaderSnapshot, BridgeError> {
+    let (snapshot, request_id) = {
+        let mut guard = state
+            .lock()
+            .map_err(|_| bridge_error("lock_poisoned", "Backend state lock poisoned"))?;
+        let normalizer = guard.normalizer.clone();
+        let panels = guard.panels;
+        let request_id = allocate_request_id(&mut guard);
+        let reader = guard
+            .reader
+            .as_mut()
+            .ok_or_else(|| bridge_error("no_reader", "No active reader session"))?;
+        cache::save_normalizer_overlay(&reader.source_path, &overlay)
+            .map_err(|err| bridge_error("io_error", err))?;
+        info!(
+            request_id,
+            path = %reader.source_path.display(),
+            custom_pronunciations = overlay.custom_pronunciations.len(),
+            brand_map = overlay.brand_map.len(),
+            replacements = overlay.replacements.len(),
+            "Updated per-book normalizer overlay"
+        );
+        reader.set_normalizer_overlay(overlay);
+        (reader.snapshot(panels, &normalizer), request_id)
+    };
+    emit_reader_state(&app, request_id, "reader_set_normalizer_overlay", &snapshot);
+    emit_tts_state(
+        &app,
+        request_id,
+        "reader_set_normalizer_overlay",
+        &snapshot.tts,
+    );
+    sync_tts_runtime_after_reader_change(&app, &state);
     Ok(snapshot)
 }
+
+/// Apply an annotation edit to the active reader, persist the book's
+/// annotations and publish the refreshed snapshot.
+fn apply_annotation_change<F>(
+    app: &tauri::AppHandle,
+    state: &State<'_, Mutex<BackendState>>,
+    action: &str,
+    change: F,
+) -> Result<session::ReaderSnapshot, BridgeError>
+where
+    F: FnOnce(&mut session::ReaderSession) -> Result<(), String>,
+{
+    let (snapshot, request_id) = {
+        let mut guard = state
+            .lock()
+            .map_err(|_| bridge_error("lock_poisoned", "Backend state lock poisoned"))?;
+        let normalizer = guard.normalizer.clone();
+        let panels = guard.panels;
+        let request_id = allocate_request_id(&mut guard);
+        let reader = guard
+            .reader
+            .as_mut()
+            .ok_or_else(|| bridge_error("no_reader", "No active reader session"))?;
+        change(reader).map_err(|err| bridge_error("invalid_annotation", err))?;
+        cache::save_annotations(&reader.source_path, reader.annotations())
+            .map_err(|err| bridge_error("io_error", err))?;
+        info!(
+            request_id,
+            action,
+            path = %reader.source_path.display(),
+            annotations = reader.annotations().len(),
+            "Updated book annotations"
+        );
+        (reader.snapshot(panels, &normalizer), request_id)
+    };
+    emit_reader_state(app, request_id, action, &snapshot);
+    Ok(snapshot)
+}
+
+#[tauri::command]
+fn reader_add_annotation(
+    app: tauri::AppHandle,
+    state: State<'_, Mutex<BackendState>>,
+    input: annotations::AnnotationInput,
+) -> Result<session::ReaderSnapshot, BridgeError> {
+    apply_annotation_change(&app, &state, "reader_add_annotation", |reader| {
+        reader.add_annotation(input).map(|_| ())
+    })
+}
+
+#[tauri::command]
+fn reader_update_annotation(
+    app: tauri::AppHandle,
+    state: State<'_, Mutex<BackendState>>,
+    id: u64,
+    input: annotations::AnnotationInput,
+) -> Result<session::ReaderSnapshot, BridgeError> {
+    apply_annotation_change(&app, &state, "reader_update_annotation", |reader| {
+        reader.update_annotation(id, input).map(|_| ())
+    })
+}
+
+#[tauri::command]
+fn reader_delete_annotation(
+    app: tauri::AppHandle,
+    state: State<'_, Mutex<BackendState>>,
+    id: u64,
+) -> Result<session::ReaderSnapshot, BridgeError> {
+    apply_annotation_change(&app, &state, "reader_delete_annotation", |reader| {
+        reader.delete_annotation(id)
+    })
+}
+
+#[tauri::command]
+fn reader_export_annotations(
+    state: State<'_, Mutex<BackendState>>,
+    format: annotations::AnnotationExportFormat,
+    path: Option<String>,
+) -> Result<AnnotationExportResult, BridgeError> {
+    let (source_path, export) = {
+        let guard = state
+            .lock()
+            .map_err(|_| bridge_error("lock_poisoned", "Backend state lock poisoned"))?;
+        let reader = guard
+            .reader
+            .as_ref()
+            .ok_or_else(|| bridge_error("no_reader", "No active reader session"))?;
+        (reader.source_path.clone(), reader.annotation_export())
+    };
+    let contents = annotations::render_export(&export, format);
+    let target = path
+        .map(|value| PathBuf::from(value.trim()))
+        .filter(|value| !value.as_os_str().is_empty());
+    let written =
+        cache::write_annotation_export(&source_path, format, &contents, target.as_deref())
+            .map_err(|err| bridge_error("io_error", err))?;
+    Ok(AnnotationExportResult {
+        path: written.to_string_lossy().to_string(),
+        count: export.annotations.len(),
+    })
+}
+
+#[tauri::command]
+fn reader_explain_sentence(
+    state: State<'_, Mutex<BackendState>>,
+    sentence_idx: Option<usize>,
+) -> Result<normalizer::NormalizationTrace, BridgeError> {
+    let guard = state
+        .lock()
+        .map_err(|_| bridge_error("lock_poisoned", "Backend state lock poisoned"))?;
+    let reader = guard
+        .reader
+        .as_ref()
+        .ok_or_else(|| bridge_error("no_reader", "No active reader session"))?;
+    reader
+        .explain_sentence(sentence_idx, &guard.normalizer)
+        .ok_or_else(|| {
+            bridge_error(
+                "invalid_input",
+                "No sentence at the requested index on the current page",
+            )
+        })
+}
+
 #[tauri::command]
 fn reader_close_session(
     app: tauri::AppHandle,
@@ -2978,6 +3902,10 @@ macro_rules! bridge_command_idents {
             panel_toggle_tts,
             recent_list,
             recent_delete,
+            library_search,
+            library_open_hit,
+            activity_summary,
+            activity_daily_history,
             source_open_path,
             source_open_clipboard,
             source_open_clipboard_text,
@@ -2990,6 +3918,15 @@ macro_rules! bridge_command_idents {
             reader_next_page,
             reader_prev_page,
             reader_set_page,
+            reader_go_to_toc_entry,
+            reader_add_bookmark,
+            reader_rename_bookmark,
+            reader_delete_bookmark,
+            reader_go_to_bookmark,
+            reader_go_to_sentence,
+            reader_go_to_recent_position,
+            reader_navigate_back,
+            reader_navigate_forward,
             reader_sentence_click,
             reader_next_sentence,
             reader_prev_sentence,
@@ -2998,6 +3935,9 @@ macro_rules! bridge_command_idents {
             reader_search_set_query,
             reader_search_next,
             reader_search_prev,
+            reader_search_book,
+            reader_search_book_results,
+            reader_search_book_cancel,
             reader_tts_play,
             reader_tts_pause,
             reader_tts_toggle_play_pause,
@@ -3006,7 +3946,18 @@ macro_rules! bridge_command_idents {
             reader_tts_seek_next,
             reader_tts_seek_prev,
             reader_tts_repeat_sentence,
+            reader_tts_play_range,
+            reader_tts_ab_loop,
             reader_tts_precompute_page,
+            reader_tts_resynthesize_sentences,
+            reader_get_normalizer_overlay,
+            reader_set_normalizer_overlay,
+            reader_explain_sentence,
+            reader_add_annotation,
+            reader_update_annotation,
+            reader_delete_annotation,
+            reader_export_annotations,
+            normalizer_reload,
             reader_close_session,
             app_safe_quit,
             logging_set_level,
@@ -3039,6 +3990,7 @@ const BRIDGE_EVENT_NAMES: &[&str] = &[
     "tts-state",
     "pdf-transcription",
     "log-level",
+    "normalizer-reload",
 ];
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -3092,6 +4044,7 @@ pub fn run() {
             }) {
                 warn!("Failed to install Ctrl+C signal handler: {err}");
             }
+            spawn_normalizer_config_watcher(app.handle().clone());
             Ok(())
         })
         .on_window_event(|window, event| {
@@ -3127,7 +4080,7 @@ mod tests {
 
     #[test]
     fn bridge_command_surface_remains_stable() {
-        assert_eq!(BRIDGE_COMMAND_NAMES.len(), 45);
+        assert_eq!(BRIDGE_COMMAND_NAMES.len(), 72);
         assert_eq!(BRIDGE_COMMAND_NAMES[0], "session_get_bootstrap");
         assert_eq!(
             BRIDGE_COMMAND_NAMES[BRIDGE_COMMAND_NAMES.len() - 1],
@@ -3144,8 +4097,33 @@ mod tests {
         assert!(BRIDGE_COMMAND_NAMES.contains(&"source_refresh_browser_tab"));
         assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_tts_play"));
         assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_tts_repeat_sentence"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_tts_play_range"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_tts_ab_loop"));
         assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_tts_precompute_page"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_get_normalizer_overlay"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_set_normalizer_overlay"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_explain_sentence"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_tts_resynthesize_sentences"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"normalizer_reload"));
         assert!(BRIDGE_COMMAND_NAMES.contains(&"calibre_open_book"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_go_to_toc_entry"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_add_annotation"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_update_annotation"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_delete_annotation"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_export_annotations"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_add_bookmark"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_go_to_bookmark"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_go_to_sentence"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_go_to_recent_position"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_navigate_back"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_navigate_forward"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"library_search"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"library_open_hit"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"activity_summary"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"activity_daily_history"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_search_book"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_search_book_results"));
+        assert!(BRIDGE_COMMAND_NAMES.contains(&"reader_search_book_cancel"));
     }
 
     #[test]
@@ -3160,6 +4138,7 @@ mod tests {
                 "tts-state",
                 "pdf-transcription",
                 "log-level",
+                "normalizer-reload",
             ]
         );
     }
@@ -1,3 +1,5 @@
+#[path = "../../src/ssml.rs"]
+mod ssml;
 #[path = "../../src/tts_worker.rs"]
 mod tts_worker;
 
@@ -4,10 +4,13 @@
 //! Files are stored under `.cache/lantern-leaf/` using a hash of the source file contents
 //! as the directory name so path aliases do not fragment the cache. The format
 //! is a tiny TOML file with a `page` field plus optional `sentence_idx`,
-//! `sentence_text`, and `scroll_y` for resuming inside the page.
+//! `sentence_text` (with its `context_before`/`context_after` neighbours), and
+//! `scroll_y` for resuming inside the page.
 
+use crate::annotations::{Annotation, AnnotationExportFormat, AnnotationSet};
 use crate::config::{AppConfig, parse_config, serialize_config};
 use crate::browser_tabs::{BrowserTab, BrowserTabSnapshot};
+use crate::normalizer::NormalizerOverlay;
 use epub::doc::EpubDoc;
 use image::codecs::jpeg::JpegEncoder;
 use image::imageops::FilterType;
@@ -27,6 +30,7 @@ use std::sync::{Mutex, OnceLock};
 use std::thread;
 use std::time::{Duration, UNIX_EPOCH};
 use tracing::{debug, info, warn};
+use ts_rs::TS;
 
 pub const CACHE_DIR: &str = ".cache";
 const CACHE_APP_SUBDIR: &str = "lantern-leaf";
@@ -37,6 +41,13 @@ const CONTENT_LAYOUT_VERSION_FILE: &str = "content/layout-version.txt";
 const CONTENT_TTS_TEXT_FILE: &str = "content/tts-text.txt";
 const CONTENT_READING_MARKDOWN_FILE: &str = "content/reading-markdown.md";
 const CONTENT_READING_HTML_FILE: &str = "content/reading-html.html";
+const NORMALIZER_OVERLAY_FILE: &str = "normalizer-overlay.toml";
+const ANNOTATIONS_FILE: &str = "annotations.toml";
+const NAMED_BOOKMARKS_FILE: &str = "named-bookmarks.toml";
+const RECENT_POSITIONS_FILE: &str = "recent-positions.toml";
+const ANNOTATION_EXPORTS_SUBDIR: &str = "exports";
+const LIBRARY_INDEX_FILE: &str = "library-index.json";
+const ACTIVITY_LOG_FILE: &str = "activity-log.jsonl";
 const BROWSER_TABS_SUBDIR: &str = "browser-tabs";
 const BROWSER_TAB_MANIFEST_FILE: &str = "browser-tab.lltab";
 const BROWSER_TAB_HTML_FILE: &str = "snapshot.html";
@@ -93,12 +104,61 @@ pub struct Bookmark {
     pub page: usize,
     #[serde(default)]
     pub sentence_idx: Option<usize>,
+    /// Book-wide display sentence index. Unlike `sentence_idx` it does not
+    /// depend on the page layout, so a re-anchoring search starts from it.
+    #[serde(default)]
+    pub global_sentence_idx: Option<usize>,
     #[serde(default)]
     pub sentence_text: Option<String>,
+    /// Sentences either side of `sentence_text`, used to re-anchor the
+    /// position when the book's sentences shift.
+    #[serde(default)]
+    pub context_before: Option<String>,
+    #[serde(default)]
+    pub context_after: Option<String>,
     #[serde(default = "default_scroll")]
     pub scroll_y: f32,
 }
 
+/// User-created bookmark. Kept apart from the auto-saved resume [`Bookmark`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, TS)]
+#[ts(export)]
+pub struct NamedBookmark {
+    #[ts(type = "number")]
+    pub id: u64,
+    pub name: String,
+    /// Book-wide display sentence index.
+    pub sentence_idx: usize,
+    /// Text of the bookmarked sentence, used to re-anchor after repagination.
+    pub sentence_text: String,
+    #[ts(type = "number")]
+    pub created_unix_secs: u64,
+}
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct NamedBookmarkFile {
+    #[serde(default)]
+    bookmarks: Vec<NamedBookmark>,
+}
+
+/// Reading position left behind by a non-sequential jump.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, TS)]
+#[ts(export)]
+pub struct RecentPosition {
+    /// Book-wide display sentence index.
+    pub sentence_idx: usize,
+    /// Text of that sentence, used to re-anchor after repagination.
+    pub sentence_text: String,
+    #[ts(type = "number")]
+    pub left_unix_secs: u64,
+}
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct RecentPositionFile {
+    #[serde(default)]
+    positions: Vec<RecentPosition>,
+}
+
 #[derive(Debug, Clone)]
 pub struct RecentBook {
     pub source_path: PathBuf,
@@ -108,6 +168,13 @@ pub struct RecentBook {
     pub last_opened_unix_secs: u64,
 }
 
+/// A cached book whose `tts_text` artifact is on disk.
+#[derive(Debug, Clone)]
+pub struct CachedTtsText {
+    pub source_path: PathBuf,
+    pub tts_text_path: PathBuf,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct BrowserTabSourceManifest {
     #[serde(default)]
@@ -258,7 +325,10 @@ pub fn load_bookmark(epub_path: &Path) -> Option<Bookmark> {
     Some(Bookmark {
         page: value.page,
         sentence_idx: value.sentence_idx,
+        global_sentence_idx: value.global_sentence_idx,
         sentence_text: value.sentence_text,
+        context_before: value.context_before,
+        context_after: value.context_after,
         scroll_y: value.scroll_y.unwrap_or_else(default_scroll),
     })
 }
@@ -273,7 +343,10 @@ pub fn save_bookmark(epub_path: &Path, bookmark: &Bookmark) {
     let entry = CacheEntry {
         page: bookmark.page,
         sentence_idx: bookmark.sentence_idx,
+        global_sentence_idx: bookmark.global_sentence_idx,
         sentence_text: bookmark.sentence_text.clone(),
+        context_before: bookmark.context_before.clone(),
+        context_after: bookmark.context_after.clone(),
         scroll_y: Some(bookmark.scroll_y),
     };
     if let Ok(contents) = toml::to_string(&entry) {
@@ -293,8 +366,14 @@ struct CacheEntry {
     #[serde(default)]
     sentence_idx: Option<usize>,
     #[serde(default)]
+    global_sentence_idx: Option<usize>,
+    #[serde(default)]
     sentence_text: Option<String>,
     #[serde(default)]
+    context_before: Option<String>,
+    #[serde(default)]
+    context_after: Option<String>,
+    #[serde(default)]
     scroll_y: Option<f32>,
 }
 
@@ -358,6 +437,189 @@ fn bookmark_path(epub_path: &Path) -> PathBuf {
     hash_dir(epub_path).join("bookmark.toml")
 }
 
+/// Load the user-created bookmarks for a book.
+pub fn load_named_bookmarks(epub_path: &Path) -> Vec<NamedBookmark> {
+    let path = hash_dir(epub_path).join(NAMED_BOOKMARKS_FILE);
+    let data = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            debug!(
+                path = %path.display(),
+                "No cached named bookmarks found or unreadable: {err}"
+            );
+            return Vec::new();
+        }
+    };
+    match toml::from_str::<NamedBookmarkFile>(&data) {
+        Ok(file) => file.bookmarks,
+        Err(err) => {
+            warn!(path = %path.display(), "Cached named bookmarks invalid: {err}");
+            Vec::new()
+        }
+    }
+}
+
+/// Persist the user-created bookmarks for a book. An empty list removes the file.
+pub fn save_named_bookmarks(epub_path: &Path, bookmarks: &[NamedBookmark]) -> Result<(), String> {
+    let path = hash_dir(epub_path).join(NAMED_BOOKMARKS_FILE);
+    if bookmarks.is_empty() {
+        return match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(format!("Failed to remove {}: {err}", path.display())),
+        };
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+    }
+    let file = NamedBookmarkFile {
+        bookmarks: bookmarks.to_vec(),
+    };
+    let contents = toml::to_string(&file)
+        .map_err(|err| format!("Failed to serialize named bookmarks: {err}"))?;
+    fs::write(&path, contents)
+        .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+    debug!(
+        path = %path.display(),
+        count = bookmarks.len(),
+        "Persisted named bookmarks"
+    );
+    Ok(())
+}
+
+/// Load the positions recently left by jumps, most recent first.
+pub fn load_recent_positions(epub_path: &Path) -> Vec<RecentPosition> {
+    let path = hash_dir(epub_path).join(RECENT_POSITIONS_FILE);
+    let data = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            debug!(
+                path = %path.display(),
+                "No cached recent positions found or unreadable: {err}"
+            );
+            return Vec::new();
+        }
+    };
+    match toml::from_str::<RecentPositionFile>(&data) {
+        Ok(file) => file.positions,
+        Err(err) => {
+            warn!(path = %path.display(), "Cached recent positions invalid: {err}");
+            Vec::new()
+        }
+    }
+}
+
+/// Persist the recent positions for a book. An empty list removes the file.
+pub fn save_recent_positions(epub_path: &Path, positions: &[RecentPosition]) -> Result<(), String> {
+    let path = hash_dir(epub_path).join(RECENT_POSITIONS_FILE);
+    if positions.is_empty() {
+        return match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(format!("Failed to remove {}: {err}", path.display())),
+        };
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+    }
+    let file = RecentPositionFile {
+        positions: positions.to_vec(),
+    };
+    let contents = toml::to_string(&file)
+        .map_err(|err| format!("Failed to serialize recent positions: {err}"))?;
+    fs::write(&path, contents)
+        .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+    debug!(
+        path = %path.display(),
+        count = positions.len(),
+        "Persisted recent positions"
+    );
+    Ok(())
+}
+
+/// Load the highlights and notes saved for a book, stored next to its bookmark.
+pub fn load_annotations(epub_path: &Path) -> Vec<Annotation> {
+    let path = hash_dir(epub_path).join(ANNOTATIONS_FILE);
+    let data = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            debug!(
+                path = %path.display(),
+                "No cached annotations found or unreadable: {err}"
+            );
+            return Vec::new();
+        }
+    };
+    match toml::from_str::<AnnotationSet>(&data) {
+        Ok(set) => {
+            debug!(
+                path = %path.display(),
+                count = set.annotations.len(),
+                "Loaded cached annotations"
+            );
+            set.annotations
+        }
+        Err(err) => {
+            warn!(path = %path.display(), "Cached annotations invalid: {err}");
+            Vec::new()
+        }
+    }
+}
+
+/// Write a rendered annotation export to `target`, or to the book's cache
+/// `exports/` directory when no target is given. Returns the written path.
+pub fn write_annotation_export(
+    epub_path: &Path,
+    format: AnnotationExportFormat,
+    contents: &str,
+    target: Option<&Path>,
+) -> Result<PathBuf, String> {
+    let path = target.map(Path::to_path_buf).unwrap_or_else(|| {
+        hash_dir(epub_path)
+            .join(ANNOTATION_EXPORTS_SUBDIR)
+            .join(format!("annotations.{}", format.extension()))
+    });
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+    }
+    fs::write(&path, contents)
+        .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+    info!(path = %path.display(), ?format, "Exported annotations");
+    Ok(path)
+}
+
+/// Persist a book's annotations. An empty list removes the file.
+pub fn save_annotations(epub_path: &Path, annotations: &[Annotation]) -> Result<(), String> {
+    let path = hash_dir(epub_path).join(ANNOTATIONS_FILE);
+    if annotations.is_empty() {
+        return match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(format!("Failed to remove {}: {err}", path.display())),
+        };
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+    }
+    let set = AnnotationSet {
+        annotations: annotations.to_vec(),
+    };
+    let contents =
+        toml::to_string(&set).map_err(|err| format!("Failed to serialize annotations: {err}"))?;
+    fs::write(&path, contents)
+        .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+    debug!(
+        path = %path.display(),
+        count = annotations.len(),
+        "Persisted annotations"
+    );
+    Ok(())
+}
+
 pub fn persist_dual_view_artifacts(
     source_path: &Path,
     tts_text: &str,
@@ -1533,6 +1795,42 @@ pub fn list_recent_books(limit: usize) -> Vec<RecentBook> {
     }
     books
 }
+/// Every cached book with a `tts_text` artifact whose source still resolves.
+pub fn list_cached_tts_texts() -> Vec<CachedTtsText> {
+    let Ok(entries) = fs::read_dir(cache_root()) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_type().map(|kind| kind.is_dir()).unwrap_or(false))
+        .filter_map(|entry| {
+            let tts_text_path = entry.path().join(CONTENT_TTS_TEXT_FILE);
+            if !tts_text_path.is_file() {
+                return None;
+            }
+            let source_path_raw = fs::read_to_string(entry.path().join(SOURCE_PATH_FILE)).ok()?;
+            let source_path =
+                resolve_existing_recent_source_path(Path::new(source_path_raw.trim()))?;
+            Some(CachedTtsText {
+                source_path,
+                tts_text_path,
+            })
+        })
+        .collect()
+}
+
+pub fn cached_tts_text_path(source_path: &Path) -> PathBuf {
+    hash_dir(source_path).join(CONTENT_TTS_TEXT_FILE)
+}
+
+pub fn library_index_path() -> PathBuf {
+    cache_root().join(LIBRARY_INDEX_FILE)
+}
+
+pub fn activity_log_path() -> PathBuf {
+    cache_root().join(ACTIVITY_LOG_FILE)
+}
+
 pub fn tts_dir(epub_path: &Path) -> PathBuf {
     hash_dir(epub_path).join("tts")
 }
@@ -1541,7 +1839,9 @@ pub fn normalized_dir(epub_path: &Path) -> PathBuf {
     hash_dir(epub_path).join("normalized")
 }
 
-fn infer_recent_title(source_path: &Path) -> String {
+/// Display title for a source: browser-tab title or URL, clipboard first line,
+/// EPUB metadata title, then the file stem.
+pub fn infer_recent_title(source_path: &Path) -> String {
     if let Some(manifest) = load_browser_tab_manifest(source_path) {
         let trimmed = manifest.title.trim();
         if !trimmed.is_empty() {
@@ -1796,6 +2096,54 @@ pub fn save_epub_config(epub_path: &Path, config: &AppConfig) {
     }
 }
 
+/// Load the per-book normalizer overlay (pronunciations, replacements, ...).
+pub fn load_normalizer_overlay(epub_path: &Path) -> Option<NormalizerOverlay> {
+    let path = hash_dir(epub_path).join(NORMALIZER_OVERLAY_FILE);
+    let data = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            debug!(
+                path = %path.display(),
+                "No cached normalizer overlay found or unreadable: {err}"
+            );
+            return None;
+        }
+    };
+    match toml::from_str::<NormalizerOverlay>(&data) {
+        Ok(overlay) => {
+            debug!(path = %path.display(), "Loaded cached normalizer overlay");
+            Some(overlay)
+        }
+        Err(err) => {
+            warn!(path = %path.display(), "Cached normalizer overlay invalid: {err}");
+            None
+        }
+    }
+}
+
+/// Persist the per-book normalizer overlay. An empty overlay removes the file
+/// so the book falls back to the global normalizer config.
+pub fn save_normalizer_overlay(epub_path: &Path, overlay: &NormalizerOverlay) -> Result<(), String> {
+    let path = hash_dir(epub_path).join(NORMALIZER_OVERLAY_FILE);
+    if overlay.is_empty() {
+        return match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(format!("Failed to remove {}: {err}", path.display())),
+        };
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+    }
+    let contents = toml::to_string(overlay)
+        .map_err(|err| format!("Failed to serialize normalizer overlay: {err}"))?;
+    fs::write(&path, contents)
+        .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+    debug!(path = %path.display(), "Persisted normalizer overlay");
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1880,7 +2228,10 @@ mod tests {
         let bookmark = Bookmark {
             page: 42,
             sentence_idx: Some(7),
+            global_sentence_idx: Some(312),
             sentence_text: Some("A saved sentence".to_string()),
+            context_before: Some("The one before".to_string()),
+            context_after: None,
             scroll_y: 0.37,
         };
 
@@ -1889,7 +2240,10 @@ mod tests {
 
         assert_eq!(loaded.page, 42);
         assert_eq!(loaded.sentence_idx, Some(7));
+        assert_eq!(loaded.global_sentence_idx, Some(312));
         assert_eq!(loaded.sentence_text.as_deref(), Some("A saved sentence"));
+        assert_eq!(loaded.context_before.as_deref(), Some("The one before"));
+        assert_eq!(loaded.context_after, None);
         assert!((loaded.scroll_y - 0.37).abs() < f32::EPSILON);
 
         cleanup_source_and_cache(&source);
@@ -1952,6 +2306,124 @@ sentence_text = "legacy bookmark entry"
         cleanup_source_and_cache(&source);
     }
 
+    #[test]
+    fn normalizer_overlay_roundtrip_and_empty_overlay_clears_file() {
+        let source = unique_source_path("epub");
+        write_source_file(&source);
+
+        let mut overlay = NormalizerOverlay::default();
+        overlay
+            .custom_pronunciations
+            .insert("Hermione".to_string(), "Her my oh nee".to_string());
+        overlay.drop_curly_brace_text = Some(false);
+
+        save_normalizer_overlay(&source, &overlay).expect("overlay should save");
+        let loaded = load_normalizer_overlay(&source).expect("overlay should load");
+        assert_eq!(loaded, overlay);
+
+        save_normalizer_overlay(&source, &NormalizerOverlay::default())
+            .expect("empty overlay should clear");
+        assert!(load_normalizer_overlay(&source).is_none());
+
+        cleanup_source_and_cache(&source);
+    }
+
+    #[test]
+    fn named_bookmarks_roundtrip_without_touching_resume_bookmark() {
+        let source = unique_source_path("epub");
+        write_source_file(&source);
+        save_bookmark(
+            &source,
+            &Bookmark {
+                page: 2,
+                sentence_idx: Some(1),
+                global_sentence_idx: None,
+                sentence_text: None,
+                context_before: None,
+                context_after: None,
+                scroll_y: 0.0,
+            },
+        );
+
+        let named = NamedBookmark {
+            id: 1,
+            name: "Storm scene".to_string(),
+            sentence_idx: 42,
+            sentence_text: "The wind rose.".to_string(),
+            created_unix_secs: 100,
+        };
+        save_named_bookmarks(&source, std::slice::from_ref(&named))
+            .expect("named bookmarks should save");
+        assert_eq!(load_named_bookmarks(&source), vec![named]);
+        assert_eq!(load_bookmark(&source).expect("resume bookmark").page, 2);
+
+        save_named_bookmarks(&source, &[]).expect("empty list should clear");
+        assert!(load_named_bookmarks(&source).is_empty());
+        assert!(load_bookmark(&source).is_some());
+
+        cleanup_source_and_cache(&source);
+    }
+
+    #[test]
+    fn recent_positions_roundtrip_in_order_and_empty_list_clears_file() {
+        let source = unique_source_path("epub");
+        write_source_file(&source);
+
+        let positions = vec![
+            RecentPosition {
+                sentence_idx: 90,
+                sentence_text: "Later.".to_string(),
+                left_unix_secs: 20,
+            },
+            RecentPosition {
+                sentence_idx: 3,
+                sentence_text: "Earlier.".to_string(),
+                left_unix_secs: 10,
+            },
+        ];
+        save_recent_positions(&source, &positions).expect("recent positions should save");
+        assert_eq!(load_recent_positions(&source), positions);
+
+        save_recent_positions(&source, &[]).expect("empty list should clear");
+        assert!(load_recent_positions(&source).is_empty());
+        assert!(!hash_dir(&source).join(RECENT_POSITIONS_FILE).exists());
+
+        cleanup_source_and_cache(&source);
+    }
+
+    #[test]
+    fn annotations_roundtrip_and_empty_list_clears_file() {
+        let source = unique_source_path("epub");
+        write_source_file(&source);
+
+        let annotation = Annotation {
+            id: 3,
+            sentence_start: 4,
+            sentence_end: 6,
+            start_text: "First.".to_string(),
+            end_text: "Second.".to_string(),
+            note: Some("Remember this".to_string()),
+            color: crate::config::HighlightColor {
+                r: 1.0,
+                g: 0.9,
+                b: 0.1,
+                a: 0.4,
+            },
+            tags: vec!["quote".to_string()],
+            created_unix_secs: 10,
+            updated_unix_secs: 12,
+        };
+        save_annotations(&source, std::slice::from_ref(&annotation))
+            .expect("annotations should save");
+        assert_eq!(load_annotations(&source), vec![annotation]);
+
+        save_annotations(&source, &[]).expect("empty annotations should clear");
+        assert!(load_annotations(&source).is_empty());
+        assert!(!hash_dir(&source).join(ANNOTATIONS_FILE).exists());
+
+        cleanup_source_and_cache(&source);
+    }
+
     #[test]
     fn clipboard_recent_title_uses_first_non_empty_line() {
         let source = cache_root()
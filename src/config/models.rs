@@ -303,7 +303,7 @@ impl std::fmt::Display for FontWeight {
     }
 }
 
-#[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize, TS)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize, TS)]
 #[ts(export)]
 pub struct HighlightColor {
     pub r: f32,
@@ -5,13 +5,17 @@
 
 use crate::cache::{hash_dir, is_browser_tab_manifest, load_browser_tab_manifest};
 use crate::cancellation::CancellationToken;
+use crate::text_utils::{
+    BlockKind, block_marker_kind, classify_preformatted_block, encode_note_body, encode_note_ref,
+    encode_preformatted_block, encode_table, is_markdown_table_delimiter,
+};
 use anyhow::{Context, Result};
 use epub::doc::EpubDoc;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -30,8 +34,20 @@ static RE_HTML_SVG_IMAGE_HREF: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r#"(?is)<image\b[^>]*?\b(?:xlink:href|href)\s*=\s*["']([^"']+)["'][^>]*>"#)
         .expect("valid svg image href regex")
 });
+static RE_HTML_LANG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?is)<html\b[^>]*?\b(?:xml:)?lang\s*=\s*["']([^"']+)["']"#)
+        .expect("valid html lang regex")
+});
+static RE_MARKDOWN_FOOTNOTE_DEF: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\[\^([^\]\s]+)\]:\s*(.*)$").expect("valid markdown footnote definition regex")
+});
+static RE_MARKDOWN_FOOTNOTE_REF: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[\^([^\]\s]+)\]").expect("valid markdown footnote reference regex"));
+static RE_MARKDOWN_BLOCK_BREAK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:\s*LLBLOCKBREAK)+\s*").expect("valid markdown block break regex"));
+const MARKDOWN_BLOCK_BREAK: &str = "LLBLOCKBREAK";
 const PANDOC_FILTER_REL_PATH: &str = "conf/pandoc/strip-nontext.lua";
-const PANDOC_PIPELINE_REV: &str = "pandoc-clean-v1";
+const PANDOC_PIPELINE_REV: &str = "pandoc-clean-v6-chapters";
 const QUACK_CHECK_CONFIG_REL_PATH: &str = "conf/quack-check.toml";
 const QUACK_CHECK_PIPELINE_REV: &str = "quack-check-pdf-v2";
 const QUACK_CHECK_TEXT_FILENAME_DEFAULT: &str = "transcript.txt";
@@ -55,6 +71,16 @@ pub struct LoadedBook {
     pub reading_html: Option<String>,
     pub has_structured_markdown: bool,
     pub images: Vec<BookImage>,
+    /// BCP 47 tag from the source metadata, used for sentence segmentation.
+    pub language: Option<String>,
+    /// Table of contents from the EPUB nav/NCX, the PDF outline, or the heading outline.
+    pub toc: Vec<TocEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TocEntry {
+    pub title: String,
+    pub children: Vec<TocEntry>,
 }
 
 /// Load a supported source file and return plain text plus extracted image paths.
@@ -86,9 +112,12 @@ pub fn load_book_content_with_cancel(
             Vec::new()
         }
     };
+    let language = source_language(path);
+    let toc = source_toc(path, &content);
     info!(
         path = %path.display(),
         source_type,
+        language = language.as_deref().unwrap_or("unknown"),
         pretty_kind = if content.reading_html.is_some() {
             "html"
         } else if content.reading_markdown.is_some() {
@@ -101,6 +130,7 @@ pub fn load_book_content_with_cancel(
         html_chars = content.reading_html.as_ref().map(|v| v.len()).unwrap_or(0),
         tts_chars = content.tts_text.len(),
         image_count = images.len(),
+        toc_entries = toc.len(),
         elapsed_ms = start.elapsed().as_millis(),
         "Source load complete"
     );
@@ -110,6 +140,8 @@ pub fn load_book_content_with_cancel(
         reading_html: content.reading_html,
         has_structured_markdown: content.has_structured_markdown,
         images,
+        language,
+        toc,
     })
 }
 
@@ -252,6 +284,125 @@ fn load_source_content(path: &Path, cancel: Option<&CancellationToken>) -> Resul
     );
 }
 
+/// Language tag for sentence segmentation: EPUB `dc:language`, a browser
+/// tab's `lang`, or the `<html lang>` of an HTML source.
+fn source_language(path: &Path) -> Option<String> {
+    let tag = if is_browser_tab_manifest(path) {
+        load_browser_tab_manifest(path)?.lang
+    } else if is_epub(path) {
+        let doc = EpubDoc::new(path).ok()?;
+        doc.mdata("language").map(|item| item.value.clone())
+    } else if is_native_html_source(path) {
+        let html = fs::read_to_string(path).ok()?;
+        RE_HTML_LANG.captures(&html).map(|caps| caps[1].to_string())
+    } else {
+        None
+    };
+    tag.map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+}
+
+fn source_toc(path: &Path, content: &SourceContent) -> Vec<TocEntry> {
+    let toc = if is_epub(path) {
+        EpubDoc::new(path)
+            .map(|doc| epub_nav_toc(&doc.toc))
+            .unwrap_or_default()
+    } else if is_pdf(path) {
+        read_pdf_outline(path)
+    } else {
+        Vec::new()
+    };
+    if !toc.is_empty() {
+        return toc;
+    }
+    let outline = heading_outline(content.tts_text.lines());
+    if outline.is_empty()
+        && let Some(markdown) = content.reading_markdown.as_deref()
+    {
+        return heading_outline(markdown_fenceless_lines(markdown));
+    }
+    outline
+}
+
+fn epub_nav_toc(points: &[epub::doc::NavPoint]) -> Vec<TocEntry> {
+    points
+        .iter()
+        .filter_map(|point| {
+            let title = point.label.split_whitespace().collect::<Vec<_>>().join(" ");
+            (!title.is_empty()).then(|| TocEntry {
+                title,
+                children: epub_nav_toc(&point.children),
+            })
+        })
+        .collect()
+}
+
+/// Build a nested outline from line-start `#` headings.
+fn heading_outline<'a>(lines: impl Iterator<Item = &'a str>) -> Vec<TocEntry> {
+    let entries = lines
+        .filter_map(|line| {
+            let line = line.trim();
+            let Some(BlockKind::Heading { level }) = block_marker_kind(line) else {
+                return None;
+            };
+            let title = line.trim_start_matches('#').trim();
+            (!title.is_empty()).then(|| (level as u32, title.to_string()))
+        })
+        .collect();
+    nest_toc_entries(entries)
+}
+
+/// Nest flat `(level, title)` entries so deeper levels become children of the
+/// closest shallower entry before them.
+fn nest_toc_entries(entries: Vec<(u32, String)>) -> Vec<TocEntry> {
+    let mut roots = Vec::new();
+    let mut stack: Vec<(u32, TocEntry)> = Vec::new();
+    for (level, title) in entries {
+        while stack.last().is_some_and(|(open, _)| *open >= level) {
+            let (_, done) = stack.pop().expect("stack is not empty");
+            push_toc_child(&mut roots, &mut stack, done);
+        }
+        stack.push((
+            level,
+            TocEntry {
+                title,
+                children: Vec::new(),
+            },
+        ));
+    }
+    while let Some((_, done)) = stack.pop() {
+        push_toc_child(&mut roots, &mut stack, done);
+    }
+    roots
+}
+
+fn push_toc_child(roots: &mut Vec<TocEntry>, stack: &mut [(u32, TocEntry)], entry: TocEntry) {
+    match stack.last_mut() {
+        Some((_, parent)) => parent.children.push(entry),
+        None => roots.push(entry),
+    }
+}
+
+fn read_pdf_outline(path: &Path) -> Vec<TocEntry> {
+    let outline_path = pdf_outline_path(path);
+    let Ok(raw) = fs::read_to_string(&outline_path) else {
+        return Vec::new();
+    };
+    match serde_json::from_str::<Vec<crate::quack_check::engine::OutlineItem>>(&raw) {
+        Ok(items) => nest_toc_entries(
+            items
+                .into_iter()
+                .map(|item| (item.level, item.title.trim().to_string()))
+                .filter(|(_, title)| !title.is_empty())
+                .collect(),
+        ),
+        Err(err) => {
+            warn!(path = %outline_path.display(), "Ignoring unreadable PDF outline: {err}");
+            Vec::new()
+        }
+    }
+}
+
 fn source_type_label(path: &Path) -> &'static str {
     match path
         .extension()
@@ -272,23 +423,241 @@ fn source_type_label(path: &Path) -> &'static str {
 }
 
 fn markdown_to_plain_text(input: &str) -> String {
+    let (input, placeholders) = extract_markdown_layout_blocks(input);
     match html2text::from_read(input.as_bytes(), 10_000) {
         Ok(text) => {
+            let text = restore_markdown_block_breaks(&text);
             let trimmed = text.trim();
             if trimmed.is_empty() {
                 "No textual content found in this file.".to_string()
             } else {
-                text
+                restore_markdown_placeholders(text, &placeholders)
             }
         }
         Err(_) => {
+            let input = restore_markdown_block_breaks(&input);
             if input.trim().is_empty() {
                 "No textual content found in this file.".to_string()
             } else {
-                input.to_string()
+                input
+            }
+        }
+    }
+}
+
+/// Swap fenced code blocks, pipe tables and footnotes for placeholders so
+/// html2text cannot reflow them or eat `<...>` as tags; returns the
+/// sentinel-encoded text for each placeholder. Footnote bodies are emitted at
+/// the end of the chapter (before the next top-level heading) that references
+/// them. Blank lines and heading, list and quote lines get a block-break
+/// marker so paragraph structure survives html2text's whitespace collapsing.
+fn extract_markdown_layout_blocks(input: &str) -> (String, Vec<String>) {
+    let definitions = collect_markdown_footnotes(input);
+    let chapter_level = markdown_fenceless_lines(input)
+        .filter_map(markdown_heading_level)
+        .min();
+
+    let mut out = String::with_capacity(input.len());
+    let mut placeholders: Vec<String> = Vec::new();
+    let mut pending_notes: Vec<String> = Vec::new();
+    let mut in_definition = false;
+    let mut table_count = 0usize;
+    let mut in_quote = false;
+    let mut lines = input.lines().peekable();
+    while let Some(line) = lines.next() {
+        if RE_MARKDOWN_FOOTNOTE_DEF.is_match(line) {
+            in_definition = true;
+            continue;
+        }
+        if in_definition && (line.starts_with("  ") || line.starts_with('\t')) {
+            continue;
+        }
+        in_definition = false;
+
+        if let Some((fence_char, fence_len, info)) = markdown_fence_open(line) {
+            let mut body = String::new();
+            for inner in lines.by_ref() {
+                if markdown_fence_closes(inner, fence_char, fence_len) {
+                    break;
+                }
+                body.push_str(inner);
+                body.push('\n');
+            }
+            let kind = classify_preformatted_block(info, &body);
+            out.push_str(&format!(
+                "\n{}\n\n",
+                markdown_placeholder(placeholders.len())
+            ));
+            placeholders.push(encode_preformatted_block(kind, &body));
+            continue;
+        }
+
+        if line.contains('|')
+            && lines
+                .peek()
+                .is_some_and(|next| is_markdown_table_delimiter(next))
+        {
+            lines.next();
+            let header = markdown_table_cells(line);
+            let mut rows = Vec::new();
+            while let Some(next) =
+                lines.next_if(|next| next.contains('|') && !next.trim().is_empty())
+            {
+                rows.push(markdown_table_cells(next));
+            }
+            out.push_str(&format!(
+                "\n{}\n\n",
+                markdown_placeholder(placeholders.len())
+            ));
+            placeholders.push(encode_table(table_count, &header, &rows));
+            table_count += 1;
+            continue;
+        }
+
+        if chapter_level.is_some() && markdown_heading_level(line) == chapter_level {
+            flush_markdown_notes(&mut out, &mut placeholders, &mut pending_notes);
+        }
+        let trimmed = line.trim_start();
+        let quoted = trimmed.starts_with('>');
+        let line = if trimmed.is_empty() || (quoted && !in_quote) {
+            out.push_str(MARKDOWN_BLOCK_BREAK);
+            out.push('\n');
+            line
+        } else if quoted {
+            // Continuation of the open quote: one `>` marker per block.
+            trimmed.trim_start_matches('>').trim_start()
+        } else {
+            if block_marker_kind(trimmed).is_some() {
+                out.push_str(MARKDOWN_BLOCK_BREAK);
+                out.push('\n');
+            }
+            line
+        };
+        in_quote = quoted;
+        let line = RE_MARKDOWN_FOOTNOTE_REF.replace_all(line, |caps: &regex::Captures<'_>| {
+            let label = &caps[1];
+            let Some(body) = definitions.get(label) else {
+                return caps[0].to_string();
+            };
+            let body = encode_note_body(label, body);
+            if !body.is_empty() && !pending_notes.contains(&body) {
+                pending_notes.push(body);
+            }
+            let placeholder = markdown_placeholder(placeholders.len());
+            placeholders.push(encode_note_ref(label));
+            placeholder
+        });
+        out.push_str(&line);
+        out.push('\n');
+    }
+    flush_markdown_notes(&mut out, &mut placeholders, &mut pending_notes);
+    (out, placeholders)
+}
+
+fn markdown_table_cells(line: &str) -> Vec<String> {
+    let trimmed = line.trim();
+    let trimmed = trimmed.strip_prefix('|').unwrap_or(trimmed);
+    let trimmed = trimmed.strip_suffix('|').unwrap_or(trimmed);
+    trimmed
+        .split('|')
+        .map(|cell| cell.trim().to_string())
+        .collect()
+}
+
+fn collect_markdown_footnotes(input: &str) -> HashMap<String, String> {
+    let mut definitions = HashMap::new();
+    let mut current: Option<String> = None;
+    for line in markdown_fenceless_lines(input) {
+        if let Some(caps) = RE_MARKDOWN_FOOTNOTE_DEF.captures(line) {
+            let label = caps[1].to_string();
+            definitions.insert(label.clone(), caps[2].trim().to_string());
+            current = Some(label);
+        } else if let Some(label) = current
+            .as_ref()
+            .filter(|_| line.starts_with("  ") || line.starts_with('\t'))
+        {
+            if let Some(body) = definitions.get_mut(label) {
+                body.push(' ');
+                body.push_str(line.trim());
+            }
+        } else {
+            current = None;
+        }
+    }
+    definitions
+}
+
+fn flush_markdown_notes(
+    out: &mut String,
+    placeholders: &mut Vec<String>,
+    pending: &mut Vec<String>,
+) {
+    if pending.is_empty() {
+        return;
+    }
+    out.push_str(&format!(
+        "\n{}\n\n",
+        markdown_placeholder(placeholders.len())
+    ));
+    placeholders.push(std::mem::take(pending).join("\n"));
+}
+
+/// Lines outside fenced code blocks.
+fn markdown_fenceless_lines(input: &str) -> impl Iterator<Item = &str> {
+    let mut fence: Option<(char, usize)> = None;
+    input.lines().filter(move |line| match fence {
+        Some((fence_char, fence_len)) => {
+            if markdown_fence_closes(line, fence_char, fence_len) {
+                fence = None;
+            }
+            false
+        }
+        None => {
+            if let Some((fence_char, fence_len, _)) = markdown_fence_open(line) {
+                fence = Some((fence_char, fence_len));
+                return false;
             }
+            true
         }
+    })
+}
+
+fn markdown_fence_open(line: &str) -> Option<(char, usize, &str)> {
+    let trimmed = line.trim_start();
+    let fence_char = trimmed
+        .chars()
+        .next()
+        .filter(|ch| matches!(ch, '`' | '~'))?;
+    let fence_len = trimmed.chars().take_while(|ch| *ch == fence_char).count();
+    (fence_len >= 3).then(|| (fence_char, fence_len, trimmed[fence_len..].trim()))
+}
+
+fn markdown_fence_closes(line: &str, fence_char: char, fence_len: usize) -> bool {
+    let trimmed = line.trim();
+    let closing_len = trimmed.chars().take_while(|ch| *ch == fence_char).count();
+    closing_len >= fence_len && closing_len == trimmed.chars().count()
+}
+
+fn markdown_heading_level(line: &str) -> Option<usize> {
+    let level = line.chars().take_while(|ch| *ch == '#').count();
+    ((1..=6).contains(&level) && line[level..].starts_with(' ')).then_some(level)
+}
+
+fn restore_markdown_block_breaks(text: &str) -> String {
+    RE_MARKDOWN_BLOCK_BREAK
+        .replace_all(text, "\n\n")
+        .into_owned()
+}
+
+fn restore_markdown_placeholders(mut text: String, placeholders: &[String]) -> String {
+    for (idx, value) in placeholders.iter().enumerate() {
+        text = text.replace(&markdown_placeholder(idx), value);
     }
+    text
+}
+
+fn markdown_placeholder(idx: usize) -> String {
+    format!("LLPLACEHOLDER{idx}END")
 }
 
 fn wrap_browser_tab_html(html: &str, url: &str) -> String {
@@ -405,6 +774,7 @@ fn load_pdf_with_quack_check(
     let reading_markdown = resolved.reading_markdown;
 
     write_pdf_cache(path, &signature, &tts_text)?;
+    write_pdf_outline(path, &run.outline);
     info!(
         path = %path.display(),
         total_chars = tts_text.len(),
@@ -1340,6 +1710,20 @@ fn write_pdf_cache(path: &Path, signature: &PdfCacheMeta, text: &str) -> Result<
     Ok(())
 }
 
+fn pdf_outline_path(path: &Path) -> PathBuf {
+    hash_dir(path).join("pdf").join("source-outline.json")
+}
+
+fn write_pdf_outline(path: &Path, outline: &[crate::quack_check::engine::OutlineItem]) {
+    let outline_path = pdf_outline_path(path);
+    let result = serde_json::to_string(outline)
+        .map_err(anyhow::Error::from)
+        .and_then(|json| fs::write(&outline_path, json).map_err(anyhow::Error::from));
+    if let Err(err) = result {
+        warn!(path = %outline_path.display(), "Failed to persist PDF outline: {err}");
+    }
+}
+
 fn hash_file(path: &Path) -> Result<String> {
     let bytes = fs::read(path)
         .with_context(|| format!("Failed to read file for hashing: {}", path.display()))?;
@@ -1479,6 +1863,157 @@ mod tests {
         let _ = fs::remove_file(path);
     }
 
+    #[test]
+    fn markdown_blocks_survive_as_blank_line_separated_sentences() {
+        use crate::text_utils::{BlockKind, assign_blocks, split_sentences};
+
+        let path = unique_temp_file("markdown_blocks", "md");
+        fs::write(
+            &path,
+            "# Title\n\nFirst line\nwraps here.\n\n- one\n- two\n\n> Quoted\n> text.\n\nAfter.",
+        )
+        .expect("write md fixture");
+
+        let loaded = load_book_content(&path).expect("markdown should load");
+        let sentences = split_sentences(&loaded.tts_text);
+        let blocks = assign_blocks(&sentences);
+        assert_eq!(
+            blocks.kinds,
+            vec![
+                BlockKind::Heading { level: 1 },
+                BlockKind::Paragraph,
+                BlockKind::ListItem { ordered: false },
+                BlockKind::ListItem { ordered: false },
+                BlockKind::BlockQuote,
+                BlockKind::Paragraph,
+            ]
+        );
+        assert_eq!(sentences[1].trim(), "First line wraps here.");
+        assert_eq!(sentences[4].trim(), "> Quoted text.");
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn markdown_headings_become_a_nested_toc() {
+        let path = unique_temp_file("markdown_toc", "md");
+        fs::write(
+            &path,
+            "# Part One\n\nIntro.\n\n## First\n\nBody.\n\n```\n# not a heading\n```\n\n\
+             ### Detail\n\nMore.\n\n## Second\n\nBody.\n\n# Part Two\n\nEnd.",
+        )
+        .expect("write md fixture");
+
+        let loaded = load_book_content(&path).expect("markdown should load");
+        let entry = |title: &str, children: Vec<TocEntry>| TocEntry {
+            title: title.to_string(),
+            children,
+        };
+        assert_eq!(
+            loaded.toc,
+            vec![
+                entry(
+                    "Part One",
+                    vec![
+                        entry("First", vec![entry("Detail", Vec::new())]),
+                        entry("Second", Vec::new()),
+                    ],
+                ),
+                entry("Part Two", Vec::new()),
+            ]
+        );
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn markdown_fenced_blocks_become_preformatted_lines() {
+        let path = unique_temp_file("markdown_code_block", "md");
+        fs::write(
+            &path,
+            "Intro text.\n\n```rust\nlet v: Vec<u8> = Vec::new();\n\nv.len();\n```\n\nAfter.",
+        )
+        .expect("write md fixture");
+
+        let loaded = load_book_content(&path).expect("markdown should load");
+        let sentences = crate::text_utils::split_sentences(&loaded.tts_text);
+        let code: Vec<_> = sentences
+            .iter()
+            .filter_map(|sentence| crate::text_utils::parse_preformatted_line(sentence))
+            .collect();
+        assert_eq!(code.len(), 2);
+        assert_eq!(code[0].text, "let v: Vec<u8> = Vec::new();");
+        assert_eq!(code[0].block_lines, Some(3));
+        assert_eq!(code[1].text, "v.len();");
+        assert!(loaded.tts_text.contains("Intro text."));
+        assert!(loaded.tts_text.contains("After."));
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn markdown_footnotes_link_references_to_chapter_end_bodies() {
+        let path = unique_temp_file("markdown_footnotes", "md");
+        fs::write(
+            &path,
+            "# One\n\nA claim.[^src] More.\n\n[^src]: The source\n    spans two lines.\n\n# Two\n\nNext chapter.",
+        )
+        .expect("write md fixture");
+
+        let loaded = load_book_content(&path).expect("markdown should load");
+        let sentences = crate::text_utils::split_sentences(&loaded.tts_text);
+        let claim = sentences
+            .iter()
+            .position(|sentence| sentence.contains("A claim."))
+            .expect("claim sentence");
+        let body = sentences
+            .iter()
+            .position(|sentence| crate::text_utils::parse_note_body(sentence).is_some())
+            .expect("note body sentence");
+        let next_chapter = sentences
+            .iter()
+            .position(|sentence| sentence.contains("Two"))
+            .expect("second chapter heading");
+        assert!(
+            crate::text_utils::strip_display_markers(sentences[claim].trim())
+                .ends_with("A claim.[src]")
+        );
+        assert_eq!(
+            crate::text_utils::strip_display_markers(&sentences[body]),
+            "[src] The source spans two lines."
+        );
+        assert!(claim < body && body < next_chapter);
+        assert!(!loaded.tts_text.contains("[^src]"));
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn markdown_pipe_tables_become_table_rows() {
+        let path = unique_temp_file("markdown_table", "md");
+        fs::write(
+            &path,
+            "Populations:\n\n| Country | Population |\n| --- | ---: |\n| Spain | 48 million |\n| France | 68 million |\n\nDone.",
+        )
+        .expect("write md fixture");
+
+        let loaded = load_book_content(&path).expect("markdown should load");
+        let sentences = crate::text_utils::split_sentences(&loaded.tts_text);
+        let rows: Vec<_> = sentences
+            .iter()
+            .filter_map(|sentence| crate::text_utils::parse_table_row(sentence))
+            .collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].number, None);
+        assert_eq!(rows[0].cells, vec!["Country", "Population"]);
+        assert_eq!(rows[2].row, 2);
+        assert_eq!(rows[2].columns, vec!["Country", "Population"]);
+        assert_eq!(rows[2].cells, vec!["France", "68 million"]);
+        assert!(loaded.tts_text.contains("Done."));
+
+        let _ = fs::remove_file(path);
+    }
+
     #[test]
     fn text_source_falls_back_without_markdown() {
         let path = unique_temp_file("text_contract", "txt");
@@ -1541,7 +2076,13 @@ mod tests {
         assert!(loaded.tts_text.contains("Second readable paragraph."));
         assert!(!loaded.tts_text.contains("console.log"));
         assert!(!loaded.tts_text.contains("body { color: red; }"));
-        assert!(!loaded.tts_text.contains("Ignore table text"));
+        let sentences = crate::text_utils::split_sentences(&loaded.tts_text);
+        let table_cells: Vec<&str> = sentences
+            .iter()
+            .filter_map(|sentence| crate::text_utils::parse_table_row(sentence))
+            .flat_map(|row| row.cells)
+            .collect();
+        assert_eq!(table_cells, vec!["Ignore table text"]);
 
         let _ = fs::remove_file(path);
     }
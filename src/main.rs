@@ -5,6 +5,7 @@
 //! - It hosts the `--tts-worker` subprocess mode used by the Piper worker pool.
 //! - It provides a clear migration message when launched directly.
 
+mod ssml;
 mod tts_worker;
 
 fn main() {
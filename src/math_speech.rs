@@ -0,0 +1,756 @@
+//! Spoken-English rendering of TeX math.
+//!
+//! PDF transcripts and markdown carry LaTeX directly (`$...$`, `$$...$$`,
+//! `\(...\)`, `\[...\]` or bare commands such as `\frac{a}{b}`); EPUB3 MathML
+//! reaches the reader as TeX through the pandoc filter. [`find_math_spans`]
+//! locates those spans and [`verbalize`] reads one expression aloud ("a over
+//! b", "x squared"). Verbalization returns `None` for anything outside the
+//! supported subset so callers can speak a fallback announcement instead.
+
+use std::ops::Range;
+
+/// One math span in a text: `range` covers the delimiters, `tex` only the body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MathSpan {
+    pub range: Range<usize>,
+    pub tex: Range<usize>,
+    pub display: bool,
+}
+
+/// Commands that start a math span even without `$` delimiters.
+const BARE_COMMANDS: &[&str] = &[
+    "frac", "dfrac", "tfrac", "sqrt", "binom", "sum", "prod", "int", "iint", "oint", "lim",
+    "mathbb", "mathcal", "mathbf",
+];
+
+/// Commands whose braced argument is read as plain words.
+const TEXT_COMMANDS: &[&str] = &[
+    "text",
+    "textrm",
+    "textit",
+    "textbf",
+    "textsf",
+    "texttt",
+    "mbox",
+    "mathrm",
+    "mathit",
+    "mathsf",
+    "mathtt",
+    "operatorname",
+];
+
+/// Spacing and sizing commands that have no spoken form.
+const SILENT_COMMANDS: &[&str] = &[
+    ",",
+    ";",
+    ":",
+    "!",
+    " ",
+    "\\",
+    "{",
+    "}",
+    "|",
+    "quad",
+    "qquad",
+    "left",
+    "right",
+    "big",
+    "Big",
+    "bigg",
+    "Bigg",
+    "bigl",
+    "bigr",
+    "Bigl",
+    "Bigr",
+    "displaystyle",
+    "textstyle",
+    "limits",
+    "nolimits",
+];
+
+pub fn find_math_spans(text: &str) -> Vec<MathSpan> {
+    let bytes = text.as_bytes();
+    let mut spans = Vec::new();
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let span = match bytes[idx] {
+            b'$' if idx > 0 && bytes[idx - 1] == b'\\' => None,
+            b'$' if bytes.get(idx + 1) == Some(&b'$') => {
+                delimited_span(text, idx, "$$", "$$", true)
+            }
+            b'$' => dollar_span(text, idx),
+            b'\\' => match bytes.get(idx + 1) {
+                Some(b'(') => delimited_span(text, idx, "\\(", "\\)", false),
+                Some(b'[') => delimited_span(text, idx, "\\[", "\\]", true),
+                _ => bare_command_span(text, idx),
+            },
+            _ => None,
+        };
+        match span {
+            Some(span) => {
+                idx = span.range.end;
+                spans.push(span);
+            }
+            None => idx += 1,
+        }
+    }
+    spans
+}
+
+fn delimited_span(
+    text: &str,
+    start: usize,
+    open: &str,
+    close: &str,
+    display: bool,
+) -> Option<MathSpan> {
+    let body_start = start + open.len();
+    let body_len = text[body_start..].find(close)?;
+    let body_end = body_start + body_len;
+    let body = &text[body_start..body_end];
+    if body.trim().is_empty() || (!display && body.contains('\n')) {
+        return None;
+    }
+    Some(MathSpan {
+        range: start..body_end + close.len(),
+        tex: body_start..body_end,
+        display,
+    })
+}
+
+/// Inline `$...$` in the pandoc sense: the body may not start or end with
+/// whitespace and the closing `$` may not touch a digit. A `$` after
+/// whitespace opens something else, so prices such as "$5 and $10" stay text.
+fn dollar_span(text: &str, start: usize) -> Option<MathSpan> {
+    let body_start = start + 1;
+    let first = text[body_start..].chars().next()?;
+    if first.is_whitespace() {
+        return None;
+    }
+    for (offset, ch) in text[body_start..].char_indices() {
+        if ch == '\n' {
+            return None;
+        }
+        if ch != '$' || offset == 0 {
+            continue;
+        }
+        let close = body_start + offset;
+        let before = text[..close].chars().next_back()?;
+        let after = text[close + 1..].chars().next();
+        if before == '\\' {
+            continue;
+        }
+        if before.is_whitespace() || text[close + 1..].starts_with('$') {
+            return None;
+        }
+        if after.is_some_and(|ch| ch.is_ascii_digit()) {
+            return None;
+        }
+        return Some(MathSpan {
+            range: start..close + 1,
+            tex: body_start..close,
+            display: false,
+        });
+    }
+    None
+}
+
+/// A bare `\command` from [`BARE_COMMANDS`] or a Greek letter, extended over
+/// its braced arguments and trailing scripts.
+fn bare_command_span(text: &str, start: usize) -> Option<MathSpan> {
+    let name_len = text[start + 1..]
+        .find(|ch: char| !ch.is_ascii_alphabetic())
+        .unwrap_or(text.len() - start - 1);
+    let name = &text[start + 1..start + 1 + name_len];
+    if !BARE_COMMANDS.contains(&name) && greek_letter(name).is_none() {
+        return None;
+    }
+
+    let bytes = text.as_bytes();
+    let mut end = start + 1 + name_len;
+    loop {
+        match bytes.get(end) {
+            Some(b'{') => end = balanced_group_end(text, end, b'{', b'}')?,
+            Some(b'[') if name == "sqrt" => end = balanced_group_end(text, end, b'[', b']')?,
+            Some(b'^' | b'_') => match bytes.get(end + 1) {
+                Some(b'{') => end = balanced_group_end(text, end + 1, b'{', b'}')?,
+                Some(ch) if ch.is_ascii_alphanumeric() => end += 2,
+                _ => break,
+            },
+            _ => break,
+        }
+    }
+    Some(MathSpan {
+        range: start..end,
+        tex: start..end,
+        display: false,
+    })
+}
+
+fn balanced_group_end(text: &str, open_idx: usize, open: u8, close: u8) -> Option<usize> {
+    let mut depth = 0usize;
+    for (offset, byte) in text.as_bytes()[open_idx..].iter().enumerate() {
+        if *byte == open {
+            depth += 1;
+        } else if *byte == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(open_idx + offset + 1);
+            }
+        }
+    }
+    None
+}
+
+/// Read one TeX expression aloud, or `None` when it uses unsupported commands
+/// or has unbalanced groups.
+pub fn verbalize(tex: &str) -> Option<String> {
+    let mut parser = Parser {
+        tokens: tokenize(tex)?,
+        pos: 0,
+    };
+    let words = parser.parse_sequence(Closer::End)?;
+
+    let mut out = String::new();
+    for word in words.iter().map(|word| word.trim()) {
+        if word.is_empty() {
+            continue;
+        }
+        if word == "," {
+            if !out.is_empty() && !out.ends_with(',') {
+                out.push(',');
+            }
+            continue;
+        }
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        out.push_str(word);
+    }
+    let out = out.trim_end_matches(',').to_string();
+    (!out.is_empty()).then_some(out)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token<'a> {
+    Command(&'a str),
+    Text(&'a str),
+    Letter(char),
+    Digit(char),
+    Symbol(char),
+    Open,
+    Close,
+    Sup,
+    Sub,
+    Prime,
+}
+
+fn tokenize(tex: &str) -> Option<Vec<Token<'_>>> {
+    let mut tokens = Vec::new();
+    let mut chars = tex.char_indices().peekable();
+    while let Some((_, ch)) = chars.next() {
+        let token = match ch {
+            '\\' => {
+                let (next_idx, next) = chars.next()?;
+                if !next.is_ascii_alphabetic() {
+                    Token::Command(&tex[next_idx..next_idx + next.len_utf8()])
+                } else {
+                    let mut end = next_idx + 1;
+                    while let Some((letter_idx, letter)) = chars.peek().copied() {
+                        if !letter.is_ascii_alphabetic() {
+                            break;
+                        }
+                        end = letter_idx + 1;
+                        chars.next();
+                    }
+                    let name = &tex[next_idx..end];
+                    if TEXT_COMMANDS.contains(&name) {
+                        while chars.next_if(|(_, ch)| ch.is_whitespace()).is_some() {}
+                        let (open, _) = chars.next_if(|(_, ch)| *ch == '{')?;
+                        let close = balanced_group_end(tex, open, b'{', b'}')?;
+                        while chars.next_if(|(ch_idx, _)| *ch_idx < close).is_some() {}
+                        Token::Text(&tex[open + 1..close - 1])
+                    } else {
+                        Token::Command(name)
+                    }
+                }
+            }
+            _ if ch.is_whitespace() => continue,
+            '{' => Token::Open,
+            '}' => Token::Close,
+            '^' => Token::Sup,
+            '_' => Token::Sub,
+            '\'' => Token::Prime,
+            _ if ch.is_ascii_digit() => Token::Digit(ch),
+            _ if ch.is_alphabetic() => Token::Letter(ch),
+            _ => Token::Symbol(ch),
+        };
+        tokens.push(token);
+    }
+    Some(tokens)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Closer {
+    End,
+    Brace,
+    Bracket,
+}
+
+enum Atom {
+    Words(Vec<String>),
+    /// Sums, integrals and limits: their scripts read as "from/to" or "as".
+    BigOperator {
+        intro: &'static str,
+        limit: bool,
+    },
+    Nothing,
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token<'a>> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_sequence(&mut self, closer: Closer) -> Option<Vec<String>> {
+        let mut words = Vec::new();
+        loop {
+            match (self.peek(), closer) {
+                (None, Closer::End) => break,
+                (None, _) => return None,
+                (Some(Token::Close), Closer::Brace) => {
+                    self.pos += 1;
+                    break;
+                }
+                (Some(Token::Close), _) => return None,
+                (Some(Token::Symbol(']')), Closer::Bracket) => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => self.parse_term(&mut words)?,
+            }
+        }
+        Some(words)
+    }
+
+    fn parse_term(&mut self, words: &mut Vec<String>) -> Option<()> {
+        let start = self.pos;
+        match self.parse_atom()? {
+            Atom::BigOperator { intro, limit } => {
+                words.push(intro.to_string());
+                loop {
+                    match self.peek() {
+                        Some(Token::Command("limits" | "nolimits")) => self.pos += 1,
+                        Some(Token::Sub) => {
+                            self.pos += 1;
+                            words.push(if limit { "as" } else { "from" }.to_string());
+                            words.extend(self.parse_argument()?);
+                        }
+                        Some(Token::Sup) => {
+                            self.pos += 1;
+                            words.push("to".to_string());
+                            words.extend(self.parse_argument()?);
+                        }
+                        _ => break,
+                    }
+                }
+                words.push("of".to_string());
+                return Some(());
+            }
+            Atom::Words(atom) => words.extend(atom),
+            Atom::Nothing => {}
+        }
+        loop {
+            match self.peek() {
+                Some(Token::Sup) => {
+                    self.pos += 1;
+                    let exponent = self.parse_argument()?;
+                    words.extend(superscript_words(exponent));
+                }
+                Some(Token::Sub) => {
+                    self.pos += 1;
+                    words.push("sub".to_string());
+                    words.extend(self.parse_argument()?);
+                }
+                Some(Token::Prime) => {
+                    self.pos += 1;
+                    words.push("prime".to_string());
+                }
+                _ => break,
+            }
+        }
+        // Every term must consume input, or a stray token would loop forever.
+        (self.pos > start).then_some(())
+    }
+
+    /// One script or command argument: a braced group or a single token.
+    fn parse_argument(&mut self) -> Option<Vec<String>> {
+        match self.peek()? {
+            Token::Open => {
+                self.pos += 1;
+                self.parse_sequence(Closer::Brace)
+            }
+            Token::Close | Token::Sup | Token::Sub | Token::Prime => None,
+            Token::Digit(digit) => {
+                let digit = digit.to_string();
+                self.pos += 1;
+                Some(vec![digit])
+            }
+            _ => match self.parse_atom()? {
+                Atom::Words(words) => Some(words),
+                Atom::BigOperator { intro, .. } => Some(vec![intro.to_string()]),
+                Atom::Nothing => Some(Vec::new()),
+            },
+        }
+    }
+
+    fn parse_atom(&mut self) -> Option<Atom> {
+        let token = match self.peek()? {
+            Token::Sup | Token::Sub | Token::Prime => return Some(Atom::Nothing),
+            _ => self.next()?,
+        };
+        let atom = match token {
+            Token::Digit(first) => {
+                let mut number = first.to_string();
+                loop {
+                    match (self.peek(), self.tokens.get(self.pos + 1)) {
+                        (Some(Token::Digit(digit)), _) => number.push(*digit),
+                        (Some(Token::Symbol('.')), Some(Token::Digit(_))) => number.push('.'),
+                        _ => break,
+                    }
+                    self.pos += 1;
+                }
+                Atom::Words(vec![number])
+            }
+            Token::Letter(letter) => Atom::Words(vec![letter.to_string()]),
+            Token::Text(text) if text.trim().is_empty() => Atom::Nothing,
+            Token::Text(text) => Atom::Words(vec![text.trim().to_string()]),
+            Token::Open => Atom::Words(self.parse_sequence(Closer::Brace)?),
+            Token::Close | Token::Sup | Token::Sub | Token::Prime => return None,
+            Token::Symbol(symbol) => match symbol_word(symbol) {
+                Some("") => Atom::Nothing,
+                Some(word) => Atom::Words(vec![word.to_string()]),
+                None => Atom::Words(vec![symbol.to_string()]),
+            },
+            Token::Command(name) => self.parse_command(name)?,
+        };
+        Some(atom)
+    }
+
+    fn parse_command(&mut self, name: &str) -> Option<Atom> {
+        if SILENT_COMMANDS.contains(&name) {
+            return Some(Atom::Nothing);
+        }
+        let atom = match name {
+            "frac" | "dfrac" | "tfrac" | "cfrac" => {
+                let numerator = self.parse_argument()?;
+                let denominator = self.parse_argument()?;
+                let mut words = Vec::new();
+                if numerator.len() > 1 || denominator.len() > 1 {
+                    words.push("the fraction".to_string());
+                }
+                words.extend(numerator);
+                words.push("over".to_string());
+                words.extend(denominator);
+                Atom::Words(words)
+            }
+            "binom" | "dbinom" | "tbinom" => {
+                let mut words = self.parse_argument()?;
+                words.push("choose".to_string());
+                words.extend(self.parse_argument()?);
+                Atom::Words(words)
+            }
+            "sqrt" => {
+                let index = if self.peek() == Some(&Token::Symbol('[')) {
+                    self.pos += 1;
+                    self.parse_sequence(Closer::Bracket)?
+                } else {
+                    Vec::new()
+                };
+                let intro = match index.as_slice() {
+                    [] => "the square root of".to_string(),
+                    [index] if index == "2" => "the square root of".to_string(),
+                    [index] if index == "3" => "the cube root of".to_string(),
+                    [index] => format!("the {index}th root of"),
+                    _ => format!("the root of order {} of", index.join(" ")),
+                };
+                let mut words = vec![intro];
+                words.extend(self.parse_argument()?);
+                Atom::Words(words)
+            }
+            "vec" => {
+                let mut words = vec!["vector".to_string()];
+                words.extend(self.parse_argument()?);
+                Atom::Words(words)
+            }
+            "hat" | "widehat" | "bar" | "overline" | "dot" | "ddot" | "tilde" | "widetilde" => {
+                let mut words = self.parse_argument()?;
+                words.push(
+                    match name {
+                        "hat" | "widehat" => "hat",
+                        "bar" | "overline" => "bar",
+                        "dot" => "dot",
+                        "ddot" => "double dot",
+                        _ => "tilde",
+                    }
+                    .to_string(),
+                );
+                Atom::Words(words)
+            }
+            "mathbb" | "mathbf" | "mathcal" | "mathfrak" | "boldsymbol" | "mathscr" => {
+                Atom::Words(self.parse_argument()?)
+            }
+            "sum" => big_operator("the sum"),
+            "prod" => big_operator("the product"),
+            "int" => big_operator("the integral"),
+            "iint" => big_operator("the double integral"),
+            "oint" => big_operator("the contour integral"),
+            "bigcup" => big_operator("the union"),
+            "bigcap" => big_operator("the intersection"),
+            "lim" => Atom::BigOperator {
+                intro: "the limit",
+                limit: true,
+            },
+            _ => Atom::Words(vec![command_word(name)?.to_string()]),
+        };
+        Some(atom)
+    }
+}
+
+fn big_operator(intro: &'static str) -> Atom {
+    Atom::BigOperator {
+        intro,
+        limit: false,
+    }
+}
+
+fn superscript_words(exponent: Vec<String>) -> Vec<String> {
+    let word = match exponent.as_slice() {
+        [] => return Vec::new(),
+        [single] => match single.as_str() {
+            "2" => Some("squared"),
+            "3" => Some("cubed"),
+            "prime" => Some("prime"),
+            "composed with" => Some("degrees"),
+            "T" => Some("transpose"),
+            _ => None,
+        },
+        _ => None,
+    };
+    match word {
+        Some(word) => vec![word.to_string()],
+        None => {
+            let mut words = vec!["to the power of".to_string()];
+            words.extend(exponent);
+            words
+        }
+    }
+}
+
+/// Spoken form of a bare symbol; `Some("")` means silent, `None` means speak
+/// the character itself.
+fn symbol_word(symbol: char) -> Option<&'static str> {
+    let word = match symbol {
+        '+' => "plus",
+        '-' | '−' => "minus",
+        '=' => "equals",
+        '<' => "is less than",
+        '>' => "is greater than",
+        '/' => "over",
+        '!' => "factorial",
+        '*' | '×' => "times",
+        ',' | ';' => ",",
+        '(' | ')' | '[' | ']' | '|' | '&' | '~' | '.' | ':' => "",
+        _ => return None,
+    };
+    Some(word)
+}
+
+fn greek_letter(name: &str) -> Option<&'static str> {
+    let word = match name {
+        "alpha" => "alpha",
+        "beta" => "beta",
+        "gamma" => "gamma",
+        "delta" => "delta",
+        "epsilon" | "varepsilon" => "epsilon",
+        "zeta" => "zeta",
+        "eta" => "eta",
+        "theta" | "vartheta" => "theta",
+        "iota" => "iota",
+        "kappa" => "kappa",
+        "lambda" => "lambda",
+        "mu" => "mu",
+        "nu" => "nu",
+        "xi" => "xi",
+        "pi" | "varpi" => "pi",
+        "rho" | "varrho" => "rho",
+        "sigma" | "varsigma" => "sigma",
+        "tau" => "tau",
+        "upsilon" => "upsilon",
+        "phi" | "varphi" => "phi",
+        "chi" => "chi",
+        "psi" => "psi",
+        "omega" => "omega",
+        "Gamma" => "capital gamma",
+        "Delta" => "capital delta",
+        "Theta" => "capital theta",
+        "Lambda" => "capital lambda",
+        "Xi" => "capital xi",
+        "Pi" => "capital pi",
+        "Sigma" => "capital sigma",
+        "Upsilon" => "capital upsilon",
+        "Phi" => "capital phi",
+        "Psi" => "capital psi",
+        "Omega" => "capital omega",
+        _ => return None,
+    };
+    Some(word)
+}
+
+fn command_word(name: &str) -> Option<&'static str> {
+    if let Some(letter) = greek_letter(name) {
+        return Some(letter);
+    }
+    let word = match name {
+        "times" | "cdot" | "ast" => "times",
+        "div" => "divided by",
+        "pm" => "plus or minus",
+        "mp" => "minus or plus",
+        "le" | "leq" | "leqslant" => "is less than or equal to",
+        "ge" | "geq" | "geqslant" => "is greater than or equal to",
+        "ne" | "neq" => "is not equal to",
+        "lt" => "is less than",
+        "gt" => "is greater than",
+        "ll" => "is much less than",
+        "gg" => "is much greater than",
+        "approx" => "is approximately equal to",
+        "equiv" => "is equivalent to",
+        "sim" => "is similar to",
+        "simeq" | "cong" => "is congruent to",
+        "propto" => "is proportional to",
+        "in" => "in",
+        "notin" => "not in",
+        "subset" => "is a subset of",
+        "subseteq" => "is a subset of or equal to",
+        "supset" => "is a superset of",
+        "cup" => "union",
+        "cap" => "intersection",
+        "setminus" => "minus",
+        "emptyset" | "varnothing" => "the empty set",
+        "forall" => "for all",
+        "exists" => "there exists",
+        "neg" | "lnot" => "not",
+        "land" | "wedge" => "and",
+        "lor" | "vee" => "or",
+        "to" | "rightarrow" => "goes to",
+        "leftarrow" | "gets" => "comes from",
+        "Rightarrow" | "implies" => "implies",
+        "iff" | "Leftrightarrow" => "if and only if",
+        "mapsto" => "maps to",
+        "circ" => "composed with",
+        "infty" => "infinity",
+        "partial" => "partial",
+        "nabla" => "del",
+        "prime" => "prime",
+        "degree" => "degrees",
+        "angle" => "angle",
+        "perp" => "is perpendicular to",
+        "parallel" => "is parallel to",
+        "mid" => "divides",
+        "ldots" | "cdots" | "dots" | "vdots" => "dot dot dot",
+        "ell" => "l",
+        "hbar" => "h bar",
+        "sin" => "sine",
+        "cos" => "cosine",
+        "tan" => "tangent",
+        "cot" => "cotangent",
+        "sec" => "secant",
+        "csc" => "cosecant",
+        "arcsin" => "arc sine",
+        "arccos" => "arc cosine",
+        "arctan" => "arc tangent",
+        "sinh" => "hyperbolic sine",
+        "cosh" => "hyperbolic cosine",
+        "tanh" => "hyperbolic tangent",
+        "log" => "log",
+        "ln" => "natural log",
+        "exp" => "exp",
+        "det" => "determinant",
+        "max" => "max",
+        "min" => "min",
+        "sup" => "supremum",
+        "inf" => "infimum",
+        "gcd" => "g c d",
+        "mod" | "bmod" => "mod",
+        _ => return None,
+    };
+    Some(word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spoken(tex: &str) -> String {
+        verbalize(tex).unwrap_or_else(|| panic!("failed to verbalize {tex:?}"))
+    }
+
+    #[test]
+    fn common_expressions_are_read_as_english() {
+        assert_eq!(spoken(r"\frac{a}{b}"), "a over b");
+        assert_eq!(
+            spoken("x^2 + y^3 = z^{n}"),
+            "x squared plus y cubed equals z to the power of n"
+        );
+        assert_eq!(spoken(r"\sqrt{2}"), "the square root of 2");
+        assert_eq!(spoken(r"\sqrt[3]{x}"), "the cube root of x");
+        assert_eq!(spoken(r"\frac{x+1}{2}"), "the fraction x plus 1 over 2");
+        assert_eq!(
+            spoken(r"\sum_{i=1}^{n} x_i"),
+            "the sum from i equals 1 to n of x sub i"
+        );
+        assert_eq!(
+            spoken(r"\lim_{x \to 0} \frac{\sin x}{x} = 1"),
+            "the limit as x goes to 0 of the fraction sine x over x equals 1"
+        );
+        assert_eq!(
+            spoken(r"f'(x) \leq 3.5\,\text{m}"),
+            "f prime x is less than or equal to 3.5 m"
+        );
+        assert_eq!(spoken(r"\alpha, \beta \in \mathbb{R}"), "alpha, beta in R");
+    }
+
+    #[test]
+    fn unsupported_or_broken_tex_is_rejected() {
+        assert_eq!(verbalize(r"\frac{a}{b"), None);
+        assert_eq!(verbalize(r"\begin{matrix} a \end{matrix}"), None);
+        assert_eq!(verbalize("x}"), None);
+        assert_eq!(verbalize(r"\,"), None);
+    }
+
+    #[test]
+    fn spans_cover_delimited_and_bare_math_but_not_prices() {
+        let text = r"Let $x^2$ and \(y\) hold; \frac{a}{b} costs $5 and $10. $$E = mc^2$$ \[z\]";
+        let spans = find_math_spans(text);
+        let tex: Vec<&str> = spans.iter().map(|span| &text[span.tex.clone()]).collect();
+        assert_eq!(tex, vec!["x^2", "y", r"\frac{a}{b}", "E = mc^2", "z"]);
+        assert_eq!(
+            spans.iter().map(|span| span.display).collect::<Vec<_>>(),
+            vec![false, false, false, true, true]
+        );
+        assert_eq!(&text[spans[0].range.clone()], "$x^2$");
+
+        assert!(find_math_spans(r"Saved to C:\Users\notes and paid $5, then $6.").is_empty());
+    }
+}
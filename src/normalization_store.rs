@@ -0,0 +1,559 @@
+//! Per-book store for normalized sentence chunks and page plans.
+//!
+//! Every book gets a single append-only JSON-lines file inside its
+//! `normalized` cache directory instead of one TOML file per sentence and per
+//! page. Records are loaded into an in-memory index the first time a book is
+//! touched, new entries are appended in batches, and the file is rewritten
+//! (compacted) whenever the normalizer `config_hash` changes or too many
+//! superseded records pile up. Legacy `s-*.toml` / `p*.toml` caches are
+//! imported once and then removed.
+
+use crate::cache::normalized_dir;
+use crate::normalizer::PageNormalization;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const STORE_FILE: &str = "store.jsonl";
+const STORE_TMP_FILE: &str = "store.jsonl.tmp";
+const MAX_OPEN_STORES: usize = 4;
+const COMPACTION_SLACK_RECORDS: usize = 256;
+
+static OPEN_STORES: Lazy<Mutex<HashMap<PathBuf, NormalizationStore>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Cached normalization result for one display sentence.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StoredSentence {
+    #[serde(default)]
+    pub normalized: Option<String>,
+    #[serde(default)]
+    pub chunks: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum StoreRecord {
+    Sentence {
+        key: String,
+        config_hash: String,
+        #[serde(flatten)]
+        entry: StoredSentence,
+    },
+    Page {
+        key: String,
+        config_hash: String,
+        plan: PageNormalization,
+    },
+}
+
+#[derive(Debug)]
+pub struct NormalizationStore {
+    path: PathBuf,
+    config_hash: String,
+    sentences: HashMap<String, StoredSentence>,
+    pages: HashMap<String, PageNormalization>,
+    records_on_disk: usize,
+    pending: Vec<StoreRecord>,
+}
+
+/// Run `f` against the store for `epub_path`, opening, migrating or compacting
+/// it first when needed. Entries added by `f` are appended to disk before this
+/// returns.
+pub fn with_store<R>(
+    epub_path: &Path,
+    config_hash: &str,
+    f: impl FnOnce(&mut NormalizationStore) -> R,
+) -> R {
+    let dir = normalized_dir(epub_path);
+    let mut stores = match OPEN_STORES.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => {
+            tracing::warn!("Normalization store registry lock poisoned; recovering");
+            poisoned.into_inner()
+        }
+    };
+
+    let stale = stores
+        .get(&dir)
+        .map(|store| store.config_hash != config_hash || !store.path.exists())
+        .unwrap_or(true);
+    if stale {
+        stores.remove(&dir);
+        if stores.len() >= MAX_OPEN_STORES {
+            stores.clear();
+        }
+        let store = NormalizationStore::open(&dir, config_hash);
+        stores.insert(dir.clone(), store);
+    }
+
+    let store = stores
+        .get_mut(&dir)
+        .expect("normalization store was just inserted");
+    let result = f(store);
+    store.flush();
+    result
+}
+
+impl NormalizationStore {
+    fn open(dir: &Path, config_hash: &str) -> Self {
+        let path = dir.join(STORE_FILE);
+        let mut store = Self {
+            path,
+            config_hash: config_hash.to_string(),
+            sentences: HashMap::new(),
+            pages: HashMap::new(),
+            records_on_disk: 0,
+            pending: Vec::new(),
+        };
+
+        let mut needs_compaction = store.load_records();
+        if store.migrate_legacy_files(dir) {
+            needs_compaction = true;
+        }
+        if needs_compaction
+            || store.records_on_disk > store.live_records() + COMPACTION_SLACK_RECORDS
+        {
+            store.compact();
+        }
+
+        tracing::debug!(
+            path = %store.path.display(),
+            sentences = store.sentences.len(),
+            pages = store.pages.len(),
+            "Opened normalization store"
+        );
+        store
+    }
+
+    pub fn sentence(&self, key: &str) -> Option<&StoredSentence> {
+        self.sentences.get(key)
+    }
+
+    pub fn put_sentence(&mut self, key: String, entry: StoredSentence) {
+        if self.sentences.get(&key) == Some(&entry) {
+            return;
+        }
+        self.pending.push(StoreRecord::Sentence {
+            key: key.clone(),
+            config_hash: self.config_hash.clone(),
+            entry: entry.clone(),
+        });
+        self.sentences.insert(key, entry);
+    }
+
+    pub fn page(&self, key: &str) -> Option<&PageNormalization> {
+        self.pages.get(key)
+    }
+
+    pub fn put_page(&mut self, key: String, plan: PageNormalization) {
+        if self.pages.contains_key(&key) {
+            return;
+        }
+        self.pending.push(StoreRecord::Page {
+            key: key.clone(),
+            config_hash: self.config_hash.clone(),
+            plan: plan.clone(),
+        });
+        self.pages.insert(key, plan);
+    }
+
+    fn live_records(&self) -> usize {
+        self.sentences.len() + self.pages.len()
+    }
+
+    /// Load records for the active config hash. Returns true when the file
+    /// holds anything that should be dropped (other config hashes or a torn
+    /// trailing line).
+    fn load_records(&mut self) -> bool {
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(_) => return false,
+        };
+
+        let mut dropped = false;
+        for line in BufReader::new(file).lines() {
+            let Ok(line) = line else {
+                dropped = true;
+                break;
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            self.records_on_disk += 1;
+            match serde_json::from_str::<StoreRecord>(&line) {
+                Ok(record) => {
+                    if !self.apply_record(record) {
+                        dropped = true;
+                    }
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        path = %self.path.display(),
+                        "Skipping unreadable normalization store record: {err}"
+                    );
+                    dropped = true;
+                }
+            }
+        }
+        dropped
+    }
+
+    fn apply_record(&mut self, record: StoreRecord) -> bool {
+        match record {
+            StoreRecord::Sentence {
+                key,
+                config_hash,
+                entry,
+            } => {
+                if config_hash != self.config_hash {
+                    return false;
+                }
+                self.sentences.insert(key, entry);
+            }
+            StoreRecord::Page {
+                key,
+                config_hash,
+                plan,
+            } => {
+                if config_hash != self.config_hash {
+                    return false;
+                }
+                self.pages.insert(key, plan);
+            }
+        }
+        true
+    }
+
+    /// Import `s-{hash}-{config}.toml` and `p{page}-{hash}-{config}.toml`
+    /// files written by older builds, then delete them.
+    fn migrate_legacy_files(&mut self, dir: &Path) -> bool {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return false;
+        };
+
+        let mut imported = 0usize;
+        let mut removed = 0usize;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let Some(legacy) = parse_legacy_file_name(name) else {
+                continue;
+            };
+
+            if legacy.config_hash == self.config_hash
+                && let Ok(contents) = fs::read_to_string(&path)
+            {
+                let record =
+                    match legacy.kind {
+                        LegacyKind::Sentence => toml::from_str::<StoredSentence>(&contents)
+                            .ok()
+                            .map(|entry| StoreRecord::Sentence {
+                                key: legacy.key.clone(),
+                                config_hash: self.config_hash.clone(),
+                                entry,
+                            }),
+                        LegacyKind::Page => {
+                            parse_legacy_page(&contents).map(|plan| StoreRecord::Page {
+                                key: legacy.key.clone(),
+                                config_hash: self.config_hash.clone(),
+                                plan,
+                            })
+                        }
+                    };
+                if let Some(record) = record {
+                    self.apply_record(record);
+                    imported += 1;
+                }
+            }
+
+            if fs::remove_file(&path).is_ok() {
+                removed += 1;
+            }
+        }
+
+        if removed > 0 {
+            tracing::info!(
+                dir = %dir.display(),
+                imported,
+                removed,
+                "Migrated legacy normalization cache files into store"
+            );
+        }
+        removed > 0
+    }
+
+    /// Rewrite the store so it only holds live records for the active config.
+    fn compact(&mut self) {
+        let Some(parent) = self.path.parent() else {
+            return;
+        };
+        if self.live_records() == 0 {
+            let _ = fs::remove_file(&self.path);
+            self.records_on_disk = 0;
+            return;
+        }
+        if let Err(err) = fs::create_dir_all(parent) {
+            tracing::warn!(
+                path = %parent.display(),
+                "Failed to create normalization store directory: {err}"
+            );
+            return;
+        }
+
+        let mut serialized = String::new();
+        let mut written = 0usize;
+        for (key, entry) in &self.sentences {
+            let record = StoreRecord::Sentence {
+                key: key.clone(),
+                config_hash: self.config_hash.clone(),
+                entry: entry.clone(),
+            };
+            if push_record_line(&mut serialized, &record) {
+                written += 1;
+            }
+        }
+        for (key, plan) in &self.pages {
+            let record = StoreRecord::Page {
+                key: key.clone(),
+                config_hash: self.config_hash.clone(),
+                plan: plan.clone(),
+            };
+            if push_record_line(&mut serialized, &record) {
+                written += 1;
+            }
+        }
+
+        let tmp_path = parent.join(STORE_TMP_FILE);
+        let result =
+            fs::write(&tmp_path, serialized).and_then(|_| fs::rename(&tmp_path, &self.path));
+        match result {
+            Ok(()) => {
+                tracing::debug!(
+                    path = %self.path.display(),
+                    previous_records = self.records_on_disk,
+                    records = written,
+                    "Compacted normalization store"
+                );
+                self.records_on_disk = written;
+            }
+            Err(err) => {
+                let _ = fs::remove_file(&tmp_path);
+                tracing::warn!(
+                    path = %self.path.display(),
+                    "Failed to compact normalization store: {err}"
+                );
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let pending = std::mem::take(&mut self.pending);
+        let mut serialized = String::new();
+        let mut written = 0usize;
+        for record in &pending {
+            if push_record_line(&mut serialized, record) {
+                written += 1;
+            }
+        }
+
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let result = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(serialized.as_bytes()));
+        match result {
+            Ok(()) => self.records_on_disk += written,
+            Err(err) => {
+                tracing::warn!(
+                    path = %self.path.display(),
+                    "Failed to append to normalization store: {err}"
+                );
+            }
+        }
+    }
+}
+
+fn push_record_line(buffer: &mut String, record: &StoreRecord) -> bool {
+    match serde_json::to_string(record) {
+        Ok(line) => {
+            buffer.push_str(&line);
+            buffer.push('\n');
+            true
+        }
+        Err(err) => {
+            tracing::warn!("Failed to serialize normalization store record: {err}");
+            false
+        }
+    }
+}
+
+enum LegacyKind {
+    Sentence,
+    Page,
+}
+
+struct LegacyFileName {
+    kind: LegacyKind,
+    key: String,
+    config_hash: String,
+}
+
+fn parse_legacy_file_name(name: &str) -> Option<LegacyFileName> {
+    let stem = name.strip_suffix(".toml")?;
+    if let Some(rest) = stem.strip_prefix("s-") {
+        let (key, config_hash) = rest.split_once('-')?;
+        return Some(LegacyFileName {
+            kind: LegacyKind::Sentence,
+            key: key.to_string(),
+            config_hash: config_hash.to_string(),
+        });
+    }
+    let rest = stem.strip_prefix('p')?;
+    let mut parts = rest.splitn(3, '-');
+    let page = parts.next()?;
+    if page.is_empty() || !page.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    let key = parts.next()?;
+    let config_hash = parts.next()?;
+    Some(LegacyFileName {
+        kind: LegacyKind::Page,
+        key: key.to_string(),
+        config_hash: config_hash.to_string(),
+    })
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct LegacyPageCache {
+    audio_sentences: Vec<String>,
+    // TOML cannot encode `None` in arrays directly; older caches use `-1`.
+    display_to_audio: Vec<isize>,
+    audio_to_display: Vec<usize>,
+}
+
+fn parse_legacy_page(contents: &str) -> Option<PageNormalization> {
+    if let Ok(plan) = toml::from_str::<PageNormalization>(contents) {
+        return Some(plan);
+    }
+    let cached = toml::from_str::<LegacyPageCache>(contents).ok()?;
+    Some(PageNormalization {
+        audio_sentences: cached.audio_sentences,
+        display_to_audio: cached
+            .display_to_audio
+            .into_iter()
+            .map(|idx| if idx < 0 { None } else { Some(idx as usize) })
+            .collect(),
+        audio_to_display: cached.audio_to_display,
+        audio_phonemes: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_epub_path(label: &str) -> PathBuf {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time should be after epoch")
+            .as_nanos();
+        std::env::temp_dir().join(format!("lanternleaf-store-{label}-{nonce}.epub"))
+    }
+
+    fn sample_plan() -> PageNormalization {
+        PageNormalization {
+            audio_sentences: vec!["Alpha.".to_string()],
+            display_to_audio: vec![Some(0), None],
+            audio_to_display: vec![0],
+            audio_phonemes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn migrates_legacy_toml_files_and_compacts_on_config_change() {
+        let epub_path = temp_epub_path("migrate");
+        let dir = normalized_dir(&epub_path);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("create normalized dir");
+
+        fs::write(
+            dir.join("s-abc-cfg1.toml"),
+            "normalized = \"Alpha.\"\nchunks = [\"Alpha.\"]\n",
+        )
+        .expect("write legacy sentence");
+        fs::write(
+            dir.join("p3-def-cfg1.toml"),
+            "audio_sentences = [\"Alpha.\"]\ndisplay_to_audio = [0, -1]\naudio_to_display = [0]\n",
+        )
+        .expect("write legacy page");
+        fs::write(dir.join("s-old-cfg0.toml"), "chunks = []\n").expect("write stale sentence");
+
+        with_store(&epub_path, "cfg1", |store| {
+            assert_eq!(
+                store.sentence("abc").and_then(|entry| entry.chunks.clone()),
+                Some(vec!["Alpha.".to_string()])
+            );
+            assert_eq!(store.page("def"), Some(&sample_plan()));
+            assert!(store.sentence("old").is_none());
+        });
+
+        let names: Vec<String> = fs::read_dir(&dir)
+            .expect("read normalized dir")
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec![STORE_FILE.to_string()]);
+
+        with_store(&epub_path, "cfg2", |store| {
+            assert!(store.sentence("abc").is_none());
+            store.put_sentence(
+                "ghi".to_string(),
+                StoredSentence {
+                    normalized: Some("Gamma.".to_string()),
+                    chunks: Some(vec!["Gamma.".to_string()]),
+                },
+            );
+        });
+
+        let contents = fs::read_to_string(dir.join(STORE_FILE)).expect("read store");
+        assert_eq!(contents.lines().count(), 1);
+        assert!(contents.contains("\"cfg2\""));
+        assert!(!contents.contains("\"cfg1\""));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn reopened_store_reads_appended_records() {
+        let epub_path = temp_epub_path("reopen");
+        let dir = normalized_dir(&epub_path);
+        let _ = fs::remove_dir_all(&dir);
+
+        with_store(&epub_path, "cfg", |store| {
+            store.put_page("page".to_string(), sample_plan());
+        });
+        OPEN_STORES
+            .lock()
+            .expect("store registry lock")
+            .remove(&dir);
+
+        let plan = with_store(&epub_path, "cfg", |store| store.page("page").cloned());
+        assert_eq!(plan, Some(sample_plan()));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,12 +1,21 @@
-use crate::cache::normalized_dir;
+use crate::math_speech::{find_math_spans, verbalize};
+use crate::normalization_store::{StoredSentence, with_store};
+use crate::text_utils::{
+    FootnoteMode, NOTE_BODY_START, NOTE_REF_START, PREFORMATTED_LINE_END, PREFORMATTED_LINE_START,
+    PreformattedKind, TABLE_ROW_START, parse_note_body, parse_preformatted_line, parse_table_row,
+    strip_display_markers,
+};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::borrow::Cow;
 use std::cmp::Reverse;
 use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use ts_rs::TS;
 
 const DEFAULT_NORMALIZER_PATH: &str = "conf/normalizer.toml";
 const NORMALIZER_CONFIG_ENV: &str = "LANTERNLEAF_NORMALIZER_CONFIG_PATH";
@@ -30,6 +39,31 @@ static RE_CURLY_BRACKET_BLOCK: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{[^}]*\}
 static RE_HORIZONTAL_WS: Lazy<Regex> = Lazy::new(|| Regex::new(r"[ \t\u{00A0}]+").unwrap());
 static RE_SPACE_BEFORE_PUNCT: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+([,.;:!?])").unwrap());
 static RE_SOFT_BREAK_WS: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
+static RE_WORD_TOKEN: Lazy<Regex> = Lazy::new(|| Regex::new(r"[A-Za-z]+(?:'[A-Za-z]+)*").unwrap());
+static RE_SPEECH_MARKUP_TAG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"</?(?:break|emphasis|say-as|prosody|sub|phoneme)\b(?:\s+[a-z-]+\s*=\s*"[^"<>]*")*\s*/?>"#)
+        .unwrap()
+});
+/// A tag, or a whole `say-as`/`sub`/`phoneme` element: the worker interprets
+/// their inner text itself, so text stages must not rewrite it.
+static RE_SPEECH_MARKUP_SHIELD: Lazy<Regex> = Lazy::new(|| {
+    let attrs = r#"(?:\s+[a-z-]+\s*=\s*"[^"<>]*")*\s*"#;
+    let elements = ["say-as", "sub", "phoneme"]
+        .map(|name| format!(r"<{name}\b{attrs}>[^<]*</{name}\s*>"))
+        .join("|");
+    Regex::new(&format!("{elements}|{}", RE_SPEECH_MARKUP_TAG.as_str())).unwrap()
+});
+static RE_SHIELDED_MARKUP: Lazy<Regex> = Lazy::new(|| Regex::new("\u{E000}(.)\u{E001}").unwrap());
+static RE_PREFORMATTED_LINE: Lazy<Regex> =
+    Lazy::new(|| Regex::new("\u{E010}[^\u{E011}]*\u{E011}?").unwrap());
+static RE_NOTE_REF: Lazy<Regex> =
+    Lazy::new(|| Regex::new("\u{E014}[^\u{E015}]*\u{E015}?").unwrap());
+static RE_NOTE_BODY: Lazy<Regex> =
+    Lazy::new(|| Regex::new("\u{E016}[^\u{E017}]*\u{E017}?").unwrap());
+static RE_TABLE_ROW: Lazy<Regex> =
+    Lazy::new(|| Regex::new("\u{E018}[^\u{E019}]*\u{E019}?").unwrap());
+static RE_BLOCK_MARKER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^[ \t]*(?:#{1,6}|>+)[ \t]+").unwrap());
 
 #[derive(Debug, Clone)]
 pub struct TextNormalizer {
@@ -74,7 +108,12 @@ struct NormalizerConfig {
     abbreviations: AbbreviationConfig,
     drop_tokens: Vec<String>,
     acronyms: AcronymConfig,
+    heteronyms: HeteronymConfig,
     pronunciation: PronunciationConfig,
+    code_blocks: CodeBlockConfig,
+    footnotes: FootnoteConfig,
+    tables: TableConfig,
+    math: MathConfig,
 }
 
 impl Default for NormalizerConfig {
@@ -105,7 +144,12 @@ impl Default for NormalizerConfig {
             abbreviations: default_abbreviations(),
             drop_tokens: Vec::new(),
             acronyms: AcronymConfig::default(),
+            heteronyms: HeteronymConfig::default(),
             pronunciation: PronunciationConfig::default(),
+            code_blocks: CodeBlockConfig::default(),
+            footnotes: FootnoteConfig::default(),
+            tables: TableConfig::default(),
+            math: MathConfig::default(),
         }
     }
 }
@@ -164,6 +208,7 @@ struct PronunciationConfig {
     enable_brand_map: bool,
     brand_map: BTreeMap<String, String>,
     custom_pronunciations: BTreeMap<String, String>,
+    phonemes: BTreeMap<String, PhonemeOverride>,
 }
 
 impl Default for PronunciationConfig {
@@ -182,6 +227,7 @@ impl Default for PronunciationConfig {
             enable_brand_map: true,
             brand_map,
             custom_pronunciations: BTreeMap::new(),
+            phonemes: BTreeMap::new(),
         }
     }
 }
@@ -194,11 +240,304 @@ enum YearMode {
     None,
 }
 
+/// Phoneme string for a word, either as IPA (what Piper voices consume) or as
+/// English espeak mnemonics, which are converted to IPA.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum PhonemeOverride {
+    Ipa(String),
+    Espeak(String),
+}
+
+impl PhonemeOverride {
+    fn to_ipa(&self) -> String {
+        match self {
+            Self::Ipa(ipa) => ipa.trim().to_string(),
+            Self::Espeak(mnemonics) => espeak_to_ipa(mnemonics.trim()),
+        }
+    }
+}
+
+/// Context rules that respell ambiguous words (e.g. past-tense "read") so the
+/// TTS voice picks the intended pronunciation. `custom_rules` are tried before
+/// the built-in `rules`, so user entries can both add words and override.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+struct HeteronymConfig {
+    enabled: bool,
+    custom_rules: Vec<HeteronymRule>,
+    rules: Vec<HeteronymRule>,
+}
+
+impl Default for HeteronymConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            custom_rules: Vec::new(),
+            rules: default_heteronym_rules(),
+        }
+    }
+}
+
+/// Rewrites `word` to `replace` when any of the `window` words before it is in
+/// `preceded_by`, or any of the `window` words after it is in `followed_by`.
+/// Context never crosses clause punctuation. A rule whose `replace` equals
+/// `word` acts as a guard that keeps the default pronunciation.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+struct HeteronymRule {
+    word: String,
+    replace: String,
+    preceded_by: Vec<String>,
+    followed_by: Vec<String>,
+    window: usize,
+}
+
+impl Default for HeteronymRule {
+    fn default() -> Self {
+        Self {
+            word: String::new(),
+            replace: String::new(),
+            preceded_by: Vec::new(),
+            followed_by: Vec::new(),
+            window: 1,
+        }
+    }
+}
+
+/// How lines of code and other preformatted blocks are spoken. Every line
+/// stays a highlightable display sentence; a policy only changes its audio,
+/// and lines that produce no audio are skipped during playback.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+struct CodeBlockConfig {
+    code: CodeBlockPolicy,
+    preformatted: CodeBlockPolicy,
+    /// Spoken on a block's first line under `announce`; `{lines}` becomes
+    /// e.g. "12 lines".
+    announce_code: String,
+    announce_preformatted: String,
+    comment_prefixes: Vec<String>,
+    /// Names spoken for symbols under `verbatim`; longest symbols match first.
+    symbol_names: BTreeMap<String, String>,
+}
+
+impl Default for CodeBlockConfig {
+    fn default() -> Self {
+        Self {
+            code: CodeBlockPolicy::Announce,
+            preformatted: CodeBlockPolicy::Read,
+            announce_code: "Code listing, {lines}.".to_string(),
+            announce_preformatted: "Preformatted text, {lines}.".to_string(),
+            comment_prefixes: ["///", "//!", "//", "#", "--", ";;", "/*", "*", "<!--"]
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            symbol_names: default_code_symbol_names(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+enum CodeBlockPolicy {
+    /// Silent; the lines are still shown and highlighted.
+    Skip,
+    /// Speak the announcement once for the block, then skip its lines.
+    Announce,
+    /// Speak only comment text.
+    Comments,
+    /// Speak every line, naming symbols.
+    Verbatim,
+    /// Speak lines like ordinary prose.
+    Read,
+}
+
+/// Footnote bodies linked by the loaders. `mode` also decides where the
+/// session lays bodies out; note references are never spoken.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+struct FootnoteConfig {
+    mode: FootnoteMode,
+    /// Spoken before a note body; `{label}` becomes the note label.
+    announce: String,
+}
+
+impl Default for FootnoteConfig {
+    fn default() -> Self {
+        Self {
+            mode: FootnoteMode::Skip,
+            announce: "Note {label}.".to_string(),
+        }
+    }
+}
+
+/// How table rows from the loaders are spoken. Rows stay highlightable
+/// display sentences in both modes.
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+struct TableConfig {
+    mode: TableMode,
+    /// Spoken for the header row; `{cells}` lists the column names. Leave
+    /// empty to keep header rows silent.
+    header_template: String,
+    /// `{number}` is the 1-based body row and `{cells}` the "column value"
+    /// pairs, e.g. "Row 2: Country France, Population 68 million."
+    row_template: String,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        Self {
+            mode: TableMode::Rows,
+            header_template: "Table with columns {cells}.".to_string(),
+            row_template: "Row {number}: {cells}.".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+enum TableMode {
+    #[default]
+    Rows,
+    Skip,
+}
+
+/// LaTeX spans (`$...$`, `\(...\)`, bare `\frac{a}{b}`, and EPUB MathML that
+/// pandoc hands over as TeX). Display text keeps the source markup.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+struct MathConfig {
+    mode: MathMode,
+    /// Spoken in place of an expression that cannot be verbalized, or of every
+    /// expression in `announce` mode. Empty keeps such expressions silent.
+    fallback: String,
+}
+
+impl Default for MathConfig {
+    fn default() -> Self {
+        Self {
+            mode: MathMode::Speak,
+            fallback: "equation omitted".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+enum MathMode {
+    /// Read expressions as English ("a over b"), falling back per expression.
+    #[default]
+    Speak,
+    /// Speak the fallback for every expression.
+    Announce,
+    /// Drop expressions from audio.
+    Skip,
+}
+
+/// Per-book normalizer additions persisted in the book cache and layered over
+/// the global config when a reader session loads.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct NormalizerOverlay {
+    pub custom_pronunciations: BTreeMap<String, String>,
+    pub brand_map: BTreeMap<String, String>,
+    pub replacements: BTreeMap<String, String>,
+    pub abbreviations: BTreeMap<String, String>,
+    pub drop_tokens: Vec<String>,
+    pub acronym_tokens: Vec<String>,
+    #[ts(optional)]
+    pub drop_square_bracket_text: Option<bool>,
+    #[ts(optional)]
+    pub drop_curly_brace_text: Option<bool>,
+    /// Replaces the global `[normalization.math] fallback` for this book.
+    #[ts(optional)]
+    pub math_fallback: Option<String>,
+}
+
+impl NormalizerOverlay {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// One normalizer stage as reported by [`TextNormalizer::explain`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, TS)]
+#[ts(export)]
+pub struct NormalizationStage {
+    pub stage: String,
+    pub before: String,
+    pub after: String,
+    pub changed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, TS)]
+#[ts(export)]
+pub struct NormalizationTrace {
+    pub input: String,
+    pub stages: Vec<NormalizationStage>,
+    pub normalized: Option<String>,
+    pub chunks: Vec<String>,
+    pub config_hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct PageNormalization {
     pub audio_sentences: Vec<String>,
     pub display_to_audio: Vec<Option<usize>>,
     pub audio_to_display: Vec<usize>,
+    /// Phoneme overrides per audio sentence, parallel to `audio_sentences`.
+    #[serde(default)]
+    pub audio_phonemes: Vec<Vec<PhonemeSpan>>,
+}
+
+/// Byte range of an audio sentence that should be synthesized from IPA
+/// phonemes instead of text.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PhonemeSpan {
+    pub start: usize,
+    pub end: usize,
+    pub phonemes: String,
+}
+
+impl PageNormalization {
+    /// Text handed to the TTS worker for audio sentence `idx`, with phoneme
+    /// spans rendered as inline `<phoneme ph="...">word</phoneme>` markup.
+    pub fn tts_input(&self, idx: usize) -> Option<String> {
+        let sentence = self.audio_sentences.get(idx)?;
+        let spans = match self.audio_phonemes.get(idx) {
+            Some(spans) if !spans.is_empty() => spans,
+            _ => return Some(sentence.clone()),
+        };
+
+        let mut out = String::with_capacity(sentence.len() + spans.len() * 32);
+        let mut last = 0;
+        for span in spans {
+            let Some(word) = sentence.get(span.start..span.end) else {
+                continue;
+            };
+            if span.start < last {
+                continue;
+            }
+            out.push_str(&sentence[last..span.start]);
+            out.push_str(&format!(
+                "<phoneme ph=\"{}\">{}</phoneme>",
+                span.phonemes.replace('"', ""),
+                word
+            ));
+            last = span.end;
+        }
+        out.push_str(&sentence[last..]);
+        Some(out)
+    }
+
+    pub fn tts_inputs(&self) -> Vec<String> {
+        (0..self.audio_sentences.len())
+            .filter_map(|idx| self.tts_input(idx))
+            .collect()
+    }
 }
 
 impl TextNormalizer {
@@ -208,21 +547,14 @@ impl TextNormalizer {
     }
 
     pub fn load(path: &Path) -> Self {
-        match fs::read_to_string(path) {
-            Ok(contents) => match toml::from_str::<NormalizerFile>(&contents) {
-                Ok(file) => {
-                    let mut config = file.normalization;
-                    config
-                        .abbreviations
-                        .extend(load_external_abbreviations(path));
-                    tracing::info!(path = %path.display(), "Loaded text normalizer config");
-                    Self { config }
-                }
-                Err(err) => {
-                    tracing::warn!(path = %path.display(), "Invalid normalizer config TOML: {err}");
-                    Self::default()
-                }
-            },
+        match read_normalization_config(path) {
+            Ok(mut config) => {
+                config
+                    .abbreviations
+                    .extend(load_external_abbreviations(path));
+                tracing::info!(path = %path.display(), "Loaded text normalizer config");
+                Self { config }
+            }
             Err(err) => {
                 tracing::warn!(path = %path.display(), "Falling back to default normalizer config: {err}");
                 Self::default()
@@ -230,6 +562,83 @@ impl TextNormalizer {
         }
     }
 
+    /// Like [`Self::load_default`], but reports an unreadable or invalid
+    /// config instead of falling back to defaults.
+    pub fn try_load_default() -> Result<Self, String> {
+        let path = resolve_default_normalizer_path();
+        Self::try_load(path.as_path())
+    }
+
+    /// Load the normalizer config and its external abbreviations file. A
+    /// missing abbreviations file is fine; an invalid one is an error.
+    pub fn try_load(path: &Path) -> Result<Self, String> {
+        let mut config = read_normalization_config(path)?;
+        config
+            .abbreviations
+            .extend(try_load_external_abbreviations(path)?);
+        tracing::info!(path = %path.display(), "Loaded text normalizer config");
+        Ok(Self { config })
+    }
+
+    /// Return a copy of this normalizer with a per-book overlay merged on top.
+    /// Overlay entries win over global ones, so the merged config hash (and
+    /// therefore every normalization/audio cache key) changes with the overlay.
+    pub fn with_overlay(&self, overlay: &NormalizerOverlay) -> Self {
+        if overlay.is_empty() {
+            return self.clone();
+        }
+        let mut config = self.config.clone();
+        for (token, replacement) in &overlay.custom_pronunciations {
+            config
+                .pronunciation
+                .custom_pronunciations
+                .insert(token.clone(), replacement.clone());
+        }
+        for (token, replacement) in &overlay.brand_map {
+            config
+                .pronunciation
+                .brand_map
+                .insert(token.clone(), replacement.clone());
+        }
+        if !overlay.brand_map.is_empty() {
+            config.pronunciation.enable_brand_map = true;
+        }
+        for (from, to) in &overlay.replacements {
+            config.replacements.insert(from.clone(), to.clone());
+        }
+        for (token, replacement) in &overlay.abbreviations {
+            config
+                .abbreviations
+                .nocase
+                .insert(token.clone(), replacement.clone());
+        }
+        for token in &overlay.drop_tokens {
+            if !config.drop_tokens.contains(token) {
+                config.drop_tokens.push(token.clone());
+            }
+        }
+        for token in &overlay.acronym_tokens {
+            if !config.acronyms.tokens.contains(token) {
+                config.acronyms.tokens.push(token.clone());
+            }
+        }
+        if let Some(value) = overlay.drop_square_bracket_text {
+            config.drop_square_bracket_text = value;
+        }
+        if let Some(value) = overlay.drop_curly_brace_text {
+            config.drop_curly_brace_text = value;
+        }
+        if let Some(fallback) = &overlay.math_fallback {
+            config.math.fallback = fallback.clone();
+        }
+        Self { config }
+    }
+
+    /// Where the session should place footnote bodies before pagination.
+    pub fn footnote_mode(&self) -> FootnoteMode {
+        self.config.footnotes.mode
+    }
+
     pub fn plan_page_cached(
         &self,
         epub_path: &Path,
@@ -250,18 +659,17 @@ impl TextNormalizer {
     ) -> PageNormalization {
         let source_hash = hash_sentences(display_sentences);
         let config_hash = self.config_hash();
-        let cache_path =
-            self.normalized_cache_path(epub_path, page_idx, &source_hash, &config_hash);
 
-        if let Some(cached) = self.read_page_plan_cache(&cache_path, page_idx) {
+        if let Some(cached) =
+            self.read_page_plan_cache(epub_path, &config_hash, &source_hash, page_idx)
+        {
             return cached;
         }
 
         let plan = self.plan_page(display_sentences);
-        if let Some(parent) = cache_path.parent() {
-            let _ = fs::create_dir_all(parent);
-        }
-        self.write_page_plan_cache(&cache_path, page_idx, &plan);
+        with_store(epub_path, &config_hash, |store| {
+            store.put_page(source_hash, plan.clone());
+        });
         plan
     }
 
@@ -276,44 +684,84 @@ impl TextNormalizer {
                 audio_sentences: Vec::new(),
                 display_to_audio: Vec::new(),
                 audio_to_display: Vec::new(),
+                audio_phonemes: Vec::new(),
             };
         }
 
         let source_hash = hash_sentences(display_sentences);
         let config_hash = self.config_hash();
-        let page_cache_path =
-            self.normalized_cache_path(epub_path, page_idx, &source_hash, &config_hash);
-        if let Some(cached) = self.read_page_plan_cache(&page_cache_path, page_idx) {
-            return cached;
+        let sentence_hashes: Vec<String> = display_sentences
+            .iter()
+            .map(|sentence| hash_sentence(sentence))
+            .collect();
+
+        let (cached_plan, mut cached_sentences) = with_store(epub_path, &config_hash, |store| {
+            if let Some(plan) = store.page(&source_hash) {
+                return (Some(plan.clone()), Vec::new());
+            }
+            let sentences = sentence_hashes
+                .iter()
+                .map(|hash| store.sentence(hash).cloned())
+                .collect();
+            (None, sentences)
+        });
+        if let Some(plan) = cached_plan {
+            tracing::debug!(
+                path = %epub_path.display(),
+                page = page_idx + 1,
+                "Loaded normalized page cache"
+            );
+            return plan;
+        }
+
+        // Normalize outside the store lock so parallel precompute workers do
+        // not serialize on each other.
+        let mut fresh_sentences = Vec::new();
+        for (display_idx, sentence) in display_sentences.iter().enumerate() {
+            let entry = cached_sentences[display_idx].take();
+            let (entry, changed) = self.resolve_sentence_entry(sentence, entry);
+            if changed {
+                fresh_sentences.push((sentence_hashes[display_idx].clone(), entry.clone()));
+            }
+            cached_sentences[display_idx] = Some(entry);
         }
 
         let mut audio_sentences = Vec::with_capacity(display_sentences.len());
         let mut display_to_audio = vec![None; display_sentences.len()];
         let mut audio_to_display = Vec::new();
 
-        for (display_idx, sentence) in display_sentences.iter().enumerate() {
-            if let Some(chunks) =
-                self.normalize_sentence_chunks_cached(epub_path, &config_hash, sentence)
-            {
-                let first_audio_idx = audio_sentences.len();
-                display_to_audio[display_idx] = Some(first_audio_idx);
-                for chunk in chunks {
-                    audio_to_display.push(display_idx);
-                    audio_sentences.push(chunk);
-                }
+        for (display_idx, entry) in cached_sentences.into_iter().enumerate() {
+            let chunks = entry.and_then(|entry| entry.chunks).unwrap_or_default();
+            if chunks.is_empty() {
+                continue;
+            }
+            let first_audio_idx = audio_sentences.len();
+            display_to_audio[display_idx] = Some(first_audio_idx);
+            for chunk in chunks {
+                audio_to_display.push(display_idx);
+                audio_sentences.push(chunk);
             }
         }
 
+        let audio_phonemes = self.phoneme_spans_for(&audio_sentences);
         let plan = PageNormalization {
             audio_sentences,
             display_to_audio,
             audio_to_display,
+            audio_phonemes,
         };
 
-        if let Some(parent) = page_cache_path.parent() {
-            let _ = fs::create_dir_all(parent);
-        }
-        self.write_page_plan_cache(&page_cache_path, page_idx, &plan);
+        with_store(epub_path, &config_hash, |store| {
+            for (hash, entry) in fresh_sentences {
+                store.put_sentence(hash, entry);
+            }
+            store.put_page(source_hash, plan.clone());
+        });
+        tracing::debug!(
+            path = %epub_path.display(),
+            page = page_idx + 1,
+            "Stored normalized page cache"
+        );
 
         plan
     }
@@ -324,17 +772,22 @@ impl TextNormalizer {
                 audio_sentences: Vec::new(),
                 display_to_audio: Vec::new(),
                 audio_to_display: Vec::new(),
+                audio_phonemes: Vec::new(),
             };
         }
 
         if !self.config.enabled {
-            let audio_sentences = display_sentences.to_vec();
+            let audio_sentences = display_sentences
+                .iter()
+                .map(|sentence| strip_display_markers(sentence).into_owned())
+                .collect();
             let display_to_audio = (0..display_sentences.len()).map(Some).collect();
             let audio_to_display = (0..display_sentences.len()).collect();
             return PageNormalization {
                 audio_sentences,
                 display_to_audio,
                 audio_to_display,
+                audio_phonemes: Vec::new(),
             };
         }
 
@@ -365,10 +818,12 @@ impl TextNormalizer {
             }
         }
 
+        let audio_phonemes = self.phoneme_spans_for(&audio_sentences);
         PageNormalization {
             audio_sentences,
             display_to_audio,
             audio_to_display,
+            audio_phonemes,
         }
     }
 
@@ -396,90 +851,243 @@ impl TextNormalizer {
     }
 
     fn clean_text_core(&self, input: &str) -> String {
-        let mut text = normalize_unicode_punctuation(input);
-        text = text.replace('"', "");
+        self.clean_text_core_traced(input, None)
+    }
+
+    fn clean_text_core_traced(
+        &self,
+        input: &str,
+        mut trace: Option<&mut Vec<NormalizationStage>>,
+    ) -> String {
+        let mut text = input.to_string();
+        if text.contains([NOTE_REF_START, NOTE_BODY_START]) {
+            apply_stage(&mut trace, "footnotes", &mut text, |t| {
+                apply_footnote_policy(t, &self.config.footnotes)
+            });
+        }
+        if text.contains(TABLE_ROW_START) {
+            apply_stage(&mut trace, "tables", &mut text, |t| {
+                apply_table_policy(t, &self.config.tables)
+            });
+        }
+        if text.contains(PREFORMATTED_LINE_START) {
+            apply_stage(&mut trace, "code_blocks", &mut text, |t| {
+                apply_code_block_policy(t, &self.config.code_blocks)
+            });
+        }
+        if text.contains(['$', '\\']) {
+            apply_stage(&mut trace, "math", &mut text, |t| {
+                apply_math_policy(t, &self.config.math)
+            });
+        }
+        if text.contains(['#', '>']) {
+            // Heading and quote markers only carry block structure.
+            apply_stage(&mut trace, "block_markers", &mut text, |t| {
+                RE_BLOCK_MARKER.replace_all(t, "").to_string()
+            });
+        }
+        apply_stage(
+            &mut trace,
+            "unicode_punctuation",
+            &mut text,
+            normalize_unicode_punctuation,
+        );
+        apply_stage(&mut trace, "strip_double_quotes", &mut text, |t| {
+            t.replace('"', "")
+        });
 
         if self.config.strip_markdown_links {
-            text = RE_MARKDOWN_LINK.replace_all(&text, "$1").to_string();
+            apply_stage(&mut trace, "strip_markdown_links", &mut text, |t| {
+                RE_MARKDOWN_LINK.replace_all(t, "$1").to_string()
+            });
         }
 
         if self.config.strip_inline_code {
-            text = RE_INLINE_CODE.replace_all(&text, "$1").to_string();
+            apply_stage(&mut trace, "strip_inline_code", &mut text, |t| {
+                RE_INLINE_CODE.replace_all(t, "$1").to_string()
+            });
         }
 
         if self.config.drop_numeric_bracket_citations {
-            text = RE_NUMERIC_BRACKET_CITE.replace_all(&text, " ").to_string();
+            apply_stage(
+                &mut trace,
+                "drop_numeric_bracket_citations",
+                &mut text,
+                |t| RE_NUMERIC_BRACKET_CITE.replace_all(t, " ").to_string(),
+            );
         }
 
         if self.config.drop_parenthetical_numeric_citations {
-            text = RE_PARENTHETICAL_NUMERIC.replace_all(&text, " ").to_string();
+            apply_stage(
+                &mut trace,
+                "drop_parenthetical_numeric_citations",
+                &mut text,
+                |t| RE_PARENTHETICAL_NUMERIC.replace_all(t, " ").to_string(),
+            );
         }
 
         if self.config.drop_superscript_citations {
-            text = RE_SUPERSCRIPT_CITE.replace_all(&text, " ").to_string();
+            apply_stage(&mut trace, "drop_superscript_citations", &mut text, |t| {
+                RE_SUPERSCRIPT_CITE.replace_all(t, " ").to_string()
+            });
         }
 
         if self.config.drop_word_suffix_numeric_footnotes {
-            text = RE_WORD_SUFFIX_FOOTNOTE
-                .replace_all(&text, "$prefix")
-                .to_string();
+            apply_stage(
+                &mut trace,
+                "drop_word_suffix_numeric_footnotes",
+                &mut text,
+                |t| {
+                    RE_WORD_SUFFIX_FOOTNOTE
+                        .replace_all(t, "$prefix")
+                        .to_string()
+                },
+            );
         }
 
         if self.config.drop_square_bracket_text {
-            text = RE_SQUARE_BRACKET_BLOCK.replace_all(&text, " ").to_string();
+            apply_stage(&mut trace, "drop_square_bracket_text", &mut text, |t| {
+                RE_SQUARE_BRACKET_BLOCK.replace_all(t, " ").to_string()
+            });
         }
 
         if self.config.drop_curly_brace_text {
-            text = RE_CURLY_BRACKET_BLOCK.replace_all(&text, " ").to_string();
+            apply_stage(&mut trace, "drop_curly_brace_text", &mut text, |t| {
+                RE_CURLY_BRACKET_BLOCK.replace_all(t, " ").to_string()
+            });
         }
 
         if !self.config.abbreviations.is_empty() {
-            text = apply_abbreviation_map(&text, &self.config.abbreviations);
+            apply_stage(&mut trace, "abbreviations", &mut text, |t| {
+                apply_abbreviation_map(t, &self.config.abbreviations)
+            });
         }
 
         if !self.config.replacements.is_empty() {
-            let mut entries: Vec<_> = self.config.replacements.iter().collect();
-            entries.sort_by_key(|(from, _)| Reverse(from.len()));
-            for (from, to) in entries {
-                text = text.replace(from.as_str(), to.as_str());
-            }
+            apply_stage(&mut trace, "replacements", &mut text, |t| {
+                let mut out = t.to_string();
+                let mut entries: Vec<_> = self.config.replacements.iter().collect();
+                entries.sort_by_key(|(from, _)| Reverse(from.len()));
+                for (from, to) in entries {
+                    out = out.replace(from.as_str(), to.as_str());
+                }
+                out
+            });
         }
 
         if !self.config.drop_tokens.is_empty() {
-            for token in &self.config.drop_tokens {
-                if !token.is_empty() {
-                    text = text.replace(token, " ");
+            apply_stage(&mut trace, "drop_tokens", &mut text, |t| {
+                let mut out = t.to_string();
+                for token in &self.config.drop_tokens {
+                    if !token.is_empty() {
+                        out = out.replace(token, " ");
+                    }
                 }
-            }
+                out
+            });
+        }
+
+        if self.config.heteronyms.enabled {
+            apply_stage(&mut trace, "heteronyms", &mut text, |t| {
+                apply_heteronyms(t, &self.config.heteronyms)
+            });
         }
 
         if self.config.pronunciation.enable_brand_map
             && !self.config.pronunciation.brand_map.is_empty()
         {
-            text = apply_brand_map(&text, &self.config.pronunciation.brand_map);
+            apply_stage(&mut trace, "brand_map", &mut text, |t| {
+                apply_brand_map(t, &self.config.pronunciation.brand_map)
+            });
         }
 
         if !self.config.pronunciation.custom_pronunciations.is_empty() {
-            text = apply_brand_map(&text, &self.config.pronunciation.custom_pronunciations);
+            apply_stage(&mut trace, "custom_pronunciations", &mut text, |t| {
+                apply_brand_map(t, &self.config.pronunciation.custom_pronunciations)
+            });
         }
 
         if self.config.pronunciation.year_mode != YearMode::None {
-            text = apply_year_pronunciation(&text, &self.config.pronunciation);
+            apply_stage(&mut trace, "year_pronunciation", &mut text, |t| {
+                apply_year_pronunciation(t, &self.config.pronunciation)
+            });
         }
 
         if self.config.acronyms.enabled && !self.config.acronyms.tokens.is_empty() {
-            text = apply_acronym_expansion(&text, &self.config.acronyms);
+            apply_stage(&mut trace, "acronyms", &mut text, |t| {
+                apply_acronym_expansion(t, &self.config.acronyms)
+            });
         }
 
         if self.config.collapse_whitespace {
-            text = RE_HORIZONTAL_WS.replace_all(&text, " ").to_string();
+            apply_stage(&mut trace, "collapse_whitespace", &mut text, |t| {
+                RE_HORIZONTAL_WS.replace_all(t, " ").to_string()
+            });
         }
 
         if self.config.remove_space_before_punctuation {
-            text = RE_SPACE_BEFORE_PUNCT.replace_all(&text, "$1").to_string();
+            apply_stage(
+                &mut trace,
+                "remove_space_before_punctuation",
+                &mut text,
+                |t| RE_SPACE_BEFORE_PUNCT.replace_all(t, "$1").to_string(),
+            );
         }
 
-        text.trim().to_string()
+        apply_stage(&mut trace, "trim", &mut text, |t| t.trim().to_string());
+        text
+    }
+
+    /// Run a single display sentence through the sentence-mode pipeline and
+    /// report every stage that ran, in order, with its input and output text.
+    /// Nothing is read from or written to the normalization cache.
+    pub fn explain(&self, sentence: &str) -> NormalizationTrace {
+        if !self.config.enabled {
+            let spoken = strip_display_markers(sentence).into_owned();
+            let chunks = if spoken.trim().is_empty() {
+                Vec::new()
+            } else {
+                vec![spoken.clone()]
+            };
+            return NormalizationTrace {
+                input: sentence.to_string(),
+                stages: Vec::new(),
+                normalized: Some(spoken),
+                chunks,
+                config_hash: self.config_hash(),
+            };
+        }
+
+        let mut stages = Vec::new();
+        let cleaned = self.clean_text_core_traced(sentence, Some(&mut stages));
+        let normalized = self.finalize_sentence(&cleaned);
+        stages.push(NormalizationStage {
+            stage: "finalize_sentence".to_string(),
+            changed: normalized.as_deref() != Some(cleaned.as_str()),
+            before: cleaned,
+            after: normalized.clone().unwrap_or_default(),
+        });
+
+        let chunks = normalized
+            .as_deref()
+            .map(|text| self.chunk_sentence_for_tts(text))
+            .unwrap_or_default();
+        if let Some(text) = normalized.as_deref() {
+            stages.push(NormalizationStage {
+                stage: "chunk_sentence_for_tts".to_string(),
+                before: text.to_string(),
+                after: chunks.join("\n"),
+                changed: chunks.len() != 1 || chunks[0] != text,
+            });
+        }
+
+        NormalizationTrace {
+            input: sentence.to_string(),
+            stages,
+            normalized,
+            chunks,
+            config_hash: self.config_hash(),
+        }
     }
 
     fn finalize_sentence(&self, sentence: &str) -> Option<String> {
@@ -500,6 +1108,14 @@ impl TextNormalizer {
     }
 
     fn chunk_sentence_for_tts(&self, sentence: &str) -> Vec<String> {
+        let (shielded, tags) = shield_speech_markup(sentence);
+        self.chunk_shielded_sentence(&shielded)
+            .iter()
+            .map(|chunk| restore_speech_markup(chunk, &tags))
+            .collect()
+    }
+
+    fn chunk_shielded_sentence(&self, sentence: &str) -> Vec<String> {
         let cleaned = trim_boundary_noise(sentence);
         if cleaned.is_empty() {
             return Vec::new();
@@ -595,6 +1211,56 @@ impl TextNormalizer {
         *current = segment.to_string();
     }
 
+    fn phoneme_spans_for(&self, audio_sentences: &[String]) -> Vec<Vec<PhonemeSpan>> {
+        let overrides = &self.config.pronunciation.phonemes;
+        if overrides.is_empty() {
+            return Vec::new();
+        }
+        let mut entries: Vec<_> = overrides.iter().collect();
+        entries.sort_by_key(|(token, _)| Reverse(token.len()));
+        let patterns: Vec<(Regex, String)> = entries
+            .into_iter()
+            .filter(|(token, _)| !token.trim().is_empty())
+            .filter_map(|(token, phonemes)| {
+                let pattern = Regex::new(&format!(r"(?i)\b{}\b", regex::escape(token))).ok()?;
+                Some((pattern, phonemes.to_ipa()))
+            })
+            .collect();
+
+        audio_sentences
+            .iter()
+            .map(|sentence| {
+                let tags: Vec<_> = RE_SPEECH_MARKUP_SHIELD
+                    .find_iter(sentence)
+                    .map(|tag| tag.range())
+                    .collect();
+                let mut spans: Vec<PhonemeSpan> = Vec::new();
+                for (pattern, phonemes) in &patterns {
+                    for found in pattern.find_iter(sentence) {
+                        let in_tag = tags
+                            .iter()
+                            .any(|tag| found.start() < tag.end && tag.start < found.end());
+                        let overlaps = spans
+                            .iter()
+                            .any(|span| found.start() < span.end && span.start < found.end());
+                        if in_tag {
+                            continue;
+                        }
+                        if !overlaps {
+                            spans.push(PhonemeSpan {
+                                start: found.start(),
+                                end: found.end(),
+                                phonemes: phonemes.clone(),
+                            });
+                        }
+                    }
+                }
+                spans.sort_by_key(|span| span.start);
+                spans
+            })
+            .collect()
+    }
+
     fn config_hash(&self) -> String {
         let serialized = toml::to_string(&self.config).unwrap_or_default();
         let mut hasher = Sha256::new();
@@ -603,60 +1269,27 @@ impl TextNormalizer {
         format!("{:x}", hasher.finalize())
     }
 
-    fn normalized_cache_path(
+    /// Fill in a cached sentence entry, normalizing the sentence when it is
+    /// missing and upgrading older entries that only stored normalized text.
+    /// Returns whether the entry needs to be written back to the store.
+    fn resolve_sentence_entry(
         &self,
-        epub_path: &Path,
-        page_idx: usize,
-        source_hash: &str,
-        config_hash: &str,
-    ) -> PathBuf {
-        let file_name = format!("p{}-{}-{}.toml", page_idx, source_hash, config_hash);
-        normalized_dir(epub_path).join(file_name)
-    }
-
-    fn normalized_sentence_cache_path(
-        &self,
-        epub_path: &Path,
-        sentence_hash: &str,
-        config_hash: &str,
-    ) -> PathBuf {
-        let file_name = format!("s-{}-{}.toml", sentence_hash, config_hash);
-        normalized_dir(epub_path).join(file_name)
-    }
-
-    fn normalize_sentence_chunks_cached(
-        &self,
-        epub_path: &Path,
-        config_hash: &str,
         sentence: &str,
-    ) -> Option<Vec<String>> {
-        let source_hash = hash_sentence(sentence);
-        let cache_path = self.normalized_sentence_cache_path(epub_path, &source_hash, config_hash);
-
-        if let Ok(contents) = fs::read_to_string(&cache_path) {
-            if let Ok(cached) = toml::from_str::<NormalizedSentenceCache>(&contents) {
-                if let Some(chunks) = cached.chunks {
-                    return if chunks.is_empty() {
-                        None
-                    } else {
-                        Some(chunks)
-                    };
-                }
-                if let Some(normalized) = cached.normalized {
-                    let chunks = self.chunk_sentence_for_tts(&normalized);
-                    let upgraded = NormalizedSentenceCache {
-                        normalized: Some(normalized),
-                        chunks: Some(chunks.clone()),
-                    };
-                    self.write_normalized_sentence_cache(&cache_path, &upgraded);
-                    return if chunks.is_empty() {
-                        None
-                    } else {
-                        Some(chunks)
-                    };
-                }
-                return None;
+        cached: Option<StoredSentence>,
+    ) -> (StoredSentence, bool) {
+        if let Some(cached) = cached {
+            if cached.chunks.is_some() {
+                return (cached, false);
+            }
+            if let Some(normalized) = cached.normalized {
+                let chunks = self.chunk_sentence_for_tts(&normalized);
+                let upgraded = StoredSentence {
+                    normalized: Some(normalized),
+                    chunks: Some(chunks),
+                };
+                return (upgraded, true);
             }
+            return (cached, false);
         }
 
         let cleaned = self.clean_text_core(sentence);
@@ -665,86 +1298,78 @@ impl TextNormalizer {
             .as_deref()
             .map(|text| self.chunk_sentence_for_tts(text))
             .unwrap_or_default();
-        let cached = NormalizedSentenceCache {
+        let entry = StoredSentence {
             normalized,
-            chunks: Some(chunks.clone()),
+            chunks: Some(chunks),
         };
-        self.write_normalized_sentence_cache(&cache_path, &cached);
-
-        if chunks.is_empty() {
-            None
-        } else {
-            Some(chunks)
-        }
-    }
-
-    fn write_normalized_sentence_cache(&self, cache_path: &Path, cached: &NormalizedSentenceCache) {
-        if let Some(parent) = cache_path.parent() {
-            let _ = fs::create_dir_all(parent);
-        }
-        match toml::to_string(cached) {
-            Ok(serialized) => {
-                if let Err(err) = fs::write(cache_path, serialized) {
-                    tracing::warn!(
-                        path = %cache_path.display(),
-                        "Failed to write normalized sentence cache: {err}"
-                    );
-                }
-            }
-            Err(err) => {
-                tracing::warn!("Failed to serialize normalized sentence cache: {err}");
-            }
-        }
+        (entry, true)
     }
 
     fn read_page_plan_cache(
         &self,
-        cache_path: &Path,
+        epub_path: &Path,
+        config_hash: &str,
+        source_hash: &str,
         page_idx: usize,
     ) -> Option<PageNormalization> {
-        let contents = fs::read_to_string(cache_path).ok()?;
-        if let Ok(cached) = toml::from_str::<PageNormalization>(&contents) {
-            tracing::debug!(
-                path = %cache_path.display(),
-                page = page_idx + 1,
-                "Loaded normalized page cache"
-            );
-            return Some(cached);
-        }
-        if let Ok(cached) = toml::from_str::<PageNormalizationCache>(&contents) {
-            let plan = cached.into_plan();
-            tracing::debug!(
-                path = %cache_path.display(),
-                page = page_idx + 1,
-                "Loaded normalized page cache"
-            );
-            return Some(plan);
-        }
-        None
+        let cached = with_store(epub_path, config_hash, |store| {
+            store.page(source_hash).cloned()
+        })?;
+        tracing::debug!(
+            path = %epub_path.display(),
+            page = page_idx + 1,
+            "Loaded normalized page cache"
+        );
+        Some(cached)
     }
+}
 
-    fn write_page_plan_cache(&self, cache_path: &Path, page_idx: usize, plan: &PageNormalization) {
-        let serializable = PageNormalizationCache::from_plan(plan);
-        match toml::to_string(&serializable) {
-            Ok(serialized) => {
-                if let Err(err) = fs::write(cache_path, serialized) {
-                    tracing::warn!(
-                        path = %cache_path.display(),
-                        "Failed to write normalized page cache: {err}"
-                    );
-                } else {
-                    tracing::debug!(
-                        path = %cache_path.display(),
-                        page = page_idx + 1,
-                        "Stored normalized page cache"
-                    );
-                }
-            }
-            Err(err) => {
-                tracing::warn!("Failed to serialize normalized page cache: {err}");
-            }
-        }
+/// Modification stamps for the normalizer and abbreviation config files.
+/// Hosts poll this to hot-reload edited configs without a file-watcher crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigFileStamp {
+    entries: Vec<(PathBuf, Option<SystemTime>, u64)>,
+}
+
+impl ConfigFileStamp {
+    pub fn capture() -> Self {
+        let normalizer_path = resolve_default_normalizer_path();
+        let abbreviations_path = resolve_abbreviations_path(&normalizer_path);
+        let language_paths = language_abbreviation_paths(&abbreviations_path);
+        let entries = [normalizer_path, abbreviations_path]
+            .into_iter()
+            .chain(language_paths)
+            .map(|path| {
+                let metadata = fs::metadata(&path).ok();
+                let modified = metadata.as_ref().and_then(|meta| meta.modified().ok());
+                let len = metadata.map(|meta| meta.len()).unwrap_or(0);
+                (path, modified, len)
+            })
+            .collect();
+        Self { entries }
     }
+
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.entries.iter().map(|(path, _, _)| path.as_path())
+    }
+}
+
+/// Per-language `abbreviations/<subtag>.toml` files next to the English
+/// abbreviations file, sorted so stamps compare stably.
+fn language_abbreviation_paths(abbreviations_path: &Path) -> Vec<PathBuf> {
+    let dir = abbreviations_path
+        .parent()
+        .map(|parent| parent.join("abbreviations"))
+        .unwrap_or_else(|| PathBuf::from("abbreviations"));
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+    paths
 }
 
 fn resolve_default_normalizer_path() -> PathBuf {
@@ -768,32 +1393,49 @@ fn resolve_default_normalizer_path() -> PathBuf {
         }
     }
 
-    PathBuf::from(DEFAULT_NORMALIZER_PATH)
+    PathBuf::from(DEFAULT_NORMALIZER_PATH)
+}
+
+fn read_normalization_config(path: &Path) -> Result<NormalizerConfig, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+    toml::from_str::<NormalizerFile>(&contents)
+        .map(|file| file.normalization)
+        .map_err(|err| {
+            format!(
+                "Invalid normalizer config TOML in {}: {err}",
+                path.display()
+            )
+        })
 }
 
 fn load_external_abbreviations(normalizer_config_path: &Path) -> AbbreviationConfig {
+    try_load_external_abbreviations(normalizer_config_path).unwrap_or_else(|err| {
+        tracing::warn!("{err}");
+        AbbreviationConfig::default()
+    })
+}
+
+fn try_load_external_abbreviations(
+    normalizer_config_path: &Path,
+) -> Result<AbbreviationConfig, String> {
     let path = resolve_abbreviations_path(normalizer_config_path);
     let Ok(contents) = fs::read_to_string(&path) else {
-        return AbbreviationConfig::default();
+        return Ok(AbbreviationConfig::default());
     };
-    match toml::from_str::<AbbreviationsFile>(&contents) {
-        Ok(file) => {
-            let merged = file.abbreviations.merged();
-            tracing::info!(
-                path = %path.display(),
-                count = merged.case.len() + merged.nocase.len(),
-                "Loaded external abbreviations config"
-            );
-            merged
-        }
-        Err(err) => {
-            tracing::warn!(
-                path = %path.display(),
-                "Invalid abbreviations config TOML: {err}"
-            );
-            AbbreviationConfig::default()
-        }
-    }
+    let file = toml::from_str::<AbbreviationsFile>(&contents).map_err(|err| {
+        format!(
+            "Invalid abbreviations config TOML in {}: {err}",
+            path.display()
+        )
+    })?;
+    let merged = file.abbreviations.merged();
+    tracing::info!(
+        path = %path.display(),
+        count = merged.case.len() + merged.nocase.len(),
+        "Loaded external abbreviations config"
+    );
+    Ok(merged)
 }
 
 fn resolve_abbreviations_path(normalizer_config_path: &Path) -> PathBuf {
@@ -884,6 +1526,293 @@ fn default_letter_sounds() -> BTreeMap<String, String> {
     map
 }
 
+/// Replace SSML-lite tags, and `say-as`/`sub`/`phoneme` elements with their
+/// inner text, with private-use placeholders so text stages and chunking
+/// cannot rewrite or split them.
+fn shield_speech_markup(text: &str) -> (Cow<'_, str>, Vec<String>) {
+    if !text.contains('<') {
+        return (Cow::Borrowed(text), Vec::new());
+    }
+    let mut tags = Vec::new();
+    let shielded = RE_SPEECH_MARKUP_SHIELD.replace_all(text, |caps: &regex::Captures<'_>| {
+        let placeholder = char::from_u32(0xE100 + tags.len() as u32).unwrap_or('\u{E100}');
+        tags.push(caps[0].to_string());
+        format!("\u{E000}{placeholder}\u{E001}")
+    });
+    (shielded, tags)
+}
+
+fn restore_speech_markup(text: &str, tags: &[String]) -> String {
+    if tags.is_empty() {
+        return text.to_string();
+    }
+    RE_SHIELDED_MARKUP
+        .replace_all(text, |caps: &regex::Captures<'_>| {
+            caps[1]
+                .chars()
+                .next()
+                .and_then(|ch| tags.get((ch as u32).wrapping_sub(0xE100) as usize))
+                .cloned()
+                .unwrap_or_default()
+        })
+        .to_string()
+}
+
+/// Remove SSML-lite tags from an audio sentence for display. `<sub>` and
+/// `<phoneme>` keep their written text.
+pub fn strip_speech_markup(text: &str) -> String {
+    if !text.contains('<') {
+        return text.to_string();
+    }
+    let stripped = RE_SPEECH_MARKUP_TAG.replace_all(text, "");
+    RE_HORIZONTAL_WS
+        .replace_all(stripped.trim(), " ")
+        .to_string()
+}
+
+fn apply_stage<F>(
+    trace: &mut Option<&mut Vec<NormalizationStage>>,
+    stage: &str,
+    text: &mut String,
+    apply: F,
+) where
+    F: FnOnce(&str) -> String,
+{
+    let (shielded, tags) = shield_speech_markup(text);
+    let after = restore_speech_markup(&apply(&shielded), &tags);
+    if let Some(trace) = trace.as_deref_mut() {
+        trace.push(NormalizationStage {
+            stage: stage.to_string(),
+            before: text.clone(),
+            changed: *text != after,
+            after: after.clone(),
+        });
+    }
+    *text = after;
+}
+
+fn apply_footnote_policy(text: &str, cfg: &FootnoteConfig) -> String {
+    let text = RE_NOTE_REF.replace_all(text, "");
+    RE_NOTE_BODY
+        .replace_all(&text, |caps: &regex::Captures<'_>| {
+            if cfg.mode == FootnoteMode::Skip {
+                return String::new();
+            }
+            match parse_note_body(&caps[0]) {
+                Some(body) => format!(
+                    "{} {}",
+                    cfg.announce.replace("{label}", body.label),
+                    body.text
+                ),
+                None => String::new(),
+            }
+        })
+        .to_string()
+}
+
+fn apply_math_policy(text: &str, cfg: &MathConfig) -> String {
+    let spans = find_math_spans(text);
+    if spans.is_empty() {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for span in spans {
+        out.push_str(&text[last..span.range.start]);
+        let spoken = match cfg.mode {
+            MathMode::Speak => verbalize(&text[span.tex]).unwrap_or_else(|| cfg.fallback.clone()),
+            MathMode::Announce => cfg.fallback.clone(),
+            MathMode::Skip => String::new(),
+        };
+        out.push(' ');
+        out.push_str(&spoken);
+        out.push(' ');
+        last = span.range.end;
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+fn apply_table_policy(text: &str, cfg: &TableConfig) -> String {
+    RE_TABLE_ROW
+        .replace_all(text, |caps: &regex::Captures<'_>| {
+            if cfg.mode == TableMode::Skip {
+                return String::new();
+            }
+            let Some(row) = parse_table_row(&caps[0]) else {
+                return String::new();
+            };
+            let Some(number) = row.number else {
+                let columns = row
+                    .cells
+                    .iter()
+                    .map(|cell| cell.trim())
+                    .filter(|cell| !cell.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                return cfg.header_template.replace("{cells}", &columns);
+            };
+            let cells = row
+                .cells
+                .iter()
+                .enumerate()
+                .map(|(idx, cell)| (row.columns.get(idx).map(|c| c.trim()), cell.trim()))
+                .filter(|(_, cell)| !cell.is_empty())
+                .map(|(column, cell)| match column {
+                    Some(column) if !column.is_empty() => format!("{column} {cell}"),
+                    _ => cell.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            cfg.row_template
+                .replace("{number}", &number.to_string())
+                .replace("{cells}", &cells)
+        })
+        .to_string()
+}
+
+fn apply_code_block_policy(text: &str, cfg: &CodeBlockConfig) -> String {
+    RE_PREFORMATTED_LINE
+        .replace_all(text, |caps: &regex::Captures<'_>| {
+            let Some(line) = parse_preformatted_line(&caps[0]) else {
+                return caps[0]
+                    .chars()
+                    .filter(|ch| *ch != PREFORMATTED_LINE_START && *ch != PREFORMATTED_LINE_END)
+                    .collect();
+            };
+            let (policy, template) = match line.kind {
+                PreformattedKind::Code => (cfg.code, &cfg.announce_code),
+                PreformattedKind::Preformatted => (cfg.preformatted, &cfg.announce_preformatted),
+            };
+            match policy {
+                CodeBlockPolicy::Skip => String::new(),
+                CodeBlockPolicy::Announce => line
+                    .block_lines
+                    .map(|count| {
+                        let lines = if count == 1 {
+                            "1 line".to_string()
+                        } else {
+                            format!("{count} lines")
+                        };
+                        template.replace("{lines}", &lines)
+                    })
+                    .unwrap_or_default(),
+                CodeBlockPolicy::Comments => extract_code_comment(line.text, &cfg.comment_prefixes)
+                    .map(str::to_string)
+                    .unwrap_or_default(),
+                CodeBlockPolicy::Verbatim => spell_code_symbols(line.text, &cfg.symbol_names),
+                CodeBlockPolicy::Read => line.text.trim().to_string(),
+            }
+        })
+        .to_string()
+}
+
+/// Comment text of a code line: a whole-line comment, or a trailing `//`/`#`
+/// comment separated from the code by whitespace.
+fn extract_code_comment<'a>(line: &'a str, prefixes: &[String]) -> Option<&'a str> {
+    let mut prefixes: Vec<&str> = prefixes
+        .iter()
+        .map(String::as_str)
+        .filter(|prefix| !prefix.is_empty())
+        .collect();
+    prefixes.sort_by_key(|prefix| Reverse(prefix.len()));
+
+    let trimmed = line.trim_start();
+    let body = prefixes
+        .iter()
+        .find_map(|prefix| trimmed.strip_prefix(prefix))
+        .or_else(|| {
+            ["//", "#"]
+                .iter()
+                .filter(|marker| prefixes.contains(marker))
+                .filter_map(|marker| {
+                    trimmed
+                        .find(&format!(" {marker} "))
+                        .map(|pos| &trimmed[pos + marker.len() + 2..])
+                })
+                .next()
+        })?;
+    let body = body
+        .trim()
+        .trim_end_matches("*/")
+        .trim_end_matches("-->")
+        .trim();
+    (!body.is_empty()).then_some(body)
+}
+
+fn spell_code_symbols(line: &str, symbol_names: &BTreeMap<String, String>) -> String {
+    let mut symbols: Vec<(&str, &str)> = symbol_names
+        .iter()
+        .filter(|(symbol, _)| !symbol.is_empty())
+        .map(|(symbol, name)| (symbol.as_str(), name.as_str()))
+        .collect();
+    symbols.sort_by_key(|(symbol, _)| Reverse(symbol.len()));
+
+    let mut out = String::with_capacity(line.len() * 2);
+    let mut rest = line.trim();
+    while let Some(ch) = rest.chars().next() {
+        if let Some((symbol, name)) = symbols.iter().find(|(symbol, _)| rest.starts_with(symbol)) {
+            out.push(' ');
+            out.push_str(name);
+            out.push(' ');
+            rest = &rest[symbol.len()..];
+        } else {
+            out.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        }
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn default_code_symbol_names() -> BTreeMap<String, String> {
+    [
+        ("(", "open paren"),
+        (")", "close paren"),
+        ("[", "open bracket"),
+        ("]", "close bracket"),
+        ("{", "open brace"),
+        ("}", "close brace"),
+        ("<", "less than"),
+        (">", "greater than"),
+        ("<=", "less or equal"),
+        (">=", "greater or equal"),
+        ("=", "equals"),
+        ("==", "double equals"),
+        ("!=", "not equals"),
+        ("=>", "fat arrow"),
+        ("->", "arrow"),
+        ("::", "double colon"),
+        (":", "colon"),
+        (";", "semicolon"),
+        (",", "comma"),
+        (".", "dot"),
+        ("_", "underscore"),
+        ("&&", "and and"),
+        ("||", "or or"),
+        ("&", "ampersand"),
+        ("|", "pipe"),
+        ("!", "bang"),
+        ("?", "question mark"),
+        ("+", "plus"),
+        ("-", "minus"),
+        ("*", "star"),
+        ("/", "slash"),
+        ("\\", "backslash"),
+        ("%", "percent"),
+        ("#", "hash"),
+        ("@", "at"),
+        ("$", "dollar"),
+        ("^", "caret"),
+        ("~", "tilde"),
+        ("\"", "quote"),
+        ("'", "single quote"),
+        ("`", "backtick"),
+    ]
+    .into_iter()
+    .map(|(symbol, name)| (symbol.to_string(), name.to_string()))
+    .collect()
+}
+
 fn apply_brand_map(text: &str, brand_map: &BTreeMap<String, String>) -> String {
     let mut out = text.to_string();
     let mut entries: Vec<_> = brand_map.iter().collect();
@@ -897,6 +1826,442 @@ fn apply_brand_map(text: &str, brand_map: &BTreeMap<String, String>) -> String {
     out
 }
 
+/// Convert English espeak phoneme mnemonics (e.g. `k'eItoU`) to IPA using a
+/// greedy longest-match table. Unknown characters pass through unchanged.
+fn espeak_to_ipa(mnemonics: &str) -> String {
+    const TABLE: &[(&str, &str)] = &[
+        ("aI@", "aɪə"),
+        ("aU@", "aʊə"),
+        ("3:", "ɜː"),
+        ("A:", "ɑː"),
+        ("O:", "ɔː"),
+        ("i:", "iː"),
+        ("u:", "uː"),
+        ("i@", "ɪə"),
+        ("e@", "eə"),
+        ("U@", "ʊə"),
+        ("O@", "ɔː"),
+        ("A@", "ɑː"),
+        ("aI", "aɪ"),
+        ("aU", "aʊ"),
+        ("eI", "eɪ"),
+        ("oU", "oʊ"),
+        ("OI", "ɔɪ"),
+        ("tS", "tʃ"),
+        ("dZ", "dʒ"),
+        ("@L", "əl"),
+        ("I2", "ɪ"),
+        ("@", "ə"),
+        ("3", "ɚ"),
+        ("a", "æ"),
+        ("A", "ɑ"),
+        ("E", "ɛ"),
+        ("I", "ɪ"),
+        ("O", "ɔ"),
+        ("U", "ʊ"),
+        ("V", "ʌ"),
+        ("0", "ɒ"),
+        ("T", "θ"),
+        ("D", "ð"),
+        ("S", "ʃ"),
+        ("Z", "ʒ"),
+        ("N", "ŋ"),
+        ("r", "ɹ"),
+        ("'", "ˈ"),
+        (",", "ˌ"),
+        (":", "ː"),
+        ("_", " "),
+    ];
+
+    let mut out = String::with_capacity(mnemonics.len() * 2);
+    let mut rest = mnemonics;
+    'outer: while !rest.is_empty() {
+        for (from, to) in TABLE {
+            if let Some(tail) = rest.strip_prefix(from) {
+                out.push_str(to);
+                rest = tail;
+                continue 'outer;
+            }
+        }
+        let mut chars = rest.chars();
+        if let Some(ch) = chars.next() {
+            out.push(ch);
+        }
+        rest = chars.as_str();
+    }
+    out
+}
+
+fn heteronym_rule(
+    word: &str,
+    replace: &str,
+    preceded_by: &[&str],
+    followed_by: &[&str],
+    window: usize,
+) -> HeteronymRule {
+    HeteronymRule {
+        word: word.to_string(),
+        replace: replace.to_string(),
+        preceded_by: preceded_by.iter().map(|w| w.to_string()).collect(),
+        followed_by: followed_by.iter().map(|w| w.to_string()).collect(),
+        window,
+    }
+}
+
+fn default_heteronym_rules() -> Vec<HeteronymRule> {
+    const MODALS: &[&str] = &[
+        "to", "will", "would", "can", "could", "should", "must", "might", "don't", "didn't",
+        "won't", "can't", "let's",
+    ];
+    const SUBJECTS: &[&str] = &["i", "we", "they", "you"];
+    const DETERMINERS: &[&str] = &[
+        "a", "an", "the", "this", "that", "his", "her", "their", "our", "my", "your", "its",
+    ];
+    let verb_cues: Vec<&str> = MODALS.iter().chain(SUBJECTS).copied().collect();
+
+    vec![
+        heteronym_rule("read", "read", &["to"], &[], 1),
+        heteronym_rule(
+            "read",
+            "red",
+            &[
+                "had",
+                "have",
+                "has",
+                "having",
+                "was",
+                "were",
+                "been",
+                "be",
+                "being",
+                "is",
+                "are",
+                "already",
+                "just",
+                "ever",
+                "once",
+                "recently",
+                "previously",
+                "widely",
+                "i've",
+                "you've",
+                "we've",
+                "they've",
+                "i'd",
+                "you'd",
+                "he'd",
+                "she'd",
+                "we'd",
+                "they'd",
+            ],
+            &[],
+            2,
+        ),
+        heteronym_rule("read", "red", &[], &["yesterday", "last", "earlier"], 2),
+        heteronym_rule(
+            "lead",
+            "led",
+            &[],
+            &[
+                "poisoning",
+                "pipe",
+                "pipes",
+                "paint",
+                "pencil",
+                "pencils",
+                "weight",
+                "weights",
+                "shot",
+                "acetate",
+                "oxide",
+                "bullet",
+                "bullets",
+                "balloon",
+                "crystal",
+                "exposure",
+                "levels",
+                "contamination",
+            ],
+            1,
+        ),
+        heteronym_rule("lead", "led", &["of"], &[], 1),
+        heteronym_rule(
+            "live",
+            "live",
+            &[
+                "to", "will", "would", "can", "could", "should", "must", "might", "we", "they",
+                "i", "you", "people", "who", "don't", "didn't", "doesn't",
+            ],
+            &[],
+            1,
+        ),
+        heteronym_rule(
+            "live",
+            "lyve",
+            &[],
+            &[
+                "music",
+                "broadcast",
+                "audience",
+                "performance",
+                "show",
+                "shows",
+                "concert",
+                "concerts",
+                "wire",
+                "ammunition",
+                "bait",
+                "stream",
+                "streaming",
+                "recording",
+                "album",
+                "feed",
+                "coverage",
+                "television",
+                "tv",
+                "event",
+                "events",
+                "animals",
+                "action",
+                "fire",
+                "band",
+                "version",
+                "demo",
+                "chat",
+                "updates",
+            ],
+            1,
+        ),
+        heteronym_rule(
+            "live",
+            "lyve",
+            &[
+                "a", "an", "went", "go", "goes", "going", "gone", "is", "are", "was", "were",
+            ],
+            &[],
+            1,
+        ),
+        heteronym_rule(
+            "tear",
+            "tare",
+            &[],
+            &[
+                "down", "up", "apart", "off", "open", "through", "into", "out", "away", "it",
+                "them", "the",
+            ],
+            1,
+        ),
+        heteronym_rule("tear", "tare", MODALS, &[], 1),
+        heteronym_rule(
+            "wind",
+            "wind",
+            &[
+                "the", "a", "of", "cold", "strong", "north", "south", "east", "west", "solar",
+                "warm", "icy", "bitter",
+            ],
+            &[],
+            1,
+        ),
+        heteronym_rule(
+            "wind",
+            "wynd",
+            &[],
+            &[
+                "up", "down", "around", "round", "through", "back", "along", "its", "their", "the",
+            ],
+            1,
+        ),
+        heteronym_rule("wind", "wynd", MODALS, &[], 1),
+        heteronym_rule(
+            "close",
+            "close",
+            &[
+                "to", "will", "would", "can", "could", "should", "must", "might", "please",
+                "don't", "didn't", "let's", "i", "we", "they", "you",
+            ],
+            &[],
+            1,
+        ),
+        heteronym_rule(
+            "close",
+            "cloce",
+            &[],
+            &[
+                "to",
+                "by",
+                "call",
+                "calls",
+                "friend",
+                "friends",
+                "relative",
+                "relatives",
+                "attention",
+                "range",
+                "enough",
+                "together",
+                "behind",
+                "quarters",
+                "proximity",
+                "second",
+                "contact",
+                "ties",
+                "encounter",
+            ],
+            1,
+        ),
+        heteronym_rule(
+            "close",
+            "cloce",
+            &[
+                "very", "so", "too", "quite", "fairly", "pretty", "as", "come", "came", "stay",
+                "stayed", "get", "got", "hold", "held", "draw", "drew", "keep", "kept", "is",
+                "are", "was", "were",
+            ],
+            &[],
+            1,
+        ),
+        heteronym_rule("record", "rih cord", &verb_cues, &[], 1),
+        heteronym_rule(
+            "record",
+            "reckerd",
+            &DETERMINERS
+                .iter()
+                .chain(&[
+                    "world",
+                    "new",
+                    "criminal",
+                    "public",
+                    "track",
+                    "off",
+                    "on",
+                    "broken",
+                    "official",
+                    "historical",
+                    "written",
+                    "vinyl",
+                    "best",
+                    "personal",
+                ])
+                .copied()
+                .collect::<Vec<_>>(),
+            &[],
+            1,
+        ),
+        heteronym_rule("present", "prizent", &verb_cues, &[], 1),
+        heteronym_rule(
+            "present",
+            "prezzent",
+            &DETERMINERS
+                .iter()
+                .chain(&[
+                    "at",
+                    "birthday",
+                    "christmas",
+                    "wedding",
+                    "for",
+                    "until",
+                    "up",
+                ])
+                .copied()
+                .collect::<Vec<_>>(),
+            &[],
+            1,
+        ),
+        heteronym_rule(
+            "present",
+            "prezzent",
+            &[],
+            &[
+                "day",
+                "tense",
+                "moment",
+                "company",
+                "situation",
+                "time",
+                "age",
+                "era",
+                "circumstances",
+                "participle",
+            ],
+            1,
+        ),
+    ]
+}
+
+fn apply_heteronyms(text: &str, cfg: &HeteronymConfig) -> String {
+    let words: Vec<_> = RE_WORD_TOKEN.find_iter(text).collect();
+    let lowered: Vec<String> = words.iter().map(|m| m.as_str().to_lowercase()).collect();
+    let rules: Vec<&HeteronymRule> = cfg.custom_rules.iter().chain(cfg.rules.iter()).collect();
+
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for (idx, word) in words.iter().enumerate() {
+        let Some(rule) = rules.iter().find(|rule| {
+            rule.word.eq_ignore_ascii_case(&lowered[idx])
+                && heteronym_rule_matches(rule, text, &words, &lowered, idx)
+        }) else {
+            continue;
+        };
+        out.push_str(&text[last..word.start()]);
+        out.push_str(&match_word_case(word.as_str(), &rule.replace));
+        last = word.end();
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+fn heteronym_rule_matches(
+    rule: &HeteronymRule,
+    text: &str,
+    words: &[regex::Match<'_>],
+    lowered: &[String],
+    idx: usize,
+) -> bool {
+    let window = rule.window.max(1);
+    let in_list =
+        |list: &[String], i: usize| list.iter().any(|w| w.eq_ignore_ascii_case(&lowered[i]));
+    let joined = |left: usize, right: usize| {
+        !text[words[left].end()..words[right].start()]
+            .chars()
+            .any(|ch| {
+                matches!(
+                    ch,
+                    '.' | ',' | ';' | ':' | '!' | '?' | '(' | ')' | '"' | '—'
+                )
+            })
+    };
+
+    let preceded = (0..idx)
+        .rev()
+        .take_while(|&i| joined(i, i + 1))
+        .take(window)
+        .any(|i| in_list(rule.preceded_by.as_slice(), i));
+    let followed = (idx + 1..words.len())
+        .take_while(|&i| joined(i - 1, i))
+        .take(window)
+        .any(|i| in_list(rule.followed_by.as_slice(), i));
+    let unconditional = rule.preceded_by.is_empty() && rule.followed_by.is_empty();
+    preceded || followed || unconditional
+}
+
+fn match_word_case(original: &str, replacement: &str) -> String {
+    let mut chars = original.chars();
+    let Some(first) = chars.next() else {
+        return replacement.to_string();
+    };
+    if original.chars().count() > 1 && original.chars().all(|ch| !ch.is_lowercase()) {
+        return replacement.to_uppercase();
+    }
+    if first.is_uppercase() {
+        let mut rest = replacement.chars();
+        return match rest.next() {
+            Some(head) => head.to_uppercase().chain(rest).collect(),
+            None => String::new(),
+        };
+    }
+    replacement.to_string()
+}
+
 fn default_abbreviations() -> AbbreviationConfig {
     let mut nocase = BTreeMap::new();
     nocase.insert("Mr.".to_string(), "Mister".to_string());
@@ -1313,14 +2678,6 @@ fn hash_sentence(sentence: &str) -> String {
     format!("{:x}", hasher.finalize())
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
-struct NormalizedSentenceCache {
-    #[serde(default)]
-    normalized: Option<String>,
-    #[serde(default)]
-    chunks: Option<Vec<String>>,
-}
-
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 #[serde(default)]
 struct AbbreviationsFile {
@@ -1379,43 +2736,14 @@ impl AbbreviationConfig {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
-struct PageNormalizationCache {
-    audio_sentences: Vec<String>,
-    // TOML cannot encode `None` in arrays directly; use `-1` sentinel.
-    display_to_audio: Vec<isize>,
-    audio_to_display: Vec<usize>,
-}
-
-impl PageNormalizationCache {
-    fn from_plan(plan: &PageNormalization) -> Self {
-        Self {
-            audio_sentences: plan.audio_sentences.clone(),
-            display_to_audio: plan
-                .display_to_audio
-                .iter()
-                .map(|entry| entry.map(|idx| idx as isize).unwrap_or(-1))
-                .collect(),
-            audio_to_display: plan.audio_to_display.clone(),
-        }
-    }
-
-    fn into_plan(self) -> PageNormalization {
-        PageNormalization {
-            audio_sentences: self.audio_sentences,
-            display_to_audio: self
-                .display_to_audio
-                .into_iter()
-                .map(|idx| if idx < 0 { None } else { Some(idx as usize) })
-                .collect(),
-            audio_to_display: self.audio_to_display,
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cache::normalized_dir;
+    use crate::text_utils::{
+        encode_note_body, encode_note_ref, encode_preformatted_block, encode_table,
+        layout_footnotes, split_sentences,
+    };
     use std::sync::{Mutex, OnceLock};
     use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -1669,38 +2997,39 @@ mod tests {
         let page_a = vec!["Alpha sentence.".to_string(), "Beta sentence.".to_string()];
         let page_b = vec!["Beta sentence.".to_string(), "Gamma sentence.".to_string()];
 
+        let count_records = |kind: &str| -> usize {
+            let files: Vec<PathBuf> = fs::read_dir(&cache_root)
+                .expect("cache dir should exist")
+                .flatten()
+                .map(|entry| entry.path())
+                .collect();
+            assert_eq!(
+                files.len(),
+                1,
+                "normalization cache should live in a single store file"
+            );
+            let needle = format!("\"kind\":\"{kind}\"");
+            fs::read_to_string(&files[0])
+                .expect("store should be readable")
+                .lines()
+                .filter(|line| line.contains(&needle))
+                .count()
+        };
+
         let _ = normalizer.plan_page_cached(&epub_path, 0, &page_a);
-        let files_after_first: Vec<String> = fs::read_dir(&cache_root)
-            .expect("cache dir should exist")
-            .flatten()
-            .map(|entry| entry.file_name().to_string_lossy().to_string())
-            .collect();
-        let first_sentence_files = files_after_first
-            .iter()
-            .filter(|name| name.starts_with("s-"))
-            .count();
-        assert_eq!(first_sentence_files, 2);
-
-        let _ = normalizer.plan_page_cached(&epub_path, 99, &page_b);
-        let files_after_second: Vec<String> = fs::read_dir(&cache_root)
-            .expect("cache dir should exist")
-            .flatten()
-            .map(|entry| entry.file_name().to_string_lossy().to_string())
-            .collect();
-        let second_sentence_files = files_after_second
-            .iter()
-            .filter(|name| name.starts_with("s-"))
-            .count();
-        assert_eq!(second_sentence_files, 3);
-        let page_cache_files = files_after_second
-            .iter()
-            .filter(|name| name.starts_with("p"))
-            .count();
+        assert_eq!(count_records("sentence"), 2);
+
+        let plan_b = normalizer.plan_page_cached(&epub_path, 99, &page_b);
+        assert_eq!(count_records("sentence"), 3);
         assert!(
-            page_cache_files >= 2,
+            count_records("page") >= 2,
             "sentence mode should persist page-level normalization plans for fast reloads"
         );
 
+        let reloaded = normalizer.plan_page_cached(&epub_path, 99, &page_b);
+        assert_eq!(reloaded, plan_b);
+        assert_eq!(count_records("sentence"), 3);
+
         let _ = fs::remove_dir_all(&cache_root);
     }
 
@@ -1753,4 +3082,497 @@ mod tests {
         assert_eq!(plan.audio_sentences.len(), 1);
         assert_eq!(plan.audio_sentences[0], "Alpha beta gamma delta.");
     }
+
+    #[test]
+    fn book_overlay_applies_pronunciations_and_changes_config_hash() {
+        let base = TextNormalizer::default();
+        let mut overlay = NormalizerOverlay::default();
+        overlay
+            .custom_pronunciations
+            .insert("Daenerys".to_string(), "Duh nair iss".to_string());
+        let book = base.with_overlay(&overlay);
+
+        let page = vec![String::from("Daenerys crossed the sea.")];
+        assert_eq!(
+            base.plan_page(&page).audio_sentences[0],
+            "Daenerys crossed the sea."
+        );
+        assert_eq!(
+            book.plan_page(&page).audio_sentences[0],
+            "Duh nair iss crossed the sea."
+        );
+        assert_ne!(base.config_hash(), book.config_hash());
+        assert_eq!(
+            base.config_hash(),
+            base.with_overlay(&NormalizerOverlay::default())
+                .config_hash()
+        );
+    }
+
+    #[test]
+    fn explain_reports_ordered_stages_with_before_and_after_text() {
+        let normalizer = TextNormalizer::default();
+        let trace = normalizer.explain("Mr. Hale wrote this² in 1999 [3].");
+
+        let changed: Vec<&str> = trace
+            .stages
+            .iter()
+            .filter(|stage| stage.changed)
+            .map(|stage| stage.stage.as_str())
+            .collect();
+        assert_eq!(
+            changed,
+            vec![
+                "drop_numeric_bracket_citations",
+                "drop_superscript_citations",
+                "abbreviations",
+                "year_pronunciation",
+                "collapse_whitespace",
+                "remove_space_before_punctuation",
+            ]
+        );
+        for pair in trace.stages.windows(2) {
+            assert_eq!(pair[0].after, pair[1].before);
+        }
+        let last = trace.stages.last().expect("chunk stage should be present");
+        assert_eq!(last.stage, "chunk_sentence_for_tts");
+        assert_eq!(
+            trace.chunks,
+            normalizer
+                .plan_page(std::slice::from_ref(&trace.input))
+                .audio_sentences
+        );
+
+        let mut disabled = TextNormalizer::default();
+        disabled.config.enabled = false;
+        let marked = format!("A claim.{}", encode_note_ref("1"));
+        let trace = disabled.explain(&marked);
+        assert_eq!(
+            trace.chunks,
+            disabled
+                .plan_page(std::slice::from_ref(&marked))
+                .audio_sentences
+        );
+        assert_eq!(
+            trace.normalized,
+            Some(strip_display_markers(&marked).into_owned())
+        );
+        assert!(disabled.explain("  ").chunks.is_empty());
+    }
+
+    #[test]
+    fn heteronym_rules_pass_sentence_corpus() {
+        let corpus = [
+            (
+                "I have read that book twice.",
+                "I have red that book twice.",
+            ),
+            ("I like to read before bed.", "I like to read before bed."),
+            ("She read it yesterday.", "She red it yesterday."),
+            (
+                "Please read the instructions.",
+                "Please read the instructions.",
+            ),
+            ("The book was read aloud.", "The book was red aloud."),
+            ("Have you read it?", "Have you red it?"),
+            (
+                "They had finished. Read the rest.",
+                "They had finished. Read the rest.",
+            ),
+            (
+                "Exposure to lead paint is dangerous.",
+                "Exposure to led paint is dangerous.",
+            ),
+            (
+                "The pipes were made of lead.",
+                "The pipes were made of led.",
+            ),
+            ("They will lead the team.", "They will lead the team."),
+            ("We watched a live concert.", "We watched a lyve concert."),
+            ("Live music filled the hall.", "Lyve music filled the hall."),
+            ("Where do you live now?", "Where do you live now?"),
+            ("The show went live at noon.", "The show went lyve at noon."),
+            (
+                "A tear rolled down her cheek.",
+                "A tear rolled down her cheek.",
+            ),
+            ("Don't tear the page.", "Don't tare the page."),
+            ("They tear down old houses.", "They tare down old houses."),
+            ("The wind was cold.", "The wind was cold."),
+            ("Remember to wind the clock.", "Remember to wynd the clock."),
+            (
+                "The road will wind through the hills.",
+                "The road will wynd through the hills.",
+            ),
+            ("Please close the door.", "Please close the door."),
+            (
+                "We live close to the station.",
+                "We live cloce to the station.",
+            ),
+            ("That was a close call.", "That was a cloce call."),
+            (
+                "The store will close at nine.",
+                "The store will close at nine.",
+            ),
+            (
+                "She set a new world record.",
+                "She set a new world reckerd.",
+            ),
+            ("They record every meeting.", "They rih cord every meeting."),
+            ("We need to record this.", "We need to rih cord this."),
+            ("She bought him a present.", "She bought him a prezzent."),
+            (
+                "I will present the findings.",
+                "I will prizent the findings.",
+            ),
+            (
+                "At present, nothing has changed.",
+                "At prezzent, nothing has changed.",
+            ),
+        ];
+
+        let cfg = HeteronymConfig::default();
+        for (input, expected) in corpus {
+            assert_eq!(apply_heteronyms(input, &cfg), expected, "input: {input}");
+        }
+    }
+
+    #[test]
+    fn heteronym_custom_rules_take_precedence_and_run_in_pipeline() {
+        let mut normalizer = TextNormalizer::default();
+        normalizer.config.heteronyms.custom_rules = vec![
+            heteronym_rule("bass", "base", &[], &["guitar", "player"], 1),
+            heteronym_rule("read", "read", &["has"], &[], 1),
+        ];
+
+        assert_eq!(
+            normalizer.clean_text_core("He has read about the bass guitar."),
+            "He has read about the base guitar."
+        );
+        assert_eq!(
+            TextNormalizer::default().clean_text_core("She has read it."),
+            "She has red it."
+        );
+    }
+
+    #[test]
+    fn code_block_policies_change_audio_but_keep_display_lines() {
+        let body = "// Sum two values.\nfn add(a: i32, b: i32) -> i32 {\n    a + b // no overflow check\n}";
+        let block = encode_preformatted_block(PreformattedKind::Code, body);
+        let display = split_sentences(&format!("Here is add. {block}"));
+        assert_eq!(display.len(), 5);
+
+        let mut normalizer = TextNormalizer::default();
+        normalizer.config.mode = NormalizationMode::Sentence;
+
+        normalizer.config.code_blocks.code = CodeBlockPolicy::Announce;
+        let plan = normalizer.plan_page(&display);
+        assert_eq!(
+            plan.audio_sentences,
+            vec!["Here is add.", "Code listing, 4 lines."]
+        );
+        assert_eq!(
+            plan.display_to_audio,
+            vec![Some(0), Some(1), None, None, None]
+        );
+
+        normalizer.config.code_blocks.code = CodeBlockPolicy::Comments;
+        let plan = normalizer.plan_page(&display);
+        assert_eq!(
+            plan.audio_sentences,
+            vec!["Here is add.", "Sum two values.", "no overflow check"]
+        );
+        assert_eq!(
+            plan.display_to_audio,
+            vec![Some(0), Some(1), None, Some(2), None]
+        );
+
+        normalizer.config.code_blocks.code = CodeBlockPolicy::Skip;
+        let plan = normalizer.plan_page(&display);
+        assert_eq!(plan.audio_sentences, vec!["Here is add."]);
+
+        normalizer.config.code_blocks.code = CodeBlockPolicy::Verbatim;
+        let plan = normalizer.plan_page(&display);
+        let signature = plan.display_to_audio[2]
+            .map(|idx| plan.audio_sentences[idx].clone())
+            .expect("verbatim code line should be spoken");
+        assert!(signature.starts_with("fn add open paren a colon"));
+        assert!(signature.contains("close paren arrow"));
+        assert!(signature.ends_with("open brace"));
+    }
+
+    #[test]
+    fn footnote_references_are_silent_and_bodies_follow_mode() {
+        let text = format!(
+            "A claim.{} More text.\n\n{}",
+            encode_note_ref("1"),
+            encode_note_body("1", "See the appendix.")
+        );
+        let mut normalizer = TextNormalizer::default();
+        normalizer.config.mode = NormalizationMode::Sentence;
+
+        normalizer.config.footnotes.mode = FootnoteMode::Skip;
+        let display = split_sentences(&layout_footnotes(&text, normalizer.footnote_mode()));
+        let plan = normalizer.plan_page(&display);
+        assert_eq!(plan.audio_sentences, vec!["A claim.", "More text."]);
+        assert_eq!(plan.display_to_audio, vec![Some(0), Some(1), None]);
+
+        normalizer.config.footnotes.mode = FootnoteMode::Inline;
+        let display = split_sentences(&layout_footnotes(&text, normalizer.footnote_mode()));
+        let plan = normalizer.plan_page(&display);
+        assert_eq!(
+            plan.audio_sentences,
+            vec!["A claim.", "Note 1. See the appendix.", "More text."]
+        );
+        assert_eq!(plan.display_to_audio, vec![Some(0), Some(1), Some(2)]);
+        assert_eq!(plan.audio_to_display, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn heading_and_quote_markers_are_not_spoken() {
+        let mut normalizer = TextNormalizer::default();
+        normalizer.config.mode = NormalizationMode::Sentence;
+        let display = vec![
+            "## Chapter Two".to_string(),
+            "\n\n> Quoted words here.".to_string(),
+            "Then a > b holds.".to_string(),
+        ];
+        let audio = normalizer.plan_page(&display).audio_sentences.join(" ");
+        assert!(audio.contains("Chapter Two"));
+        assert!(audio.contains("Quoted words here."));
+        assert!(!audio.contains('#'));
+        assert!(!audio.contains("> Quoted"));
+        assert!(audio.contains("Then a"));
+    }
+
+    #[test]
+    fn math_is_verbalized_or_replaced_by_the_book_fallback() {
+        let mut normalizer = TextNormalizer::default();
+        normalizer.config.mode = NormalizationMode::Sentence;
+        let display = vec![
+            "The ratio $\\frac{a}{b}$ is fixed.".to_string(),
+            "Then \\sqrt{x} grows while $x^2$ does not.".to_string(),
+            "See $\\begin{cases} 1 \\end{cases}$ below.".to_string(),
+            "It costs $5 and $10.".to_string(),
+        ];
+        let plan = normalizer.plan_page(&display);
+        assert_eq!(
+            plan.audio_sentences,
+            vec![
+                "The ratio a over b is fixed.",
+                "Then the square root of x grows while x squared does not.",
+                "See equation omitted below.",
+                "It costs $5 and $10.",
+            ]
+        );
+
+        let overlay = NormalizerOverlay {
+            math_fallback: Some("formula".to_string()),
+            ..NormalizerOverlay::default()
+        };
+        let mut per_book = normalizer.with_overlay(&overlay);
+        assert_ne!(per_book.config_hash(), normalizer.config_hash());
+        assert_eq!(
+            per_book.plan_page(&display[2..3]).audio_sentences,
+            vec!["See formula below."]
+        );
+        per_book.config.math.mode = MathMode::Announce;
+        assert_eq!(
+            per_book.plan_page(&display[..1]).audio_sentences,
+            vec!["The ratio formula is fixed."]
+        );
+    }
+
+    #[test]
+    fn table_rows_become_spoken_sentences_or_are_skipped() {
+        let header = vec!["Country".to_string(), "Population".to_string()];
+        let rows = vec![
+            vec!["Spain".to_string(), "48 million".to_string()],
+            vec!["France".to_string(), "68 million".to_string()],
+        ];
+        let display = split_sentences(&format!("Largest. {}", encode_table(0, &header, &rows)));
+        assert_eq!(display.len(), 4);
+
+        let mut normalizer = TextNormalizer::default();
+        normalizer.config.mode = NormalizationMode::Sentence;
+        let plan = normalizer.plan_page(&display);
+        assert_eq!(
+            plan.audio_sentences,
+            vec![
+                "Largest.",
+                "Table with columns Country, Population.",
+                "Row 1: Country Spain, Population 48 million.",
+                "Row 2: Country France, Population 68 million.",
+            ]
+        );
+
+        normalizer.config.tables.mode = TableMode::Skip;
+        let plan = normalizer.plan_page(&display);
+        assert_eq!(plan.audio_sentences, vec!["Largest."]);
+        assert_eq!(plan.display_to_audio, vec![Some(0), None, None, None]);
+    }
+
+    #[test]
+    fn phoneme_overrides_become_spans_rendered_for_the_worker() {
+        let mut normalizer = TextNormalizer::default();
+        let base_hash = normalizer.config_hash();
+        normalizer.config.pronunciation.phonemes.insert(
+            "Cato".to_string(),
+            PhonemeOverride::Espeak("k'eItoU".to_string()),
+        );
+        normalizer.config.pronunciation.phonemes.insert(
+            "Plato".to_string(),
+            PhonemeOverride::Ipa("ˈpleɪtoʊ".to_string()),
+        );
+        assert_ne!(normalizer.config_hash(), base_hash);
+
+        let plan = normalizer.plan_page(&["Cato met Plato.".to_string()]);
+        assert_eq!(plan.audio_sentences, vec!["Cato met Plato.".to_string()]);
+        assert_eq!(
+            plan.audio_phonemes,
+            vec![vec![
+                PhonemeSpan {
+                    start: 0,
+                    end: 4,
+                    phonemes: "kˈeɪtoʊ".to_string(),
+                },
+                PhonemeSpan {
+                    start: 9,
+                    end: 14,
+                    phonemes: "ˈpleɪtoʊ".to_string(),
+                },
+            ]]
+        );
+        assert_eq!(
+            plan.tts_inputs(),
+            vec![
+                "<phoneme ph=\"kˈeɪtoʊ\">Cato</phoneme> met \
+                 <phoneme ph=\"ˈpleɪtoʊ\">Plato</phoneme>."
+                    .to_string()
+            ]
+        );
+        assert_eq!(
+            strip_speech_markup(&plan.tts_inputs()[0]),
+            plan.audio_sentences[0]
+        );
+
+        let plain = TextNormalizer::default().plan_page(&["Cato met Plato.".to_string()]);
+        assert_eq!(plain.tts_inputs(), plain.audio_sentences);
+    }
+
+    #[test]
+    fn speech_markup_from_replacement_maps_survives_later_stages() {
+        let mut normalizer = TextNormalizer::default();
+        normalizer
+            .config
+            .replacements
+            .insert(" -- ".to_string(), r#" <break time="500ms"/> "#.to_string());
+        normalizer
+            .config
+            .pronunciation
+            .custom_pronunciations
+            .insert("time".to_string(), "tyme".to_string());
+
+        let plan = normalizer.plan_page(&["Wait -- this time.".to_string()]);
+        assert_eq!(
+            plan.audio_sentences,
+            vec![r#"Wait <break time="500ms"/> this tyme."#.to_string()]
+        );
+        assert_eq!(
+            strip_speech_markup(&plan.audio_sentences[0]),
+            "Wait this tyme."
+        );
+    }
+
+    #[test]
+    fn speech_markup_element_text_is_not_normalized() {
+        let plan = TextNormalizer::default()
+            .plan_page(&[r#"Call <say-as interpret-as="digits">2048</say-as> now."#.to_string()]);
+        assert_eq!(plan.audio_sentences.len(), 1);
+        assert!(
+            plan.audio_sentences[0].contains(r#"<say-as interpret-as="digits">2048</say-as>"#),
+            "{}",
+            plan.audio_sentences[0]
+        );
+    }
+
+    #[test]
+    fn try_load_reports_invalid_toml_instead_of_defaults() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time should be after epoch")
+            .as_nanos();
+        let path =
+            std::env::temp_dir().join(format!("lanternleaf-invalid-normalizer-{nonce}.toml"));
+        fs::write(&path, "[normalization\nenabled = ").expect("config should be written");
+
+        let err = TextNormalizer::try_load(&path).expect_err("half-saved config should fail");
+        assert!(err.contains("Invalid normalizer config TOML"), "{err}");
+        assert!(TextNormalizer::load(&path).config.enabled);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn config_file_stamp_changes_when_abbreviations_file_is_edited() {
+        let _guard = env_lock().lock().expect("env lock should not be poisoned");
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time should be after epoch")
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("lanternleaf-stamp-abbrevs-{nonce}.toml"));
+        fs::write(&path, "[abbreviations.nocase]\n")
+            .expect("abbreviations config should be written");
+
+        // SAFETY: test-only, guarded by a process-wide mutex to avoid env var races.
+        unsafe {
+            std::env::set_var(ABBREVIATIONS_CONFIG_ENV, &path);
+        }
+        let before = ConfigFileStamp::capture();
+        fs::write(&path, "[abbreviations.nocase]\n\"pp.\" = \"pages\"\n")
+            .expect("abbreviations config should be rewritten");
+        let after = ConfigFileStamp::capture();
+        // SAFETY: test-only cleanup, guarded by a process-wide mutex to avoid env var races.
+        unsafe {
+            std::env::remove_var(ABBREVIATIONS_CONFIG_ENV);
+        }
+        let _ = fs::remove_file(&path);
+
+        assert!(after.paths().any(|watched| watched == path.as_path()));
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn config_file_stamp_watches_language_abbreviation_files() {
+        let _guard = env_lock().lock().expect("env lock should not be poisoned");
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time should be after epoch")
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("lanternleaf-stamp-lang-{nonce}"));
+        fs::create_dir_all(dir.join("abbreviations")).expect("config dir should be created");
+        let path = dir.join("abbreviations.toml");
+        let spanish = dir.join("abbreviations").join("es.toml");
+        fs::write(&path, "[abbreviations.nocase]\n")
+            .expect("abbreviations config should be written");
+        fs::write(&spanish, "[abbreviations.nocase]\n")
+            .expect("language abbreviations should be written");
+
+        // SAFETY: test-only, guarded by a process-wide mutex to avoid env var races.
+        unsafe {
+            std::env::set_var(ABBREVIATIONS_CONFIG_ENV, &path);
+        }
+        let before = ConfigFileStamp::capture();
+        fs::write(&spanish, "[abbreviations.nocase]\n\"pág.\" = \"página\"\n")
+            .expect("language abbreviations should be rewritten");
+        let after = ConfigFileStamp::capture();
+        // SAFETY: test-only cleanup, guarded by a process-wide mutex to avoid env var races.
+        unsafe {
+            std::env::remove_var(ABBREVIATIONS_CONFIG_ENV);
+        }
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(after.paths().any(|watched| watched == spanish.as_path()));
+        assert_ne!(before, after);
+    }
 }
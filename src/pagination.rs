@@ -4,7 +4,13 @@
 //! chunks based on a stable character budget so page count remains steady
 //! even when font size changes. The logic is isolated so it can be swapped
 //! for a more sophisticated layout later.
-use crate::text_utils::split_sentences;
+use crate::text_utils::{
+    BlockKind, CHAPTER_BREAK, SentenceBlocks, assign_blocks, block_kind,
+    split_sentences_with_language, starts_block, strip_display_markers,
+};
+use serde::Serialize;
+use std::collections::HashSet;
+use ts_rs::TS;
 
 /// Minimum allowed font size (points).
 pub const MIN_FONT_SIZE: u32 = 12;
@@ -17,6 +23,52 @@ pub const MAX_LINES_PER_PAGE: usize = 1000;
 
 /// Split the provided text into page-sized chunks.
 pub fn paginate(text: &str, font_size: u32, lines_per_page: usize) -> Vec<String> {
+    paginate_with_language(text, font_size, lines_per_page, None)
+}
+
+/// [`paginate`] with sentence boundaries for `language` (a BCP 47 tag), so
+/// pages never cut a sentence the reader will later split differently.
+pub fn paginate_with_language(
+    text: &str,
+    font_size: u32,
+    lines_per_page: usize,
+    language: Option<&str>,
+) -> Vec<String> {
+    paginate_structured(text, font_size, lines_per_page, language, false).pages
+}
+
+/// Chapter a page starts in. Chapters begin at each EPUB spine item and at
+/// each top-level heading; IDs count chapters in reading order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct PageChapter {
+    pub id: usize,
+    /// Text of the chapter's first heading, markers stripped.
+    pub title: Option<String>,
+}
+
+/// Pages plus the block and chapter structure of the sentences they hold.
+#[derive(Debug, Clone, Default)]
+pub struct PaginatedText {
+    pub pages: Vec<String>,
+    /// Blocks over all page sentences in reading order, so block IDs stay
+    /// stable across page boundaries.
+    pub blocks: SentenceBlocks,
+    /// Chapter of each page, parallel to `pages`.
+    pub page_chapters: Vec<PageChapter>,
+}
+
+/// [`paginate_with_language`] that also reports block and chapter structure.
+/// Sentences opening a block are joined with a blank line instead of a space
+/// so each page re-splits into the same sentences and blocks. With
+/// `break_at_chapters`, a page never holds sentences from two chapters.
+pub fn paginate_structured(
+    text: &str,
+    font_size: u32,
+    lines_per_page: usize,
+    language: Option<&str>,
+    break_at_chapters: bool,
+) -> PaginatedText {
     let _ = font_size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE); // kept for signature compatibility
     let lines = lines_per_page.clamp(MIN_LINES_PER_PAGE, MAX_LINES_PER_PAGE);
 
@@ -25,52 +77,137 @@ pub fn paginate(text: &str, font_size: u32, lines_per_page: usize) -> Vec<String
     // render time, but pagination is based on a fixed character budget.
     const CHARS_PER_LINE: usize = 80;
     let chars_per_page = CHARS_PER_LINE.saturating_mul(lines).max(1);
-    let sentences = split_sentences(text);
-    if sentences.is_empty() {
-        return vec![String::new()];
+
+    let mut sentences: Vec<String> = Vec::new();
+    let mut spine_starts = HashSet::new();
+    for (segment_idx, segment) in text.split(CHAPTER_BREAK).enumerate() {
+        let first = sentences.len();
+        sentences.extend(
+            split_sentences_with_language(segment, language)
+                .into_iter()
+                .filter(|sentence| !sentence.trim().is_empty()),
+        );
+        if segment_idx > 0 && sentences.len() > first {
+            spine_starts.insert(first);
+        }
     }
+    let (chapters, sentence_chapters) = assign_chapters(&sentences, &spine_starts);
 
     let mut pages = Vec::new();
-    let mut current_sentences: Vec<String> = Vec::new();
+    let mut page_chapters = Vec::new();
+    let mut kept: Vec<String> = Vec::with_capacity(sentences.len());
+    let mut current_page = String::new();
     let mut current_len = 0usize;
 
-    for sentence in sentences {
-        let sentence = sentence.trim();
-        if sentence.is_empty() {
-            continue;
-        }
-        let sentence_len = sentence.chars().count();
-        let separator_len = if current_sentences.is_empty() { 0 } else { 1 }; // " "
+    for (idx, sentence) in sentences.iter().enumerate() {
+        let trimmed = sentence.trim();
+        let chapter = sentence_chapters[idx];
+        let new_chapter = idx > 0 && chapter != sentence_chapters[idx - 1];
+        let opens_block = idx > 0 && (new_chapter || starts_block(sentence));
+        // Chapter starts always open a block, even when the spine item did
+        // not begin with a blank line.
+        kept.push(if opens_block && !starts_block(sentence) {
+            format!("\n\n{trimmed}")
+        } else {
+            sentence.clone()
+        });
+        let sentence_len = trimmed.chars().count();
+        let separator_len = if current_page.is_empty() { 0 } else { 1 }; // " "
         let prospective_len = current_len + separator_len + sentence_len;
 
-        if !current_sentences.is_empty() && prospective_len > chars_per_page {
-            pages.push(current_sentences.join(" "));
-            current_sentences.clear();
+        if !current_page.is_empty()
+            && (prospective_len > chars_per_page || (break_at_chapters && new_chapter))
+        {
+            pages.push(std::mem::take(&mut current_page));
             current_len = 0;
         }
 
-        if !current_sentences.is_empty() {
+        if current_page.is_empty() {
+            page_chapters.push(chapters[chapter].clone());
+        } else {
+            current_page.push_str(if opens_block { "\n\n" } else { " " });
             current_len += 1;
         }
-        current_sentences.push(sentence.to_string());
+        current_page.push_str(trimmed);
         current_len += sentence_len;
     }
 
-    if !current_sentences.is_empty() {
-        pages.push(current_sentences.join(" "));
+    if !current_page.is_empty() {
+        pages.push(current_page);
     }
-
     if pages.is_empty() {
-        vec![String::new()]
-    } else {
-        pages
+        pages.push(String::new());
+        page_chapters.push(PageChapter { id: 0, title: None });
+    }
+
+    PaginatedText {
+        pages,
+        blocks: assign_blocks(&kept),
+        page_chapters,
     }
 }
 
+/// Group sentences into chapters. A spine start or a heading at the book's
+/// top heading level opens a chapter once the current one has body text, so
+/// a spine item that starts with its own heading stays one chapter.
+fn assign_chapters(
+    sentences: &[String],
+    spine_starts: &HashSet<usize>,
+) -> (Vec<PageChapter>, Vec<usize>) {
+    let heading_levels: Vec<Option<u8>> = sentences
+        .iter()
+        .enumerate()
+        .map(|(idx, sentence)| {
+            let block_start = idx == 0 || spine_starts.contains(&idx) || starts_block(sentence);
+            match block_kind(sentence) {
+                BlockKind::Heading { level } if block_start => Some(level),
+                _ => None,
+            }
+        })
+        .collect();
+    let top_level = heading_levels.iter().flatten().min().copied();
+
+    let mut chapters: Vec<PageChapter> = Vec::new();
+    let mut sentence_chapters = Vec::with_capacity(sentences.len());
+    let mut has_body = false;
+    let mut in_title = false;
+    for (idx, sentence) in sentences.iter().enumerate() {
+        let level = heading_levels[idx];
+        let boundary = spine_starts.contains(&idx) || (level.is_some() && level == top_level);
+        if chapters.is_empty() || (boundary && has_body) {
+            chapters.push(PageChapter {
+                id: chapters.len(),
+                title: None,
+            });
+            has_body = false;
+            in_title = false;
+        }
+        let chapter = chapters.last_mut().expect("chapter pushed above");
+        let text = strip_display_markers(sentence.trim()).into_owned();
+        if level.is_some() && chapter.title.is_none() {
+            chapter.title = Some(text.trim_start_matches('#').trim().to_string());
+            in_title = true;
+        } else if in_title && level.is_none() && !starts_block(sentence) {
+            // Headings split into several sentences ("Chapter 1. The Start").
+            if let Some(title) = chapter.title.as_mut() {
+                title.push(' ');
+                title.push_str(&text);
+            }
+        } else {
+            in_title = false;
+            has_body |= level.is_none();
+        }
+        sentence_chapters.push(chapter.id);
+    }
+    (chapters, sentence_chapters)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::text_utils::split_sentences;
+    use crate::text_utils::{
+        BlockKind, CHAPTER_BREAK, assign_blocks, split_sentences, split_sentences_with_language,
+    };
 
     #[test]
     fn pagination_preserves_sentence_text_across_page_sizes() {
@@ -102,4 +239,98 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn multilingual_pagination_preserves_sentence_text() {
+        let paragraph = "¿Vienes mañana? —preguntó ella. ¡Claro! Dijo \"sí.\" \
+                         今日は晴れです。明日は雨ですか？ \
+                         यह एक वाक्य है। यह दूसरा है। \
+                         Wait... then go (quietly.) Done?! Yes. ";
+        let text = paragraph.repeat(30);
+
+        for language in [None, Some("es"), Some("ja"), Some("hi-IN")] {
+            let canonical: Vec<String> = split_sentences_with_language(&text, language)
+                .into_iter()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect();
+            for lines in [8usize, 12, 40] {
+                let pages = paginate_with_language(&text, 16, lines, language);
+                let rebuilt: Vec<String> = pages
+                    .iter()
+                    .flat_map(|p| split_sentences_with_language(p, language))
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect();
+                assert_eq!(
+                    rebuilt, canonical,
+                    "sentence corpus changed for {language:?} at lines_per_page={lines}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn structured_pagination_keeps_block_boundaries_on_every_page() {
+        let chapter = "# Chapter\n\nFirst paragraph sentence that runs on for a while. Second one.\n\n\
+                       - one item\n- two item\n\n> A quote. Still quoting.\n\n";
+        let text = chapter.repeat(30);
+        let canonical = assign_blocks(&split_sentences(&text));
+        assert_eq!(canonical.kinds.len(), 30 * 5);
+        assert_eq!(canonical.kinds[0], BlockKind::Heading { level: 1 });
+        assert_eq!(canonical.kinds[4], BlockKind::BlockQuote);
+
+        for lines in [8usize, 12, 40] {
+            let paginated = paginate_structured(&text, 16, lines, None, false);
+            assert_eq!(paginated.blocks, canonical);
+            let mut offset = 0;
+            for page in &paginated.pages {
+                let local = assign_blocks(&split_sentences(page)).sentence_blocks;
+                let global = &canonical.sentence_blocks[offset..offset + local.len()];
+                for (local_id, global_id) in local.iter().zip(global) {
+                    assert_eq!(
+                        local_id - local[0],
+                        global_id - global[0],
+                        "block boundary moved at lines_per_page={lines}"
+                    );
+                }
+                offset += local.len();
+            }
+            assert_eq!(offset, canonical.sentence_blocks.len());
+        }
+    }
+
+    #[test]
+    fn chapter_pagination_restarts_pages_at_spine_items_and_top_headings() {
+        let body = "A sentence of chapter body text that fills some room. ".repeat(6);
+        let text = format!(
+            "Front matter.\n\n{CHAPTER_BREAK}\n\n# Chapter 1. The Start\n\n{body}\n\n\
+             ## Scene\n\n{body}\n\n# Chapter 2\n\n{body}\n\n{CHAPTER_BREAK}\n\nUntitled {body}"
+        );
+
+        let paged = paginate_structured(&text, 16, 8, None, true);
+        let titles: Vec<(usize, Option<&str>)> = paged
+            .page_chapters
+            .iter()
+            .map(|chapter| (chapter.id, chapter.title.as_deref()))
+            .collect();
+        assert_eq!(titles.first(), Some(&(0, None)));
+        assert!(titles.contains(&(1, Some("Chapter 1. The Start"))));
+        assert!(titles.contains(&(2, Some("Chapter 2"))));
+        assert_eq!(titles.last(), Some(&(3, None)));
+        assert_eq!(paged.pages[0], "Front matter.");
+        for (page, chapter) in paged.pages.iter().zip(&paged.page_chapters) {
+            assert!(!page.contains(CHAPTER_BREAK));
+            let heading = page.find("# Chapter");
+            assert!(
+                heading.is_none_or(|at| at == 0),
+                "chapter {} shares a page with the previous one",
+                chapter.id
+            );
+        }
+
+        let windowed = paginate_structured(&text, 16, 8, None, false);
+        assert!(windowed.pages.len() < paged.pages.len());
+        assert_eq!(windowed.blocks, paged.blocks);
+    }
 }
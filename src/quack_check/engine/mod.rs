@@ -4,7 +4,7 @@ pub mod types;
 use anyhow::Result;
 use std::path::Path;
 
-pub use types::{ConvertIn, ConvertOut, DocDiag, ProbeOut, SplitChunk};
+pub use types::{ConvertIn, ConvertOut, DocDiag, OutlineItem, ProbeOut, SplitChunk};
 
 pub trait Engine {
     fn doctor(&self) -> Result<DocDiag>;
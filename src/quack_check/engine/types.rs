@@ -18,9 +18,22 @@ pub struct ProbeOut {
     pub garbage_ratio: f32,
     pub whitespace_ratio: f32,
     #[serde(default)]
+    pub outline: Vec<OutlineItem>,
+    #[serde(default)]
     pub error: Option<String>,
 }
 
+/// One PDF outline (bookmark) entry in document order; `level` is 0 for
+/// top-level entries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutlineItem {
+    pub title: String,
+    pub level: u32,
+    /// 0-based page index of the destination, when it resolves.
+    #[serde(default)]
+    pub page: Option<u32>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConvertIn {
     pub input_pdf: String,
@@ -12,6 +12,7 @@ pub mod util;
 
 use crate::cancellation::CancellationToken;
 use crate::quack_check::config::Config;
+use crate::quack_check::engine::OutlineItem;
 use crate::quack_check::engine::python::PythonEngine;
 use crate::quack_check::pipeline::Pipeline;
 use crate::quack_check::util::{ensure_dir, hash_file, sha256_hex};
@@ -25,6 +26,8 @@ pub struct RunResult {
     pub job_dir: PathBuf,
     pub markdown: String,
     pub text: String,
+    /// PDF outline from the probe, used for the reader's table of contents.
+    pub outline: Vec<OutlineItem>,
 }
 
 pub fn run_pdf_to_text(config_path: &Path, input: &Path, out_root: &Path) -> Result<RunResult> {
@@ -116,6 +119,7 @@ pub fn run_pdf_to_text_with_cancel(
         job_dir,
         markdown: result.markdown,
         text: result.text,
+        outline: result.outline,
     })
 }
 
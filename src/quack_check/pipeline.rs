@@ -2,7 +2,7 @@ use crate::cancellation::CancellationToken;
 use crate::quack_check::{
     chunk_plan::ChunkPlan,
     config::Config,
-    engine::{ConvertIn, Engine},
+    engine::{ConvertIn, Engine, OutlineItem},
     policy, postprocess, probe,
     report::{ChunkReport, JobReport},
     util::ensure_dir,
@@ -22,6 +22,7 @@ pub struct JobOutput {
     pub markdown: String,
     pub text: String,
     pub report: JobReport,
+    pub outline: Vec<OutlineItem>,
 }
 
 impl<E: Engine> Pipeline<E> {
@@ -214,6 +215,7 @@ impl<E: Engine> Pipeline<E> {
             markdown: merged_md,
             text: merged_txt,
             report,
+            outline: probe_res.outline,
         })
     }
 
@@ -454,6 +456,7 @@ mod tests {
             avg_chars_per_page: 2500,
             garbage_ratio: 0.0,
             whitespace_ratio: 0.15,
+            outline: Vec::new(),
             error: None,
         }
     }
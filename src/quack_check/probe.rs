@@ -1,4 +1,7 @@
-use crate::quack_check::{config::Config, engine::Engine};
+use crate::quack_check::{
+    config::Config,
+    engine::{Engine, OutlineItem},
+};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -7,6 +10,8 @@ use std::path::Path;
 pub struct ProbeResult {
     pub input: ProbeInput,
     pub sample: ProbeSampleStats,
+    #[serde(default)]
+    pub outline: Vec<OutlineItem>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,5 +59,6 @@ pub fn probe_pdf(cfg: &Config, engine: &dyn Engine, input: &Path) -> Result<Prob
             garbage_ratio: probe.garbage_ratio,
             whitespace_ratio: probe.whitespace_ratio,
         },
+        outline: probe.outline,
     })
 }
@@ -0,0 +1,389 @@
+//! SSML-lite markup interpreted by the TTS worker.
+//!
+//! Audio sentences may carry a small tag subset produced by normalizer maps:
+//! `<break>`, `<emphasis>`, `<say-as interpret-as="characters|digits">`,
+//! `<prosody rate>`, `<sub alias>` and `<phoneme ph>`. Parsing never fails:
+//! unknown tags are spoken as text and unbalanced tags are tolerated.
+
+const DEFAULT_BREAK_MS: u32 = 400;
+const MAX_BREAK_MS: u32 = 10_000;
+const MIN_RATE: f32 = 0.5;
+const MAX_RATE: f32 = 2.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpeechContent {
+    Text(String),
+    Phonemes(String),
+    Silence { millis: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechSegment {
+    pub content: SpeechContent,
+    /// Speaking-rate multiplier; `1.0` is the voice's normal speed.
+    pub rate: f32,
+    /// Linear amplitude multiplier applied to synthesized samples.
+    pub gain: f32,
+}
+
+impl SpeechSegment {
+    fn is_neutral(&self) -> bool {
+        (self.rate - 1.0).abs() < f32::EPSILON && (self.gain - 1.0).abs() < f32::EPSILON
+    }
+}
+
+/// True when the segments can be synthesized as one plain text request.
+pub fn is_plain_text(segments: &[SpeechSegment]) -> bool {
+    segments
+        .iter()
+        .all(|segment| matches!(segment.content, SpeechContent::Text(_)) && segment.is_neutral())
+}
+
+pub fn plain_text(segments: &[SpeechSegment]) -> String {
+    segments
+        .iter()
+        .filter_map(|segment| match &segment.content {
+            SpeechContent::Text(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SpellMode {
+    None,
+    Characters,
+    Digits,
+}
+
+#[derive(Debug, Clone)]
+struct Frame {
+    name: String,
+    rate: f32,
+    gain: f32,
+    spell: SpellMode,
+    /// `<sub>` and `<phoneme>` replace their written content.
+    skip_content: bool,
+}
+
+struct Tag<'a> {
+    name: &'a str,
+    closing: bool,
+    self_closing: bool,
+    attrs: Vec<(&'a str, &'a str)>,
+}
+
+impl Tag<'_> {
+    fn attr(&self, key: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            .map(|(_, value)| *value)
+    }
+}
+
+pub fn parse(input: &str) -> Vec<SpeechSegment> {
+    let mut segments: Vec<SpeechSegment> = Vec::new();
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        let Some(lt) = rest.find('<') else {
+            push_text(&mut segments, &stack, rest);
+            break;
+        };
+        push_text(&mut segments, &stack, &rest[..lt]);
+        let candidate = &rest[lt..];
+        let Some((tag, consumed)) = parse_tag(candidate) else {
+            push_text(&mut segments, &stack, "<");
+            rest = &candidate[1..];
+            continue;
+        };
+        rest = &candidate[consumed..];
+
+        if tag.closing {
+            if let Some(pos) = stack.iter().rposition(|frame| frame.name == tag.name) {
+                stack.truncate(pos);
+            }
+            continue;
+        }
+
+        let (rate, gain, spell, skip) = current_style(&stack);
+        match tag.name {
+            "break" => {
+                let millis = break_millis(&tag);
+                if millis > 0 {
+                    segments.push(SpeechSegment {
+                        content: SpeechContent::Silence { millis },
+                        rate: 1.0,
+                        gain: 1.0,
+                    });
+                }
+                continue;
+            }
+            "phoneme" if !skip => {
+                if let Some(ph) = tag.attr("ph").filter(|ph| !ph.trim().is_empty()) {
+                    segments.push(SpeechSegment {
+                        content: SpeechContent::Phonemes(ph.trim().to_string()),
+                        rate,
+                        gain,
+                    });
+                }
+            }
+            "sub" if !skip => {
+                if let Some(alias) = tag.attr("alias") {
+                    push_text(&mut segments, &stack, alias);
+                }
+            }
+            _ => {}
+        }
+        if tag.self_closing {
+            continue;
+        }
+
+        let mut frame = Frame {
+            name: tag.name.to_string(),
+            rate,
+            gain,
+            spell,
+            skip_content: skip,
+        };
+        match tag.name {
+            "emphasis" => {
+                let (rate_scale, gain_scale) = match tag.attr("level").unwrap_or("moderate") {
+                    "strong" => (0.8, 1.3),
+                    "reduced" => (1.05, 0.85),
+                    "none" => (1.0, 1.0),
+                    _ => (0.9, 1.15),
+                };
+                frame.rate = (rate * rate_scale).clamp(MIN_RATE, MAX_RATE);
+                frame.gain = gain * gain_scale;
+            }
+            "prosody" => {
+                if let Some(scale) = tag.attr("rate").and_then(parse_rate) {
+                    frame.rate = (rate * scale).clamp(MIN_RATE, MAX_RATE);
+                }
+            }
+            "say-as" => {
+                frame.spell = match tag.attr("interpret-as").unwrap_or_default() {
+                    "characters" | "spell-out" => SpellMode::Characters,
+                    "digits" => SpellMode::Digits,
+                    _ => spell,
+                };
+            }
+            "sub" | "phoneme" => frame.skip_content = true,
+            _ => {}
+        }
+        stack.push(frame);
+    }
+
+    segments
+}
+
+fn current_style(stack: &[Frame]) -> (f32, f32, SpellMode, bool) {
+    stack
+        .last()
+        .map(|frame| (frame.rate, frame.gain, frame.spell, frame.skip_content))
+        .unwrap_or((1.0, 1.0, SpellMode::None, false))
+}
+
+fn push_text(segments: &mut Vec<SpeechSegment>, stack: &[Frame], text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    let (rate, gain, spell, skip) = current_style(stack);
+    if skip {
+        return;
+    }
+    let text = match spell {
+        SpellMode::None => text.to_string(),
+        SpellMode::Characters => spell_out(text, |ch| ch.is_alphanumeric()),
+        SpellMode::Digits => spell_out(text, |ch| ch.is_ascii_digit()),
+    };
+
+    if let Some(last) = segments.last_mut()
+        && let SpeechContent::Text(existing) = &mut last.content
+        && last.rate == rate
+        && last.gain == gain
+    {
+        existing.push_str(&text);
+        return;
+    }
+    segments.push(SpeechSegment {
+        content: SpeechContent::Text(text),
+        rate,
+        gain,
+    });
+}
+
+/// Separate each selected character with spaces so the voice reads them one
+/// by one ("SQL" -> "S Q L", "2048" -> "2 0 4 8").
+fn spell_out(text: &str, selected: impl Fn(char) -> bool) -> String {
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut prev_selected = false;
+    for ch in text.chars() {
+        let is_selected = selected(ch);
+        if is_selected && prev_selected {
+            out.push(' ');
+        }
+        out.push(ch);
+        prev_selected = is_selected;
+    }
+    out
+}
+
+fn parse_tag(input: &str) -> Option<(Tag<'_>, usize)> {
+    let end = input.find('>')?;
+    let inner = input[1..end].trim();
+    let (closing, inner) = match inner.strip_prefix('/') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, inner),
+    };
+    let (self_closing, inner) = match inner.strip_suffix('/') {
+        Some(rest) => (true, rest.trim_end()),
+        None => (false, inner),
+    };
+
+    let name_end = inner
+        .find(|ch: char| ch.is_whitespace())
+        .unwrap_or(inner.len());
+    let name = &inner[..name_end];
+    if !matches!(
+        name,
+        "break" | "emphasis" | "say-as" | "prosody" | "sub" | "phoneme"
+    ) {
+        return None;
+    }
+
+    let mut attrs = Vec::new();
+    let mut rest = inner[name_end..].trim_start();
+    while !rest.is_empty() {
+        let eq = rest.find('=')?;
+        let key = rest[..eq].trim();
+        let after_eq = rest[eq + 1..].trim_start();
+        let value_body = after_eq.strip_prefix('"')?;
+        let close = value_body.find('"')?;
+        attrs.push((key, &value_body[..close]));
+        rest = value_body[close + 1..].trim_start();
+    }
+
+    Some((
+        Tag {
+            name,
+            closing,
+            self_closing,
+            attrs,
+        },
+        end + 1,
+    ))
+}
+
+fn break_millis(tag: &Tag<'_>) -> u32 {
+    if let Some(time) = tag.attr("time") {
+        let time = time.trim();
+        let millis = if let Some(ms) = time.strip_suffix("ms") {
+            ms.trim().parse::<f32>().ok()
+        } else if let Some(secs) = time.strip_suffix('s') {
+            secs.trim().parse::<f32>().ok().map(|secs| secs * 1000.0)
+        } else {
+            time.parse::<f32>().ok()
+        };
+        if let Some(millis) = millis {
+            return millis.clamp(0.0, MAX_BREAK_MS as f32) as u32;
+        }
+    }
+    match tag.attr("strength").unwrap_or("medium") {
+        "none" => 0,
+        "x-weak" => 100,
+        "weak" => 200,
+        "strong" => 700,
+        "x-strong" => 1000,
+        _ => DEFAULT_BREAK_MS,
+    }
+}
+
+fn parse_rate(value: &str) -> Option<f32> {
+    let value = value.trim();
+    let scale = match value {
+        "x-slow" => 0.6,
+        "slow" => 0.8,
+        "medium" | "default" => 1.0,
+        "fast" => 1.25,
+        "x-fast" => 1.5,
+        _ => match value.strip_suffix('%') {
+            Some(percent) => percent.trim().parse::<f32>().ok()? / 100.0,
+            None => value.parse::<f32>().ok()?,
+        },
+    };
+    (scale > 0.0).then_some(scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(value: &str, rate: f32, gain: f32) -> SpeechSegment {
+        SpeechSegment {
+            content: SpeechContent::Text(value.to_string()),
+            rate,
+            gain,
+        }
+    }
+
+    #[test]
+    fn plain_sentences_stay_a_single_text_segment() {
+        let segments = parse("Plain text, x < y and no tags.");
+        assert!(is_plain_text(&segments));
+        assert_eq!(plain_text(&segments), "Plain text, x < y and no tags.");
+    }
+
+    #[test]
+    fn say_as_and_sub_rewrite_text_without_changing_style() {
+        let segments = parse(
+            r#"Use <say-as interpret-as="characters">SQL</say-as> on <sub alias="World Wide Web">WWW</sub>, pin <say-as interpret-as="digits">2048</say-as>."#,
+        );
+        assert!(is_plain_text(&segments));
+        assert_eq!(
+            plain_text(&segments),
+            "Use S Q L on World Wide Web, pin 2 0 4 8."
+        );
+    }
+
+    #[test]
+    fn breaks_prosody_emphasis_and_phonemes_split_segments() {
+        let segments = parse(
+            r#"Wait<break time="1.5s"/> <prosody rate="150%">quickly</prosody> <emphasis level="strong">now</emphasis> <phoneme ph="kˈeɪtoʊ">Cato</phoneme>"#,
+        );
+        assert_eq!(
+            segments,
+            vec![
+                text("Wait", 1.0, 1.0),
+                SpeechSegment {
+                    content: SpeechContent::Silence { millis: 1500 },
+                    rate: 1.0,
+                    gain: 1.0,
+                },
+                text(" ", 1.0, 1.0),
+                text("quickly", 1.5, 1.0),
+                text(" ", 1.0, 1.0),
+                text("now", 0.8, 1.3),
+                text(" ", 1.0, 1.0),
+                SpeechSegment {
+                    content: SpeechContent::Phonemes("kˈeɪtoʊ".to_string()),
+                    rate: 1.0,
+                    gain: 1.0,
+                },
+            ]
+        );
+        assert!(!is_plain_text(&segments));
+    }
+
+    #[test]
+    fn unbalanced_tags_are_tolerated() {
+        let segments = parse(r#"</emphasis>Start <prosody rate="slow">slow to the end"#);
+        assert_eq!(
+            segments,
+            vec![text("Start ", 1.0, 1.0), text("slow to the end", 0.8, 1.0)]
+        );
+    }
+}
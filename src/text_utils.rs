@@ -2,17 +2,725 @@
 
 use once_cell::sync::Lazy;
 use regex::Regex;
-use serde::Deserialize;
-use std::collections::{BTreeMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use ts_rs::TS;
 
 const MAX_DISPLAY_SENTENCE_CHARS: usize = 220;
+/// How far from a stored index sentence re-anchoring looks for stored text.
+const SENTENCE_ANCHOR_SEARCH_RADIUS: usize = 400;
+/// Lowest fuzzy anchor score accepted as the same sentence.
+const FUZZY_ANCHOR_MIN_SCORE: f64 = 0.6;
+/// Share of a fuzzy anchor score taken from the neighbouring sentences.
+const FUZZY_ANCHOR_CONTEXT_WEIGHT: f64 = 0.2;
+/// Score lost by a candidate at the edge of the search radius, so the closer
+/// of two equally good matches wins.
+const FUZZY_ANCHOR_DISTANCE_PENALTY: f64 = 0.05;
 const MAX_DISPLAY_SENTENCE_WORDS: usize = 36;
 
-/// Very lightweight sentence splitter based on punctuation.
+/// Loaders wrap every line of a code/preformatted block in private-use
+/// sentinels so pagination and sentence splitting keep it intact:
+/// `\u{E010}<kind>[<block line count>]\u{E012}<line>\u{E011}`, where kind is
+/// `c` (code) or `p` (preformatted) and only a block's first line carries the
+/// count. Display text strips the sentinels; the normalizer reads them to pick
+/// a per-block audio policy.
+pub const PREFORMATTED_LINE_START: char = '\u{E010}';
+pub const PREFORMATTED_LINE_HEADER_END: char = '\u{E012}';
+pub const PREFORMATTED_LINE_END: char = '\u{E011}';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreformattedKind {
+    Code,
+    Preformatted,
+}
+
+impl PreformattedKind {
+    fn tag(self) -> char {
+        match self {
+            Self::Code => 'c',
+            Self::Preformatted => 'p',
+        }
+    }
+
+    fn from_tag(tag: char) -> Option<Self> {
+        match tag {
+            'c' => Some(Self::Code),
+            'p' => Some(Self::Preformatted),
+            _ => None,
+        }
+    }
+}
+
+/// One sentinel-wrapped line as produced by [`encode_preformatted_block`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreformattedLine<'a> {
+    pub kind: PreformattedKind,
+    /// Total lines in the block; only present on the block's first line.
+    pub block_lines: Option<usize>,
+    pub text: &'a str,
+}
+
+/// Encode a code/preformatted block as sentinel-wrapped lines separated by
+/// newlines. Blank lines count toward the block length but are not emitted.
+pub fn encode_preformatted_block(kind: PreformattedKind, body: &str) -> String {
+    let lines: Vec<&str> = body.lines().map(str::trim_end).collect();
+    let Some(first) = lines.iter().position(|line| !line.trim().is_empty()) else {
+        return String::new();
+    };
+    let last = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .unwrap_or(first);
+    let lines = &lines[first..=last];
+
+    let mut out = String::new();
+    for (idx, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push(PREFORMATTED_LINE_START);
+        out.push(kind.tag());
+        if idx == 0 {
+            out.push_str(&lines.len().to_string());
+        }
+        out.push(PREFORMATTED_LINE_HEADER_END);
+        out.extend(line.chars().filter(|ch| !is_layout_sentinel(*ch)));
+        out.push(PREFORMATTED_LINE_END);
+    }
+    out
+}
+
+/// Parse a display sentence that is a single sentinel-wrapped line.
+pub fn parse_preformatted_line(sentence: &str) -> Option<PreformattedLine<'_>> {
+    let rest = sentence.trim().strip_prefix(PREFORMATTED_LINE_START)?;
+    let mut chars = rest.chars();
+    let kind = PreformattedKind::from_tag(chars.next()?)?;
+    let rest = chars.as_str();
+    let (count, rest) = rest.split_once(PREFORMATTED_LINE_HEADER_END)?;
+    let text = rest.strip_suffix(PREFORMATTED_LINE_END).unwrap_or(rest);
+    Some(PreformattedLine {
+        kind,
+        block_lines: count.parse().ok(),
+        text,
+    })
+}
+
+/// Footnote references and bodies use their own sentinels. A reference sits
+/// where the note marker was: `\u{E014}<label>\u{E015}`. A body is an atomic
+/// display sentence `\u{E016}<label>\u{E012}<text>\u{E017}`; loaders put
+/// bodies at the end of the chapter that references them and
+/// [`layout_footnotes`] moves them for the other modes.
+pub const NOTE_REF_START: char = '\u{E014}';
+pub const NOTE_REF_END: char = '\u{E015}';
+pub const NOTE_BODY_START: char = '\u{E016}';
+pub const NOTE_BODY_END: char = '\u{E017}';
+
+/// Where footnote bodies are read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FootnoteMode {
+    /// Bodies stay at the chapter end and are never spoken.
+    #[default]
+    Skip,
+    /// Right after the sentence holding the reference.
+    Inline,
+    /// After the paragraph holding the reference.
+    EndOfParagraph,
+    /// Collected at the end of the chapter.
+    Chapter,
+}
+
+/// A footnote body display sentence as produced by [`encode_note_body`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteBody<'a> {
+    pub label: &'a str,
+    pub text: &'a str,
+}
+
+pub fn encode_note_ref(label: &str) -> String {
+    let label: String = label
+        .chars()
+        .filter(|ch| !is_layout_sentinel(*ch))
+        .collect();
+    format!("{NOTE_REF_START}{}{NOTE_REF_END}", label.trim())
+}
+
+/// Encode a note body on one line; returns an empty string for empty notes.
+pub fn encode_note_body(label: &str, body: &str) -> String {
+    let body = body
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace(|ch: char| is_layout_sentinel(ch), "");
+    if body.is_empty() {
+        return String::new();
+    }
+    let label: String = label
+        .chars()
+        .filter(|ch| !is_layout_sentinel(*ch))
+        .collect();
+    format!(
+        "{NOTE_BODY_START}{}{PREFORMATTED_LINE_HEADER_END}{body}{NOTE_BODY_END}",
+        label.trim()
+    )
+}
+
+pub fn parse_note_body(sentence: &str) -> Option<NoteBody<'_>> {
+    let rest = sentence.trim().strip_prefix(NOTE_BODY_START)?;
+    let (label, rest) = rest.split_once(PREFORMATTED_LINE_HEADER_END)?;
+    let text = rest.strip_suffix(NOTE_BODY_END).unwrap_or(rest);
+    Some(NoteBody { label, text })
+}
+
+fn note_ref_labels(text: &str) -> Vec<&str> {
+    let mut labels = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(NOTE_REF_START) {
+        let after = &rest[start + NOTE_REF_START.len_utf8()..];
+        let Some(end) = after.find(NOTE_REF_END) else {
+            break;
+        };
+        labels.push(&after[..end]);
+        rest = &after[end + NOTE_REF_END.len_utf8()..];
+    }
+    labels
+}
+
+/// Move footnote bodies next to their references for `inline` and
+/// `end-of-paragraph` modes. Unreferenced bodies stay at the end. Other modes
+/// keep the loader placement (chapter end).
+pub fn layout_footnotes(text: &str, mode: FootnoteMode) -> Cow<'_, str> {
+    if !matches!(mode, FootnoteMode::Inline | FootnoteMode::EndOfParagraph)
+        || !text.contains(NOTE_BODY_START)
+    {
+        return Cow::Borrowed(text);
+    }
+
+    let mut bodies: Vec<(String, String)> = Vec::new();
+    let mut stripped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(NOTE_BODY_START) {
+        stripped.push_str(&rest[..start]);
+        let after = &rest[start..];
+        let end = after
+            .find(NOTE_BODY_END)
+            .map(|end| end + NOTE_BODY_END.len_utf8())
+            .unwrap_or(after.len());
+        let span = &after[..end];
+        if let Some(body) = parse_note_body(span) {
+            bodies.push((body.label.to_string(), span.to_string()));
+        }
+        rest = &after[end..];
+    }
+    stripped.push_str(rest);
+
+    let mut out = String::with_capacity(text.len());
+    let mut pending: Vec<String> = Vec::new();
+    for sentence in split_sentences(&stripped) {
+        let leading = &sentence[..sentence.len() - sentence.trim_start().len()];
+        if leading.contains("\n\n") && !pending.is_empty() {
+            for body in pending.drain(..) {
+                push_spaced(&mut out, &body);
+            }
+        }
+        push_spaced(&mut out, &sentence);
+        for label in note_ref_labels(&sentence) {
+            let Some(pos) = bodies.iter().position(|(candidate, _)| candidate == label) else {
+                continue;
+            };
+            let (_, body) = bodies.remove(pos);
+            if mode == FootnoteMode::Inline {
+                push_spaced(&mut out, &body);
+            } else {
+                pending.push(body);
+            }
+        }
+    }
+    for body in pending
+        .into_iter()
+        .chain(bodies.into_iter().map(|(_, body)| body))
+    {
+        push_spaced(&mut out, &body);
+    }
+    Cow::Owned(out)
+}
+
+fn push_spaced(out: &mut String, piece: &str) {
+    let needs_space = out
+        .chars()
+        .next_back()
+        .is_some_and(|ch| !ch.is_whitespace())
+        && piece.chars().next().is_some_and(|ch| !ch.is_whitespace());
+    if needs_space {
+        out.push(' ');
+    }
+    out.push_str(piece);
+}
+
+/// Table rows are atomic display sentences too:
+/// `\u{E018}<table>:<tr>:<number|h>[\u{E01B}<column>\u{E01A}...]\u{E012}<cell>\u{E01A}<cell>...\u{E019}`.
+/// `table` counts tables in document order, `tr` is the row position inside
+/// the table (header row included) and body rows carry a 1-based `number`
+/// plus the column names so each row can be spoken on its own.
+pub const TABLE_ROW_START: char = '\u{E018}';
+pub const TABLE_ROW_END: char = '\u{E019}';
+const TABLE_CELL_SEPARATOR: char = '\u{E01A}';
+const TABLE_COLUMNS_START: char = '\u{E01B}';
+
+/// One table row display sentence as produced by [`encode_table`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableRow<'a> {
+    pub table: usize,
+    pub row: usize,
+    /// 1-based body row number; `None` for the header row.
+    pub number: Option<usize>,
+    pub columns: Vec<&'a str>,
+    pub cells: Vec<&'a str>,
+}
+
+/// Encode a table as one sentinel-wrapped line per row. An empty `header`
+/// means the table has no header row.
+pub fn encode_table(table_idx: usize, header: &[String], rows: &[Vec<String>]) -> String {
+    let clean = |cells: &[String]| -> String {
+        cells
+            .iter()
+            .map(|cell| {
+                cell.split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .replace(|ch: char| is_layout_sentinel(ch), "")
+            })
+            .collect::<Vec<_>>()
+            .join(&TABLE_CELL_SEPARATOR.to_string())
+    };
+    let has_header = header.iter().any(|cell| !cell.trim().is_empty());
+
+    let mut lines = Vec::with_capacity(rows.len() + 1);
+    if has_header {
+        lines.push(format!(
+            "{TABLE_ROW_START}{table_idx}:0:h{PREFORMATTED_LINE_HEADER_END}{}{TABLE_ROW_END}",
+            clean(header)
+        ));
+    }
+    let columns = if has_header {
+        format!("{TABLE_COLUMNS_START}{}", clean(header))
+    } else {
+        String::new()
+    };
+    let first_tr = usize::from(has_header);
+    for (idx, row) in rows.iter().enumerate() {
+        if row.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        lines.push(format!(
+            "{TABLE_ROW_START}{table_idx}:{}:{}{columns}{PREFORMATTED_LINE_HEADER_END}{}{TABLE_ROW_END}",
+            first_tr + idx,
+            idx + 1,
+            clean(row)
+        ));
+    }
+    lines.join("\n")
+}
+
+pub fn parse_table_row<'a>(sentence: &'a str) -> Option<TableRow<'a>> {
+    let rest = sentence.trim().strip_prefix(TABLE_ROW_START)?;
+    let (meta, rest) = rest.split_once(PREFORMATTED_LINE_HEADER_END)?;
+    let body = rest.strip_suffix(TABLE_ROW_END).unwrap_or(rest);
+    let (ids, columns) = meta.split_once(TABLE_COLUMNS_START).unwrap_or((meta, ""));
+    let mut ids = ids.split(':');
+    let table = ids.next()?.parse().ok()?;
+    let row = ids.next()?.parse().ok()?;
+    let number = match ids.next()? {
+        "h" => None,
+        value => Some(value.parse().ok()?),
+    };
+    let split = |value: &'a str| -> Vec<&'a str> {
+        if value.is_empty() {
+            Vec::new()
+        } else {
+            value.split(TABLE_CELL_SEPARATOR).collect()
+        }
+    };
+    Some(TableRow {
+        table,
+        row,
+        number,
+        columns: split(columns),
+        cells: body.split(TABLE_CELL_SEPARATOR).collect(),
+    })
+}
+
+/// True for a markdown pipe-table delimiter row such as `|---|:--:|`.
+pub fn is_markdown_table_delimiter(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.contains('|')
+        && trimmed.trim_matches('|').split('|').all(|cell| {
+            let dashes = cell.trim().trim_matches(':');
+            !dashes.is_empty() && dashes.chars().all(|ch| ch == '-')
+        })
+}
+
+/// Replace layout sentinels with reader-facing text: preformatted lines lose
+/// their headers, note references become `[label]`, note bodies start with
+/// `[label] ` and table cells are separated by ` | `.
+pub fn strip_display_markers(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_layout_sentinel) {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut in_header = false;
+    let mut in_note_label = false;
+    for ch in text.chars() {
+        match ch {
+            PREFORMATTED_LINE_START | TABLE_ROW_START => in_header = true,
+            PREFORMATTED_LINE_HEADER_END if in_note_label => {
+                in_note_label = false;
+                out.push_str("] ");
+            }
+            PREFORMATTED_LINE_HEADER_END => in_header = false,
+            NOTE_BODY_START => {
+                in_note_label = true;
+                out.push('[');
+            }
+            NOTE_REF_START => out.push('['),
+            NOTE_REF_END => out.push(']'),
+            _ if in_header => {}
+            TABLE_CELL_SEPARATOR => out.push_str(" | "),
+            PREFORMATTED_LINE_END
+            | NOTE_BODY_END
+            | TABLE_ROW_END
+            | TABLE_COLUMNS_START
+            | CHAPTER_BREAK => {}
+            _ => out.push(ch),
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Decide whether a `<pre>`/fenced block is source code. An explicit info
+/// string or class wins (`text`/`plain`/`poem` mean preformatted prose);
+/// otherwise blocks where at least half the lines carry code punctuation are
+/// treated as code.
+pub fn classify_preformatted_block(info: &str, body: &str) -> PreformattedKind {
+    let info = info
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches("language-")
+        .to_ascii_lowercase();
+    if matches!(
+        info.as_str(),
+        "text" | "txt" | "plain" | "plaintext" | "poem" | "verse"
+    ) {
+        return PreformattedKind::Preformatted;
+    }
+    if !info.is_empty() {
+        return PreformattedKind::Code;
+    }
+
+    let lines: Vec<&str> = body
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    let code_lines = lines
+        .iter()
+        .filter(|line| line.contains(['{', '}', ';', '=', '(', ')', '<', '>', '[', ']']))
+        .count();
+    if !lines.is_empty() && code_lines * 2 >= lines.len() {
+        PreformattedKind::Code
+    } else {
+        PreformattedKind::Preformatted
+    }
+}
+
+fn is_layout_sentinel(ch: char) -> bool {
+    matches!(
+        ch,
+        PREFORMATTED_LINE_START
+            | PREFORMATTED_LINE_HEADER_END
+            | PREFORMATTED_LINE_END
+            | NOTE_REF_START
+            | NOTE_REF_END
+            | NOTE_BODY_START
+            | NOTE_BODY_END
+            | TABLE_ROW_START
+            | TABLE_ROW_END
+            | TABLE_CELL_SEPARATOR
+            | TABLE_COLUMNS_START
+            | CHAPTER_BREAK
+    )
+}
+
+/// Closing sentinel for spans the splitter keeps as one display sentence.
+fn atomic_span_end(ch: char) -> Option<char> {
+    match ch {
+        PREFORMATTED_LINE_START => Some(PREFORMATTED_LINE_END),
+        NOTE_BODY_START => Some(NOTE_BODY_END),
+        TABLE_ROW_START => Some(TABLE_ROW_END),
+        _ => None,
+    }
+}
+
+/// Sentence terminators across scripts: Latin `.!?`, CJK full stops and
+/// marks, Devanagari danda, Arabic/Urdu and Ethiopic stops.
+fn is_sentence_terminal(ch: char) -> bool {
+    matches!(
+        ch,
+        '.' | '!'
+            | '?'
+            | '\u{3002}'
+            | '\u{FF01}'
+            | '\u{FF1F}'
+            | '\u{FF0E}'
+            | '\u{FF61}'
+            | '\u{0964}'
+            | '\u{0965}'
+            | '\u{061F}'
+            | '\u{06D4}'
+            | '\u{1362}'
+            | '\u{1367}'
+            | '\u{203C}'
+            | '\u{2047}'
+            | '\u{2048}'
+            | '\u{2049}'
+    )
+}
+
+/// Closing quotes and brackets that belong to the sentence they follow.
+fn is_sentence_closer(ch: char) -> bool {
+    matches!(
+        ch,
+        '"' | '\''
+            | ')'
+            | ']'
+            | '}'
+            | '\u{201D}'
+            | '\u{2019}'
+            | '\u{00BB}'
+            | '\u{203A}'
+            | '\u{300D}'
+            | '\u{300F}'
+            | '\u{3011}'
+            | '\u{3015}'
+            | '\u{300B}'
+            | '\u{3009}'
+            | '\u{FF09}'
+            | '\u{FF3D}'
+    )
+}
+
+/// Punctuation that may open a sentence or clause before its first letter,
+/// including Spanish `¿`/`¡` and dialogue dashes.
+fn is_sentence_opener(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{00BF}'
+            | '\u{00A1}'
+            | '"'
+            | '\''
+            | '('
+            | '['
+            | '\u{201C}'
+            | '\u{2018}'
+            | '\u{00AB}'
+            | '\u{2039}'
+            | '\u{300C}'
+            | '\u{300E}'
+            | '\u{FF08}'
+            | '\u{2014}'
+            | '\u{2013}'
+    )
+}
+
+/// Loaders put this sentinel on its own line where an EPUB spine item starts.
+/// Pagination turns it into a chapter boundary and drops it.
+pub const CHAPTER_BREAK: char = '\u{E01C}';
+
+/// Structural kind of a block of display sentences. Loaders keep block
+/// boundaries as blank lines (or a line break before a marker line) and mark
+/// headings with `#` and quotes with `>`; list items keep their bullet or
+/// number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(export)]
+pub enum BlockKind {
+    Paragraph,
+    Heading { level: u8 },
+    ListItem { ordered: bool },
+    BlockQuote,
+}
+
+/// Block structure of a run of sentences, as built by [`assign_blocks`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SentenceBlocks {
+    /// Kind of each block, indexed by block ID.
+    pub kinds: Vec<BlockKind>,
+    /// Block ID of each sentence.
+    pub sentence_blocks: Vec<usize>,
+}
+
+/// Whether a sentence as returned by the splitters (leading whitespace
+/// included) opens a new block.
+pub fn starts_block(sentence: &str) -> bool {
+    let body = sentence.trim_start();
+    let leading = &sentence[..sentence.len() - body.len()];
+    leading.matches('\n').count() >= 2
+        || (leading.contains('\n') && block_marker_kind(body).is_some())
+}
+
+/// Kind of the block a sentence opens, from its leading marker.
+pub fn block_kind(sentence: &str) -> BlockKind {
+    block_marker_kind(sentence.trim_start()).unwrap_or(BlockKind::Paragraph)
+}
+
+/// Group `sentences` into blocks. The first sentence always opens one.
+pub fn assign_blocks<S: AsRef<str>>(sentences: &[S]) -> SentenceBlocks {
+    let mut blocks = SentenceBlocks::default();
+    for sentence in sentences {
+        let sentence = sentence.as_ref();
+        if blocks.kinds.is_empty() || starts_block(sentence) {
+            blocks.kinds.push(block_kind(sentence));
+        }
+        blocks.sentence_blocks.push(blocks.kinds.len() - 1);
+    }
+    blocks
+}
+
+/// Block kind announced by a line's leading `#`, `>`, bullet or list number.
+pub fn block_marker_kind(line: &str) -> Option<BlockKind> {
+    let hashes = line.chars().take_while(|ch| *ch == '#').count();
+    if (1..=6).contains(&hashes) && line[hashes..].starts_with([' ', '\t']) {
+        return Some(BlockKind::Heading {
+            level: hashes as u8,
+        });
+    }
+    if line.starts_with('>') {
+        return Some(BlockKind::BlockQuote);
+    }
+    if ["- ", "* ", "+ ", "\u{2022} "]
+        .iter()
+        .any(|bullet| line.starts_with(bullet))
+    {
+        return Some(BlockKind::ListItem { ordered: false });
+    }
+    let digits = line.chars().take_while(|ch| ch.is_ascii_digit()).count();
+    let rest = &line[digits..];
+    if (1..=3).contains(&digits) && (rest.starts_with(". ") || rest.starts_with(") ")) {
+        return Some(BlockKind::ListItem { ordered: true });
+    }
+    None
+}
+
+/// `current` is an ordered-list number ("2.") at the start of a line.
+fn is_list_number(current: &str, at_text_start: bool) -> bool {
+    let body = current.trim_start();
+    let leading = &current[..current.len() - body.len()];
+    let digits = body.strip_suffix('.').unwrap_or(body);
+    (1..=3).contains(&digits.len())
+        && digits.len() < body.len()
+        && digits.chars().all(|ch| ch.is_ascii_digit())
+        && (leading.contains('\n') || (at_text_start && leading.is_empty()))
+}
+
+/// A line break ends the current sentence when a blank line or a block
+/// marker line follows it.
+fn breaks_block_after_newline(chars: &[char], from: usize) -> bool {
+    let mut idx = from;
+    while idx < chars.len() && matches!(chars[idx], ' ' | '\t') {
+        idx += 1;
+    }
+    if chars.get(idx).is_none_or(|ch| *ch == '\n' || *ch == '\r') {
+        return true;
+    }
+    let line: String = chars[idx..]
+        .iter()
+        .take_while(|ch| **ch != '\n')
+        .take(12)
+        .collect();
+    block_marker_kind(&line).is_some()
+}
+
+/// Very lightweight sentence splitter based on punctuation, using the English
+/// abbreviation list.
 pub fn split_sentences(text: &str) -> Vec<String> {
-    split_sentences_with_abbreviations(text, &ABBREVIATION_TOKENS)
+    split_sentences_with_abbreviations(text, &current_abbreviation_tokens())
+}
+
+/// Split with the abbreviation list for `language`, a BCP 47 tag such as
+/// `es-MX` from EPUB metadata or a browser tab's `lang`. Languages without a
+/// `conf/abbreviations/<subtag>.toml` file use the English list.
+pub fn split_sentences_with_language(text: &str, language: Option<&str>) -> Vec<String> {
+    match language.and_then(language_subtag) {
+        Some(subtag) if subtag != "en" => {
+            split_sentences_with_abbreviations(text, &language_abbreviation_tokens(&subtag))
+        }
+        _ => split_sentences(text),
+    }
+}
+
+/// Lowercased primary subtag of a language tag (`"pt"` for `"pt_BR"`).
+pub fn language_subtag(tag: &str) -> Option<String> {
+    let subtag = tag.trim().split(['-', '_']).next()?;
+    (matches!(subtag.len(), 2 | 3) && subtag.chars().all(|ch| ch.is_ascii_alphabetic()))
+        .then(|| subtag.to_ascii_lowercase())
+}
+
+/// Re-read the abbreviation tokens from `conf/normalizer.toml` and
+/// `conf/abbreviations.toml` so later splits pick up edited configs.
+pub fn reload_abbreviation_tokens() {
+    let fresh = Arc::new(load_abbreviation_tokens());
+    tracing::info!(
+        nocase = fresh.nocase.len(),
+        case = fresh.case.len(),
+        regex = fresh.regex.len(),
+        "Reloaded sentence-splitter abbreviation tokens"
+    );
+    match ABBREVIATION_TOKENS.write() {
+        Ok(mut guard) => *guard = fresh,
+        Err(poisoned) => *poisoned.into_inner() = fresh,
+    }
+    match LANGUAGE_ABBREVIATION_TOKENS.write() {
+        Ok(mut guard) => guard.clear(),
+        Err(poisoned) => poisoned.into_inner().clear(),
+    }
+}
+
+fn current_abbreviation_tokens() -> Arc<AbbreviationTokenSet> {
+    match ABBREVIATION_TOKENS.read() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+fn language_abbreviation_tokens(subtag: &str) -> Arc<AbbreviationTokenSet> {
+    let cached = match LANGUAGE_ABBREVIATION_TOKENS.read() {
+        Ok(guard) => guard.get(subtag).cloned(),
+        Err(poisoned) => poisoned.into_inner().get(subtag).cloned(),
+    };
+    if let Some(cached) = cached {
+        return cached.unwrap_or_else(current_abbreviation_tokens);
+    }
+
+    let loaded = load_language_abbreviation_tokens(subtag).map(Arc::new);
+    tracing::info!(
+        language = subtag,
+        found = loaded.is_some(),
+        "Loaded sentence-splitter abbreviation tokens for language"
+    );
+    match LANGUAGE_ABBREVIATION_TOKENS.write() {
+        Ok(mut guard) => guard.insert(subtag.to_string(), loaded.clone()),
+        Err(poisoned) => poisoned
+            .into_inner()
+            .insert(subtag.to_string(), loaded.clone()),
+    };
+    loaded.unwrap_or_else(current_abbreviation_tokens)
 }
 
 fn split_sentences_with_abbreviations(
@@ -24,15 +732,60 @@ fn split_sentences_with_abbreviations(
     let chars: Vec<char> = text.chars().collect();
     let protected_periods = protected_period_indices(text, abbreviations);
 
-    for (idx, ch) in chars.iter().copied().enumerate() {
+    let mut idx = 0;
+    while idx < chars.len() {
+        let ch = chars[idx];
+        if let Some(close) = atomic_span_end(ch) {
+            // Preformatted lines, note bodies and table rows are atomic display
+            // sentences: no punctuation or soft-break splitting inside them.
+            push_sentence_with_soft_breaks(&mut sentences, &current);
+            current.clear();
+            let end = span_end(&chars, idx, close);
+            sentences.push(chars[idx..=end].iter().collect());
+            idx = end + 1;
+            continue;
+        }
+        if ch == '\n'
+            && current.chars().any(|c| !c.is_whitespace())
+            && breaks_block_after_newline(&chars, idx + 1)
+        {
+            push_sentence_with_soft_breaks(&mut sentences, &current);
+            current.clear();
+        }
         current.push(ch);
-        if matches!(ch, '.' | '!' | '?')
-            && !(ch == '.' && protected_periods.contains(&idx))
+        let protected_period = ch == '.'
+            && (protected_periods.contains(&idx) || is_list_number(&current, sentences.is_empty()));
+        if is_sentence_terminal(ch)
+            && !protected_period
             && !period_is_abbreviation(&chars, idx, abbreviations)
         {
+            // Runs such as "?!" or "...", then closing quotes and brackets,
+            // end the same sentence.
+            let mut periods_only = ch == '.';
+            while let Some(&next) = chars.get(idx + 1) {
+                if is_sentence_terminal(next) {
+                    periods_only &= next == '.';
+                } else if !is_sentence_closer(next) {
+                    break;
+                }
+                current.push(next);
+                idx += 1;
+            }
+            if sentence_continues(&chars, idx + 1, periods_only, &current) {
+                idx += 1;
+                continue;
+            }
+            // Note references right after the terminator belong to this
+            // sentence, not the next one.
+            while chars.get(idx + 1) == Some(&NOTE_REF_START) {
+                let end = span_end(&chars, idx + 1, NOTE_REF_END);
+                current.extend(&chars[idx + 1..=end]);
+                idx = end;
+            }
             push_sentence_with_soft_breaks(&mut sentences, &current);
             current.clear();
         }
+        idx += 1;
     }
 
     push_sentence_with_soft_breaks(&mut sentences, &current);
@@ -40,15 +793,50 @@ fn split_sentences_with_abbreviations(
     sentences
 }
 
+/// Unicode-style exceptions to breaking after a terminator: a period followed
+/// by a digit or a lowercase word (UAX #29 SB6/SB8), and Spanish `?`/`!`
+/// closing a `¿`/`¡` clause that continues in lowercase ("¿Vienes? —preguntó").
+fn sentence_continues(chars: &[char], from: usize, periods_only: bool, current: &str) -> bool {
+    if periods_only && chars.get(from).is_some_and(|ch| ch.is_ascii_digit()) {
+        return true;
+    }
+    let mut idx = from;
+    while idx < chars.len() && chars[idx].is_whitespace() {
+        idx += 1;
+    }
+    while idx < chars.len() && is_sentence_opener(chars[idx]) {
+        idx += 1;
+    }
+    if !chars.get(idx).is_some_and(|ch| ch.is_lowercase()) {
+        return false;
+    }
+    periods_only || current.contains(['\u{00BF}', '\u{00A1}'])
+}
+
+fn span_end(chars: &[char], start: usize, close: char) -> usize {
+    chars[start..]
+        .iter()
+        .position(|ch| *ch == close)
+        .map(|offset| start + offset)
+        .unwrap_or(chars.len() - 1)
+}
+
 fn push_sentence_with_soft_breaks(out: &mut Vec<String>, sentence: &str) {
     if !sentence.chars().any(|c| !c.is_whitespace()) {
         return;
     }
-    out.extend(split_oversized_sentence(
+    let mut pieces = split_oversized_sentence(
         sentence,
         MAX_DISPLAY_SENTENCE_CHARS,
         MAX_DISPLAY_SENTENCE_WORDS,
-    ));
+    );
+    // Soft breaks drop leading whitespace; keep the line breaks that mark a
+    // block start on the first piece.
+    let leading = &sentence[..sentence.len() - sentence.trim_start().len()];
+    if let Some(first) = pieces.first_mut().filter(|_| leading.contains('\n')) {
+        *first = format!("{leading}{}", first.trim_start());
+    }
+    out.extend(pieces);
 }
 
 fn split_oversized_sentence(sentence: &str, max_chars: usize, max_words: usize) -> Vec<String> {
@@ -287,69 +1075,57 @@ fn period_is_domain_tld(chars: &[char], dot_idx: usize) -> bool {
     )
 }
 
-static ABBREVIATION_TOKENS: Lazy<AbbreviationTokenSet> = Lazy::new(load_abbreviation_tokens);
+static ABBREVIATION_TOKENS: Lazy<RwLock<Arc<AbbreviationTokenSet>>> =
+    Lazy::new(|| RwLock::new(Arc::new(load_abbreviation_tokens())));
+/// Per-language token sets by primary subtag; `None` records a missing file.
+static LANGUAGE_ABBREVIATION_TOKENS: Lazy<
+    RwLock<HashMap<String, Option<Arc<AbbreviationTokenSet>>>>,
+> = Lazy::new(|| RwLock::new(HashMap::new()));
 
 fn load_abbreviation_tokens() -> AbbreviationTokenSet {
-    let mut out_nocase = HashSet::new();
-    let mut out_case = HashSet::new();
-    let mut out_regex = Vec::new();
+    let mut tokens = AbbreviationTokenSet::default();
     for default in ["mr.", "ms.", "mrs.", "mass.", "st."] {
-        out_nocase.insert(default.to_string());
+        tokens.nocase.insert(default.to_string());
     }
 
     let normalizer_path = resolve_normalizer_config_path();
     if let Ok(contents) = fs::read_to_string(&normalizer_path)
         && let Ok(file) = toml::from_str::<NormalizerFile>(&contents)
     {
-        let merged = file.normalization.abbreviations.merged();
-        for key in merged.nocase.keys() {
-            let normalized = normalize_abbreviation_token(key);
-            if !normalized.is_empty() {
-                out_nocase.insert(normalized);
-            }
-        }
-        for key in merged.case.keys() {
-            let normalized = normalize_abbreviation_token_case(key);
-            if !normalized.is_empty() {
-                out_case.insert(normalized);
-            }
-        }
-        for rule in &merged.regex {
-            if let Some(compiled) = compile_abbreviation_regex(rule) {
-                out_regex.push(compiled);
-            }
-        }
+        tokens.extend_from_config(&file.normalization.abbreviations);
     }
 
     let abbreviations_path = resolve_abbreviations_config_path(&normalizer_path);
     if let Ok(contents) = fs::read_to_string(&abbreviations_path)
         && let Ok(file) = toml::from_str::<AbbreviationsFile>(&contents)
     {
-        let merged = file.abbreviations.merged();
-        for key in merged.nocase.keys() {
-            let normalized = normalize_abbreviation_token(key);
-            if !normalized.is_empty() {
-                out_nocase.insert(normalized);
-            }
-        }
-        for key in merged.case.keys() {
-            let normalized = normalize_abbreviation_token_case(key);
-            if !normalized.is_empty() {
-                out_case.insert(normalized);
-            }
-        }
-        for rule in &merged.regex {
-            if let Some(compiled) = compile_abbreviation_regex(rule) {
-                out_regex.push(compiled);
-            }
-        }
+        tokens.extend_from_config(&file.abbreviations);
     }
 
-    AbbreviationTokenSet {
-        case: out_case,
-        nocase: out_nocase,
-        regex: out_regex,
-    }
+    tokens
+}
+
+/// Tokens from `abbreviations/<subtag>.toml` next to the English
+/// abbreviations file, in the same format. The English list is not merged in:
+/// "St." or "no." mean other things in other languages.
+fn load_language_abbreviation_tokens(subtag: &str) -> Option<AbbreviationTokenSet> {
+    let abbreviations_path = resolve_abbreviations_config_path(&resolve_normalizer_config_path());
+    let path = abbreviations_path
+        .parent()
+        .map(|parent| parent.join("abbreviations"))
+        .unwrap_or_else(|| PathBuf::from("abbreviations"))
+        .join(format!("{subtag}.toml"));
+    let contents = fs::read_to_string(&path).ok()?;
+    let file = match toml::from_str::<AbbreviationsFile>(&contents) {
+        Ok(file) => file,
+        Err(err) => {
+            tracing::warn!(path = %path.display(), "Invalid language abbreviations TOML: {err}");
+            return None;
+        }
+    };
+    let mut tokens = AbbreviationTokenSet::default();
+    tokens.extend_from_config(&file.abbreviations);
+    Some(tokens)
 }
 
 fn compile_abbreviation_regex(rule: &AbbreviationRegexRule) -> Option<Regex> {
@@ -503,11 +1279,193 @@ struct AbbreviationTokenSet {
     regex: Vec<Regex>,
 }
 
+impl AbbreviationTokenSet {
+    fn extend_from_config(&mut self, config: &AbbreviationConfig) {
+        let merged = config.merged();
+        for key in merged.nocase.keys() {
+            let normalized = normalize_abbreviation_token(key);
+            if !normalized.is_empty() {
+                self.nocase.insert(normalized);
+            }
+        }
+        for key in merged.case.keys() {
+            let normalized = normalize_abbreviation_token_case(key);
+            if !normalized.is_empty() {
+                self.case.insert(normalized);
+            }
+        }
+        for rule in &merged.regex {
+            if let Some(compiled) = compile_abbreviation_regex(rule) {
+                self.regex.push(compiled);
+            }
+        }
+    }
+}
+
+/// Whitespace-collapsed sentence text without display sentinels.
+pub fn anchor_text(sentence: &str) -> String {
+    strip_display_markers(sentence)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Index at or after `min_idx` whose sentence matches `text`, closest to
+/// `around`. Exact matches win; otherwise a sentence that contains or is
+/// contained in the stored text counts, which covers sentences that were
+/// split or merged at a new page boundary. Only sentences within
+/// `SENTENCE_ANCHOR_SEARCH_RADIUS` of `around` are considered.
+pub fn find_sentence_near<S: AsRef<str>>(
+    sentences: &[S],
+    text: &str,
+    around: usize,
+    min_idx: usize,
+) -> Option<usize> {
+    let target = anchor_text(text);
+    if target.is_empty() {
+        return None;
+    }
+    let around = around.clamp(min_idx, sentences.len() - 1);
+    let exact = |idx: usize| anchor_text(sentences[idx].as_ref()) == target;
+    let partial = |idx: usize| {
+        let candidate = anchor_text(sentences[idx].as_ref());
+        !candidate.is_empty() && (candidate.contains(&target) || target.contains(&candidate))
+    };
+    for matches in [&exact as &dyn Fn(usize) -> bool, &partial] {
+        for distance in 0..=SENTENCE_ANCHOR_SEARCH_RADIUS {
+            let after = around + distance;
+            if after < sentences.len() && matches(after) {
+                return Some(after);
+            }
+            if distance > 0
+                && let Some(before) = around.checked_sub(distance)
+                && before >= min_idx
+                && matches(before)
+            {
+                return Some(before);
+            }
+            if after >= sentences.len() && around.saturating_sub(distance) <= min_idx {
+                break;
+            }
+        }
+    }
+    None
+}
+
+/// Saved sentence text plus the sentences around it, for
+/// [`find_sentence_fuzzy`].
+#[derive(Debug, Clone, Copy)]
+pub struct SentenceAnchor<'a> {
+    pub text: &'a str,
+    pub before: Option<&'a str>,
+    pub after: Option<&'a str>,
+}
+
+/// Sorted character bigrams of the anchor text, ignoring case and whitespace.
+fn char_bigrams(text: &str) -> Vec<(char, char)> {
+    let chars: Vec<char> = anchor_text(text)
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    let mut grams: Vec<(char, char)> = match chars.as_slice() {
+        [only] => vec![(*only, *only)],
+        _ => chars.windows(2).map(|pair| (pair[0], pair[1])).collect(),
+    };
+    grams.sort_unstable();
+    grams
+}
+
+fn bigram_dice(a: &[(char, char)], b: &[(char, char)]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let (mut i, mut j, mut shared) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                shared += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    2.0 * shared as f64 / (a.len() + b.len()) as f64
+}
+
+/// Dice coefficient over character bigrams: 1.0 for the same text up to
+/// case, whitespace and display markers, 0.0 for nothing in common.
+pub fn text_similarity(a: &str, b: &str) -> f64 {
+    bigram_dice(&char_bigrams(a), &char_bigrams(b))
+}
+
+/// Index near `around` that best matches a saved sentence, with its score.
+/// Edited sentences still match by [`text_similarity`]; the saved neighbours
+/// tell repeated sentences apart. Only sentences within
+/// `SENTENCE_ANCHOR_SEARCH_RADIUS` of `around` are considered, and nothing is
+/// returned when the best score is below `FUZZY_ANCHOR_MIN_SCORE`.
+pub fn find_sentence_fuzzy<S: AsRef<str>>(
+    sentences: &[S],
+    anchor: &SentenceAnchor<'_>,
+    around: usize,
+) -> Option<(usize, f64)> {
+    let target = char_bigrams(anchor.text);
+    if target.is_empty() || sentences.is_empty() {
+        return None;
+    }
+    let context = |text: Option<&str>| text.map(char_bigrams).filter(|grams| !grams.is_empty());
+    let before = context(anchor.before);
+    let after = context(anchor.after);
+    let context_count = usize::from(before.is_some()) + usize::from(after.is_some());
+
+    let last = sentences.len() - 1;
+    let around = around.min(last);
+    let start = around.saturating_sub(SENTENCE_ANCHOR_SEARCH_RADIUS);
+    let end = (around + SENTENCE_ANCHOR_SEARCH_RADIUS).min(last);
+    // Neighbours of the window edges are compared too.
+    let grams_start = start.saturating_sub(1);
+    let grams: Vec<Vec<(char, char)>> = (grams_start..=(end + 1).min(last))
+        .map(|idx| char_bigrams(sentences[idx].as_ref()))
+        .collect();
+    let grams_at = |idx: usize| grams[idx - grams_start].as_slice();
+
+    let mut best: Option<(usize, f64)> = None;
+    for idx in start..=end {
+        let mut score = bigram_dice(&target, grams_at(idx));
+        if context_count > 0 {
+            let mut context_score = 0.0;
+            if let (Some(before), Some(prev)) = (&before, idx.checked_sub(1)) {
+                context_score += bigram_dice(before, grams_at(prev));
+            }
+            if let Some(after) = &after
+                && idx < last
+            {
+                context_score += bigram_dice(after, grams_at(idx + 1));
+            }
+            score = (1.0 - FUZZY_ANCHOR_CONTEXT_WEIGHT) * score
+                + FUZZY_ANCHOR_CONTEXT_WEIGHT * context_score / context_count as f64;
+        }
+        let distance = idx.abs_diff(around) as f64 / SENTENCE_ANCHOR_SEARCH_RADIUS as f64;
+        score -= FUZZY_ANCHOR_DISTANCE_PENALTY * distance;
+        if best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((idx, score));
+        }
+    }
+    best.filter(|&(_, score)| score >= FUZZY_ANCHOR_MIN_SCORE)
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        AbbreviationTokenSet, load_abbreviation_tokens, split_sentences,
-        split_sentences_with_abbreviations,
+        AbbreviationTokenSet, BlockKind, FootnoteMode, PreformattedKind, SentenceAnchor,
+        assign_blocks, classify_preformatted_block, encode_note_body, encode_note_ref,
+        encode_preformatted_block, encode_table, find_sentence_fuzzy, is_markdown_table_delimiter,
+        language_subtag, layout_footnotes, load_abbreviation_tokens,
+        load_language_abbreviation_tokens, parse_note_body, parse_preformatted_line,
+        parse_table_row, split_sentences, split_sentences_with_abbreviations,
+        strip_display_markers, text_similarity,
     };
     use std::sync::{Mutex, OnceLock};
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -589,6 +1547,115 @@ mod tests {
         let _ = std::fs::remove_file(&path);
     }
 
+    #[test]
+    fn loads_language_abbreviation_files_next_to_the_english_list() {
+        let _guard = env_lock().lock().expect("env lock should not be poisoned");
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time should be after epoch")
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("lanternleaf-lang-abbrevs-{nonce}"));
+        std::fs::create_dir_all(dir.join("abbreviations"))
+            .expect("language abbreviations dir should be created");
+        let path = dir.join("abbreviations.toml");
+        std::fs::write(&path, "[abbreviations.nocase]\n\"pp.\" = \"pages\"\n")
+            .expect("abbreviations config should be written");
+        std::fs::write(
+            dir.join("abbreviations").join("es.toml"),
+            "[abbreviations.nocase]\n\"Sra.\" = \"señora\"\n",
+        )
+        .expect("spanish abbreviations should be written");
+
+        // SAFETY: test-only, guarded by a process-wide mutex to avoid env var races.
+        unsafe {
+            std::env::set_var("LANTERNLEAF_ABBREVIATIONS_CONFIG_PATH", &path);
+        }
+        let spanish = load_language_abbreviation_tokens("es");
+        let missing = load_language_abbreviation_tokens("xx");
+        // SAFETY: test-only cleanup, guarded by a process-wide mutex to avoid env var races.
+        unsafe {
+            std::env::remove_var("LANTERNLEAF_ABBREVIATIONS_CONFIG_PATH");
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let spanish = spanish.expect("es.toml should load");
+        assert!(spanish.nocase.contains("sra."));
+        assert!(!spanish.nocase.contains("pp."));
+        assert!(missing.is_none());
+
+        let text = "La Sra. García llegó. Luego salió.";
+        assert_eq!(split_sentences_with_abbreviations(text, &spanish).len(), 2);
+        assert_eq!(
+            split_sentences_with_abbreviations(text, &AbbreviationTokenSet::default()).len(),
+            3
+        );
+        assert_eq!(language_subtag("es-MX").as_deref(), Some("es"));
+        assert_eq!(language_subtag(" pt_BR ").as_deref(), Some("pt"));
+        assert_eq!(language_subtag("x-klingon"), None);
+    }
+
+    #[test]
+    fn splits_cjk_danda_and_inverted_marks_keeping_closers() {
+        let text = "今日は晴れです。明日は？ 「はい。」 यह एक वाक्य है। यह दूसरा है। \
+                    ¿Vienes? —preguntó ella. ¡Sí! He said \"stop.\" (Quietly.) \
+                    They left... and waited?! Yes, e.g. twice.";
+        let sentences: Vec<String> = split_sentences(text)
+            .into_iter()
+            .map(|sentence| sentence.trim().to_string())
+            .collect();
+        assert_eq!(
+            sentences,
+            vec![
+                "今日は晴れです。",
+                "明日は？",
+                "「はい。」",
+                "यह एक वाक्य है।",
+                "यह दूसरा है।",
+                "¿Vienes? —preguntó ella.",
+                "¡Sí!",
+                "He said \"stop.\"",
+                "(Quietly.)",
+                "They left... and waited?!",
+                "Yes, e.g. twice.",
+            ]
+        );
+    }
+
+    #[test]
+    fn splits_blocks_at_blank_lines_and_marker_lines() {
+        let text = "# Chapter One\n\nIt began. It rained\n- first item\n- second item\n\n\
+                    > Quoted words. More.\n\n2. Numbered step.";
+        let sentences = split_sentences(text);
+        let trimmed: Vec<&str> = sentences.iter().map(|s| s.trim()).collect();
+        assert_eq!(
+            trimmed,
+            vec![
+                "# Chapter One",
+                "It began.",
+                "It rained",
+                "- first item",
+                "- second item",
+                "> Quoted words.",
+                "More.",
+                "2. Numbered step.",
+            ]
+        );
+
+        let blocks = assign_blocks(&sentences);
+        assert_eq!(blocks.sentence_blocks, vec![0, 1, 1, 2, 3, 4, 4, 5]);
+        assert_eq!(
+            blocks.kinds,
+            vec![
+                BlockKind::Heading { level: 1 },
+                BlockKind::Paragraph,
+                BlockKind::ListItem { ordered: false },
+                BlockKind::ListItem { ordered: false },
+                BlockKind::BlockQuote,
+                BlockKind::ListItem { ordered: true },
+            ]
+        );
+    }
+
     #[test]
     fn case_sensitive_abbreviation_tokens_require_exact_case() {
         let only_case = AbbreviationTokenSet {
@@ -664,4 +1731,153 @@ mod tests {
             "expected Calif. to be treated as a non-terminal abbreviation"
         );
     }
+
+    #[test]
+    fn preformatted_lines_stay_atomic_display_sentences() {
+        let body = "fn main() {\n    let total = a.len() + b.len(); // sum. Done!\n\n}\n";
+        let block = encode_preformatted_block(classify_preformatted_block("", body), body);
+        let text = format!("Consider this listing. {block} It prints nothing.");
+        let sentences = split_sentences(&text);
+        assert_eq!(sentences.len(), 5);
+        assert_eq!(sentences[0], "Consider this listing.");
+
+        let first = parse_preformatted_line(&sentences[1]).expect("first code line");
+        assert_eq!(first.kind, PreformattedKind::Code);
+        assert_eq!(first.block_lines, Some(4));
+        assert_eq!(first.text, "fn main() {");
+        let second = parse_preformatted_line(&sentences[2]).expect("second code line");
+        assert_eq!(second.block_lines, None);
+        assert_eq!(
+            second.text,
+            "    let total = a.len() + b.len(); // sum. Done!"
+        );
+        assert_eq!(
+            strip_display_markers(&sentences[3]),
+            "}",
+            "display text must not carry sentinels"
+        );
+        assert!(parse_preformatted_line(&sentences[4]).is_none());
+    }
+
+    #[test]
+    fn classifies_preformatted_blocks_by_info_string_then_punctuation() {
+        assert_eq!(
+            classify_preformatted_block("rust", "hello"),
+            PreformattedKind::Code
+        );
+        assert_eq!(
+            classify_preformatted_block("text", "x = 1;"),
+            PreformattedKind::Preformatted
+        );
+        assert_eq!(
+            classify_preformatted_block("", "The woods are lovely,\ndark and deep."),
+            PreformattedKind::Preformatted
+        );
+        assert_eq!(
+            classify_preformatted_block("", "x = 1\nprint(x)"),
+            PreformattedKind::Code
+        );
+    }
+
+    #[test]
+    fn footnote_layout_moves_bodies_next_to_references() {
+        let text = format!(
+            "First claim.{} Still first paragraph.\n\nSecond paragraph{} ends here.\n\n{} {}",
+            encode_note_ref("1"),
+            encode_note_ref("2"),
+            encode_note_body("1", "Source one."),
+            encode_note_body("2", "Source two.")
+        );
+        let labels = |mode: FootnoteMode| -> Vec<Option<String>> {
+            split_sentences(&layout_footnotes(&text, mode))
+                .iter()
+                .map(|sentence| parse_note_body(sentence).map(|body| body.label.to_string()))
+                .collect()
+        };
+        let one = Some("1".to_string());
+        let two = Some("2".to_string());
+
+        assert_eq!(
+            labels(FootnoteMode::Chapter),
+            vec![None, None, None, one.clone(), two.clone()]
+        );
+        assert_eq!(
+            labels(FootnoteMode::Inline),
+            vec![None, one.clone(), None, None, two.clone()]
+        );
+        assert_eq!(
+            labels(FootnoteMode::EndOfParagraph),
+            vec![None, None, one, None, two]
+        );
+
+        let inline = split_sentences(&layout_footnotes(&text, FootnoteMode::Inline));
+        assert_eq!(strip_display_markers(inline[0].trim()), "First claim.[1]");
+        assert_eq!(strip_display_markers(&inline[1]), "[1] Source one.");
+    }
+
+    #[test]
+    fn table_rows_round_trip_as_display_sentences() {
+        let header = vec!["Country".to_string(), "Population".to_string()];
+        let rows = vec![
+            vec!["Spain".to_string(), "48 million".to_string()],
+            vec!["France".to_string(), "68 million".to_string()],
+        ];
+        let text = format!("Before. {} After.", encode_table(2, &header, &rows));
+        let sentences = split_sentences(&text);
+        assert_eq!(sentences.len(), 5);
+
+        let head = parse_table_row(&sentences[1]).expect("header row");
+        assert_eq!((head.table, head.row, head.number), (2, 0, None));
+        assert_eq!(head.cells, vec!["Country", "Population"]);
+        let france = parse_table_row(&sentences[3]).expect("body row");
+        assert_eq!((france.row, france.number), (2, Some(2)));
+        assert_eq!(france.columns, vec!["Country", "Population"]);
+        assert_eq!(france.cells, vec!["France", "68 million"]);
+        assert_eq!(strip_display_markers(&sentences[3]), "France | 68 million");
+
+        assert!(is_markdown_table_delimiter("| --- | :---: |"));
+        assert!(!is_markdown_table_delimiter("---"));
+    }
+
+    #[test]
+    fn fuzzy_anchor_tolerates_edits_and_uses_neighbours() {
+        assert_eq!(text_similarity("Hello  World.", "hello world."), 1.0);
+        assert_eq!(text_similarity("abc", "xyz"), 0.0);
+
+        let sentences = [
+            "Yes.",
+            "The storm rolled in from the west.",
+            "Yes.",
+            "She closed the shutters before dark.",
+            "The lamp was lit, and the house was quiet.",
+            "Yes.",
+        ];
+        // An edited sentence still matches after the text shifted by one.
+        let edited = SentenceAnchor {
+            text: "The lamp was lit and the house went quiet.",
+            before: None,
+            after: None,
+        };
+        assert_eq!(
+            find_sentence_fuzzy(&sentences, &edited, 3).map(|m| m.0),
+            Some(4)
+        );
+
+        // A repeated sentence is placed by its neighbours, not by distance.
+        let repeated = SentenceAnchor {
+            text: "Yes.",
+            before: Some("The lamp was lit, and the house was quiet."),
+            after: None,
+        };
+        let (idx, score) = find_sentence_fuzzy(&sentences, &repeated, 1).expect("match");
+        assert_eq!(idx, 5);
+        assert!(score > 0.9);
+
+        let missing = SentenceAnchor {
+            text: "Nothing like this appears anywhere.",
+            before: None,
+            after: None,
+        };
+        assert_eq!(find_sentence_fuzzy(&sentences, &missing, 2), None);
+    }
 }
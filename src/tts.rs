@@ -347,6 +347,18 @@ fn cache_path(base: &Path, model_path: &Path, sentence: &str) -> PathBuf {
     base.join(format!("tts-{hash}.wav"))
 }
 
+/// Length of the cached clip for `sentence` synthesized with `model_path`,
+/// without synthesizing anything. `None` when no readable clip exists.
+pub fn cached_sentence_duration(
+    cache_root: &Path,
+    model_path: &Path,
+    sentence: &str,
+) -> Option<std::time::Duration> {
+    let path = cache_path(cache_root, model_path, &normalize_sentence(sentence));
+    let file = File::open(path).ok()?;
+    Decoder::new(BufReader::new(file)).ok()?.total_duration()
+}
+
 fn normalize_sentence(sentence: &str) -> String {
     let mut out = String::with_capacity(sentence.len());
     let mut prev_ws = false;
@@ -1,6 +1,7 @@
+use crate::ssml;
 use hound::WavSpec;
-use piper_rs::from_config_path;
 use piper_rs::synth::PiperSpeechSynthesizer;
+use piper_rs::{PiperModel, from_config_path};
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::fs;
@@ -73,7 +74,7 @@ fn run_worker() -> anyhow::Result<()> {
         );
     }
     let model = from_config_path(&config_path)?;
-    let piper = PiperSpeechSynthesizer::new(model)?;
+    let piper = PiperSpeechSynthesizer::new(model.clone())?;
 
     let stdin = std::io::stdin();
     let mut reader = BufReader::new(stdin.lock());
@@ -96,7 +97,7 @@ fn run_worker() -> anyhow::Result<()> {
         let result = match (req.text, req.path) {
             (Some(text), Some(path)) => {
                 let path = PathBuf::from(path);
-                synthesize_to_file_serial(&piper, &path, &text)
+                synthesize_to_file_serial(&piper, model.as_ref(), &path, &text)
             }
             _ => Err(anyhow::anyhow!("Invalid request payload")),
         };
@@ -133,14 +134,21 @@ fn resolve_piper_config(model_path: &Path) -> PathBuf {
 
 fn synthesize_to_file_serial(
     piper: &PiperSpeechSynthesizer,
+    model: &(dyn PiperModel + Send + Sync),
     path: &Path,
     sentence: &str,
 ) -> anyhow::Result<()> {
+    let segments = ssml::parse(sentence);
+    if !ssml::is_plain_text(&segments) {
+        return synthesize_segments(model, path, &segments);
+    }
+    let sentence = ssml::plain_text(&segments);
+
     let output_config = None;
     let mut samples: Vec<f32> = Vec::new();
     let mut sample_rate: Option<u32> = None;
     let mut channels: Option<u16> = None;
-    for chunk in piper.synthesize_lazy(sentence.to_string(), output_config)? {
+    for chunk in piper.synthesize_lazy(sentence, output_config)? {
         let chunk = chunk?;
         if sample_rate.is_none() {
             sample_rate = Some(chunk.info.sample_rate as u32);
@@ -163,6 +171,132 @@ fn synthesize_to_file_serial(
     Ok(())
 }
 
+enum SynthesizedPart {
+    Audio(Vec<f32>),
+    Silence { millis: u32 },
+}
+
+/// Synthesize SSML-lite segments run by run: consecutive text/phoneme
+/// segments that share rate and gain become one phoneme sequence, breaks
+/// become silence, and rate/gain are applied to each run's samples.
+fn synthesize_segments(
+    model: &(dyn PiperModel + Send + Sync),
+    path: &Path,
+    segments: &[ssml::SpeechSegment],
+) -> anyhow::Result<()> {
+    let mut parts: Vec<SynthesizedPart> = Vec::new();
+    let mut sample_rate: Option<u32> = None;
+    let mut channels: Option<u16> = None;
+    let mut run: Vec<String> = Vec::new();
+    let mut run_style = (1.0f32, 1.0f32);
+
+    let mut flush_run = |run: &mut Vec<String>,
+                         style: (f32, f32),
+                         parts: &mut Vec<SynthesizedPart>|
+     -> anyhow::Result<()> {
+        if run.is_empty() {
+            return Ok(());
+        }
+        let audio = model.speak_one_sentence(run.join(" "))?;
+        run.clear();
+        let rate = audio.info.sample_rate as u32;
+        let num_channels = audio.info.num_channels as u16;
+        sample_rate.get_or_insert(rate);
+        channels.get_or_insert(num_channels);
+
+        let (speed, gain) = style;
+        let mut samples = if num_channels == 1 {
+            time_stretch(audio.samples.as_slice(), rate, speed)
+        } else {
+            audio.samples.as_slice().to_vec()
+        };
+        if (gain - 1.0).abs() > f32::EPSILON {
+            for sample in &mut samples {
+                *sample = (*sample * gain).clamp(-1.0, 1.0);
+            }
+        }
+        parts.push(SynthesizedPart::Audio(samples));
+        Ok(())
+    };
+
+    for segment in segments {
+        let style = (segment.rate, segment.gain);
+        if style != run_style {
+            flush_run(&mut run, run_style, &mut parts)?;
+            run_style = style;
+        }
+        match &segment.content {
+            ssml::SpeechContent::Text(text) => {
+                if !text.trim().is_empty() {
+                    let phonemized = model.phonemize_text(text)?;
+                    run.extend(phonemized.sentences().iter().cloned());
+                }
+            }
+            ssml::SpeechContent::Phonemes(ipa) => run.push(ipa.clone()),
+            ssml::SpeechContent::Silence { millis } => {
+                flush_run(&mut run, run_style, &mut parts)?;
+                parts.push(SynthesizedPart::Silence { millis: *millis });
+            }
+        }
+    }
+    flush_run(&mut run, run_style, &mut parts)?;
+
+    let sample_rate = sample_rate.unwrap_or(22050);
+    let channels = channels.unwrap_or(1);
+    let mut samples: Vec<f32> = Vec::new();
+    for part in parts {
+        match part {
+            SynthesizedPart::Audio(audio) => samples.extend(audio),
+            SynthesizedPart::Silence { millis } => {
+                let frames = sample_rate as u64 * millis as u64 / 1000;
+                samples.extend(std::iter::repeat_n(
+                    0.0,
+                    (frames * channels as u64) as usize,
+                ));
+            }
+        }
+    }
+    if samples.is_empty() {
+        anyhow::bail!("No speech data to write");
+    }
+
+    write_wav(path, sample_rate, channels, &samples)
+}
+
+/// Overlap-add time stretch for mono samples. `speed > 1` shortens the audio
+/// while keeping pitch; good enough for short `<prosody>`/`<emphasis>` runs.
+fn time_stretch(samples: &[f32], sample_rate: u32, speed: f32) -> Vec<f32> {
+    if (speed - 1.0).abs() < 0.01 || samples.len() < 2 {
+        return samples.to_vec();
+    }
+    let frame = (sample_rate as usize * 40 / 1000).max(64);
+    let hop_out = frame / 2;
+    let hop_in = ((hop_out as f32) * speed).round().max(1.0) as usize;
+    let window: Vec<f32> = (0..frame)
+        .map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / (frame - 1) as f32).cos())
+        .collect();
+
+    let frames = samples.len().saturating_sub(frame) / hop_in + 1;
+    let out_len = (frames - 1) * hop_out + frame;
+    let mut out = vec![0.0f32; out_len];
+    let mut norm = vec![0.0f32; out_len];
+    for idx in 0..frames {
+        let in_start = idx * hop_in;
+        let out_start = idx * hop_out;
+        for (offset, weight) in window.iter().enumerate() {
+            let sample = samples.get(in_start + offset).copied().unwrap_or(0.0);
+            out[out_start + offset] += sample * weight;
+            norm[out_start + offset] += weight;
+        }
+    }
+    for (sample, weight) in out.iter_mut().zip(norm) {
+        if weight > 1e-3 {
+            *sample /= weight;
+        }
+    }
+    out
+}
+
 fn write_wav(path: &Path, sample_rate: u32, channels: u16, samples: &[f32]) -> anyhow::Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
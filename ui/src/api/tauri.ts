@@ -163,6 +163,8 @@ const mockReaderSnapshot = (): ReaderSnapshot => ({
   source_name: "mock.txt",
   current_page: 0,
   total_pages: MOCK_PAGES.length,
+  chapter: { id: 0, title: null },
+  toc: [],
   text_only_mode: false,
   has_structured_markdown: false,
   pretty_kind: "none",
@@ -173,7 +175,23 @@ const mockReaderSnapshot = (): ReaderSnapshot => ({
   page_text: MOCK_PAGES[0].text,
   sentences: MOCK_PAGES[0].sentences,
   sentence_anchor_map: [0],
+  sentence_block_ids: MOCK_PAGES[0].sentences.map(() => 0),
+  blocks: [
+    {
+      id: 0,
+      kind: { type: "paragraph" },
+      sentence_start: 0,
+      sentence_end: MOCK_PAGES[0].sentences.length
+    }
+  ],
+  annotations: [],
+  bookmarks: [],
+  can_navigate_back: false,
+  can_navigate_forward: false,
+  recent_positions: [],
+  bookmark_reanchor: null,
   highlighted_sentence_idx: 0,
+  active_table_row: null,
   search_query: "",
   search_matches: [],
   selected_search_match: null,
@@ -213,7 +231,9 @@ const mockReaderSnapshot = (): ReaderSnapshot => ({
     words_read_up_to_page_end: MOCK_PAGES[0].text.split(/\s+/).length,
     sentences_read_up_to_page_end: 1,
     words_read_up_to_current_position: MOCK_PAGES[0].text.split(/\s+/).length,
-    sentences_read_up_to_current_position: 1
+    sentences_read_up_to_current_position: 1,
+    current_chapter: null,
+    current_toc_entry: null
   },
   tts: {
     state: "idle",
@@ -221,7 +241,9 @@ const mockReaderSnapshot = (): ReaderSnapshot => ({
     sentence_count: 1,
     can_seek_prev: false,
     can_seek_next: false,
-    progress_pct: 100
+    progress_pct: 100,
+    range: null,
+    loop_start: null
   },
   panels: {
     show_settings: true,
@@ -299,6 +321,15 @@ function applyMockPage(reader: ReaderSnapshot, page: number): void {
   reader.page_text = pageData.text;
   reader.sentences = pageData.sentences;
   reader.sentence_anchor_map = pageData.sentences.map((_, idx) => idx);
+  reader.sentence_block_ids = pageData.sentences.map(() => clampedPage);
+  reader.blocks = [
+    {
+      id: clampedPage,
+      kind: { type: "paragraph" },
+      sentence_start: 0,
+      sentence_end: pageData.sentences.length
+    }
+  ];
   reader.highlighted_sentence_idx = 0;
   reader.tts.current_sentence_idx = 0;
   reader.tts.sentence_count = pageData.sentences.length;
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ActivityBookDay = { source_path: string, title: string, reading_seconds: number, pages_advanced: number, sentences_advanced: number, tts_seconds: number, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ActivityBookDay } from "./ActivityBookDay";
+
+export type ActivityDay = { 
+/**
+ * `YYYY-MM-DD` in the caller's time zone.
+ */
+date: string, reading_seconds: number, pages_advanced: number, sentences_advanced: number, tts_seconds: number, tts_words: number, books: Array<ActivityBookDay>, };
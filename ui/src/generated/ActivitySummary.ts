@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ActivityTotals } from "./ActivityTotals";
+
+export type ActivitySummary = { totals: ActivityTotals, 
+/**
+ * Words spoken per minute of TTS audio played.
+ */
+average_listening_wpm: number | null, 
+/**
+ * Consecutive active days ending today or yesterday.
+ */
+current_streak_days: number, longest_streak_days: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ActivityTotals = { sessions: number, books: number, 
+/**
+ * Time with a book open.
+ */
+reading_seconds: number, pages_advanced: number, sentences_advanced: number, tts_seconds: number, tts_words: number, };
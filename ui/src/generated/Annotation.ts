@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HighlightColor } from "./HighlightColor";
+
+export type Annotation = { id: number, 
+/**
+ * First book-wide display sentence covered.
+ */
+sentence_start: number, 
+/**
+ * One past the last book-wide display sentence covered.
+ */
+sentence_end: number, 
+/**
+ * Text of the first covered sentence, used for re-anchoring.
+ */
+start_text: string, 
+/**
+ * Text of the last covered sentence, used for re-anchoring.
+ */
+end_text: string, note: string | null, color: HighlightColor, tags: Array<string>, created_unix_secs: number, updated_unix_secs: number, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HighlightColor } from "./HighlightColor";
+
+export type AnnotationInput = { sentence_start: number, sentence_end: number, note?: string, 
+/**
+ * Falls back to the day highlight color when omitted.
+ */
+color?: HighlightColor, tags: Array<string>, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+export type BookSearchHit = { page: number, 
+/**
+ * Page-local display sentence index.
+ */
+sentence_idx: number, 
+/**
+ * Book-wide display sentence index.
+ */
+global_idx: number, snippet_before: string, snippet_match: string, snippet_after: string, 
+/**
+ * The match was found in the normalized audio text only.
+ */
+in_audio: boolean, };
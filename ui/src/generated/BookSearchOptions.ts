@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+export type BookSearchOptions = { case_sensitive: boolean, whole_word: boolean, 
+/**
+ * Treat the query as plain text instead of a regex.
+ */
+literal: boolean, 
+/**
+ * Also match the normalized audio text (expanded numbers, abbreviations...).
+ */
+include_audio: boolean, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BookSearchHit } from "./BookSearchHit";
+import type { BookSearchOptions } from "./BookSearchOptions";
+
+export type BookSearchResults = { query: string, options: BookSearchOptions, total_hits: number, offset: number, hits: Array<BookSearchHit>, 
+/**
+ * More than [`MAX_BOOK_SEARCH_HITS`] sentences matched.
+ */
+truncated: boolean, };
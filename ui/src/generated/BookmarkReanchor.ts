@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How the resume position was re-anchored when the book was opened, set
+ * when the saved sentence moved or could not be found.
+ */
+export type BookmarkReanchor = { 
+/**
+ * Book-wide display sentence the saved index pointed at.
+ */
+saved_sentence_idx: number, 
+/**
+ * Book-wide display sentence reading resumed from.
+ */
+sentence_idx: number, 
+/**
+ * Similarity of the matched sentence, from 0 to 1. `None` when the saved
+ * text was not found and the saved index was kept.
+ */
+score: number | null, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LibrarySearchHit } from "./LibrarySearchHit";
+
+export type LibrarySearchBook = { source_path: string, title: string, 
+/**
+ * Sentences containing every query word.
+ */
+total_hits: number, 
+/**
+ * Exact phrase matches first, then reading order.
+ */
+hits: Array<LibrarySearchHit>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+export type LibrarySearchHit = { 
+/**
+ * Sentence ordinal within the book's `tts_text`; a hint for re-anchoring.
+ */
+sentence_idx: number, sentence_text: string, snippet_before: string, snippet_match: string, snippet_after: string, };
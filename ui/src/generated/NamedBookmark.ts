@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+export type NamedBookmark = { id: number, name: string, 
+/**
+ * Book-wide display sentence index.
+ */
+sentence_idx: number, 
+/**
+ * Text of the bookmarked sentence, used to re-anchor after repagination.
+ */
+sentence_text: string, created_unix_secs: number, };
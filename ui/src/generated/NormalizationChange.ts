@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A display sentence on the current page whose audio text changed after a
+ * normalizer config reload.
+ */
+export type NormalizationChange = { sentence_idx: number, sentence: string, before: Array<string>, after: Array<string>, };
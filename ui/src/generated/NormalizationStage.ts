@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One normalizer stage as reported by [`TextNormalizer::explain`].
+ */
+export type NormalizationStage = { stage: string, before: string, after: string, changed: boolean, };
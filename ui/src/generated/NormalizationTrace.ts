@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NormalizationStage } from "./NormalizationStage";
+
+export type NormalizationTrace = { input: string, stages: Array<NormalizationStage>, normalized: string | null, chunks: Array<string>, config_hash: string, };
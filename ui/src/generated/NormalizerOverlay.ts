@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Per-book normalizer additions persisted in the book cache and layered over
+ * the global config when a reader session loads.
+ */
+export type NormalizerOverlay = { custom_pronunciations: { [key in string]?: string }, brand_map: { [key in string]?: string }, replacements: { [key in string]?: string }, abbreviations: { [key in string]?: string }, drop_tokens: Array<string>, acronym_tokens: Array<string>, drop_square_bracket_text?: boolean, drop_curly_brace_text?: boolean, 
+/**
+ * Replaces the global `[normalization.math] fallback` for this book.
+ */
+math_fallback?: string, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NormalizationChange } from "./NormalizationChange";
+
+export type NormalizerReloadEvent = { request_id: number, changed_sentences: Array<NormalizationChange>, 
+/**
+ * Why the edited config was rejected; the previous config stays active.
+ */
+error: string | null, };
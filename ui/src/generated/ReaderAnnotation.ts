@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Annotation } from "./Annotation";
+
+export type ReaderAnnotation = { annotation: Annotation, sentence_start: number, sentence_end: number, };
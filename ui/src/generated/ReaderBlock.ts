@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BlockKind } from "./BlockKind";
+
+export type ReaderBlock = { id: number, kind: BlockKind, 
+/**
+ * First entry of `sentences` in this block.
+ */
+sentence_start: number, 
+/**
+ * One past the last entry of `sentences` in this block.
+ */
+sentence_end: number, };
@@ -1,9 +1,29 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BookmarkReanchor } from "./BookmarkReanchor";
+import type { NamedBookmark } from "./NamedBookmark";
+import type { PageChapter } from "./PageChapter";
 import type { PanelState } from "./PanelState";
 import type { PrettyKind } from "./PrettyKind";
+import type { ReaderAnnotation } from "./ReaderAnnotation";
+import type { ReaderBlock } from "./ReaderBlock";
 import type { ReaderImageRef } from "./ReaderImageRef";
 import type { ReaderSettingsView } from "./ReaderSettingsView";
 import type { ReaderStats } from "./ReaderStats";
 import type { ReaderTtsView } from "./ReaderTtsView";
+import type { RecentPosition } from "./RecentPosition";
+import type { TableRowRef } from "./TableRowRef";
+import type { TocNode } from "./TocNode";
 
-export type ReaderSnapshot = { source_path: string, source_name: string, current_page: number, total_pages: number, text_only_mode: boolean, has_structured_markdown: boolean, pretty_kind: PrettyKind, images: Array<ReaderImageRef>, tts_text_page: string, reading_markdown_page: string | null, reading_html_page: string | null, page_text: string, sentences: Array<string>, sentence_anchor_map: Array<number | null>, highlighted_sentence_idx: number | null, search_query: string, search_matches: Array<number>, selected_search_match: number | null, settings: ReaderSettingsView, tts: ReaderTtsView, stats: ReaderStats, panels: PanelState, };
+export type ReaderSnapshot = { source_path: string, source_name: string, current_page: number, total_pages: number, chapter: PageChapter | null, toc: Array<TocNode>, text_only_mode: boolean, has_structured_markdown: boolean, pretty_kind: PrettyKind, images: Array<ReaderImageRef>, tts_text_page: string, reading_markdown_page: string | null, reading_html_page: string | null, page_text: string, sentences: Array<string>, sentence_anchor_map: Array<number | null>, 
+/**
+ * Block ID of each entry in `sentences`.
+ */
+sentence_block_ids: Array<number>, blocks: Array<ReaderBlock>, annotations: Array<ReaderAnnotation>, 
+/**
+ * User-created bookmarks for the whole book, in reading order.
+ */
+bookmarks: Array<NamedBookmark>, can_navigate_back: boolean, can_navigate_forward: boolean, 
+/**
+ * Positions left by recent jumps, most recent first; kept across restarts.
+ */
+recent_positions: Array<RecentPosition>, bookmark_reanchor: BookmarkReanchor | null, highlighted_sentence_idx: number | null, active_table_row: TableRowRef | null, search_query: string, search_matches: Array<number>, selected_search_match: number | null, settings: ReaderSettingsView, tts: ReaderTtsView, stats: ReaderStats, panels: PanelState, };
@@ -1,3 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type ReaderStats = { page_index: number, total_pages: number, tts_progress_pct: number, global_progress_pct: number, page_time_remaining_secs: number, book_time_remaining_secs: number, page_word_count: number, page_sentence_count: number, page_start_percent: number, page_end_percent: number, words_read_up_to_page_start: number, sentences_read_up_to_page_start: number, words_read_up_to_page_end: number, sentences_read_up_to_page_end: number, words_read_up_to_current_position: number, sentences_read_up_to_current_position: number, };
+export type ReaderStats = { page_index: number, total_pages: number, tts_progress_pct: number, global_progress_pct: number, page_time_remaining_secs: number, book_time_remaining_secs: number, page_word_count: number, page_sentence_count: number, page_start_percent: number, page_end_percent: number, words_read_up_to_page_start: number, sentences_read_up_to_page_start: number, words_read_up_to_page_end: number, sentences_read_up_to_page_end: number, words_read_up_to_current_position: number, sentences_read_up_to_current_position: number, 
+/**
+ * Title of the deepest TOC entry at or before the current sentence.
+ */
+current_chapter: string | null, current_toc_entry: number | null, };
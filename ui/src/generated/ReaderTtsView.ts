@@ -1,4 +1,13 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { TtsPlaybackState } from "./TtsPlaybackState";
+import type { TtsRange } from "./TtsRange";
 
-export type ReaderTtsView = { state: TtsPlaybackState, current_sentence_idx: number | null, sentence_count: number, can_seek_prev: boolean, can_seek_next: boolean, progress_pct: number, };
+export type ReaderTtsView = { state: TtsPlaybackState, current_sentence_idx: number | null, sentence_count: number, can_seek_prev: boolean, can_seek_next: boolean, progress_pct: number, 
+/**
+ * Sentence range being played by `TtsPlayRange` or an A-B loop.
+ */
+range: TtsRange | null, 
+/**
+ * Book-wide sentence marked as the A point of a pending A-B loop.
+ */
+loop_start: number | null, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+export type RecentPosition = { 
+/**
+ * Book-wide display sentence index.
+ */
+sentence_idx: number, 
+/**
+ * Text of that sentence, used to re-anchor after repagination.
+ */
+sentence_text: string, left_unix_secs: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+export type TocNode = { id: number, title: string, 
+/**
+ * Book-wide display sentence index the entry points at.
+ */
+sentence_idx: number | null, page: number | null, children: Array<TocNode>, };
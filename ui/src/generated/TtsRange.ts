@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Inclusive range of book-wide display sentences for range playback.
+ */
+export type TtsRange = { start: number, end: number, 
+/**
+ * Start over from `start` instead of pausing after `end`.
+ */
+repeat: boolean, };
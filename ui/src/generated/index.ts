@@ -12,16 +12,44 @@ export type { PdfTranscriptionEvent } from "./PdfTranscriptionEvent";
 export type { LogLevelEvent } from "./LogLevelEvent";
 export type { SessionStateEvent } from "./SessionStateEvent";
 export type { ReaderStateEvent } from "./ReaderStateEvent";
+export type { NormalizerReloadEvent } from "./NormalizerReloadEvent";
 export type { BridgeError } from "./BridgeError";
 export type { PanelState } from "./PanelState";
 export type { ReaderSettingsView } from "./ReaderSettingsView";
 export type { ReaderTtsView } from "./ReaderTtsView";
+export type { TtsRange } from "./TtsRange";
+export type { BookmarkReanchor } from "./BookmarkReanchor";
 export type { ReaderSettingsPatch } from "./ReaderSettingsPatch";
 export type { ReaderStats } from "./ReaderStats";
 export type { ReaderImageRef } from "./ReaderImageRef";
+export type { TableRowRef } from "./TableRowRef";
+export type { BlockKind } from "./BlockKind";
+export type { ReaderBlock } from "./ReaderBlock";
+export type { PageChapter } from "./PageChapter";
+export type { TocNode } from "./TocNode";
+export type { NamedBookmark } from "./NamedBookmark";
+export type { RecentPosition } from "./RecentPosition";
+export type { Annotation } from "./Annotation";
+export type { AnnotationInput } from "./AnnotationInput";
+export type { ReaderAnnotation } from "./ReaderAnnotation";
+export type { AnnotationExportFormat } from "./AnnotationExportFormat";
+export type { AnnotationExportResult } from "./AnnotationExportResult";
+export type { BookSearchOptions } from "./BookSearchOptions";
+export type { BookSearchHit } from "./BookSearchHit";
+export type { BookSearchResults } from "./BookSearchResults";
+export type { LibrarySearchHit } from "./LibrarySearchHit";
+export type { LibrarySearchBook } from "./LibrarySearchBook";
+export type { ActivityTotals } from "./ActivityTotals";
+export type { ActivityBookDay } from "./ActivityBookDay";
+export type { ActivityDay } from "./ActivityDay";
+export type { ActivitySummary } from "./ActivitySummary";
 export type { ReaderSnapshot } from "./ReaderSnapshot";
 export type { PrettyKind } from "./PrettyKind";
 export type { TtsPlaybackState } from "./TtsPlaybackState";
+export type { NormalizerOverlay } from "./NormalizerOverlay";
+export type { NormalizationStage } from "./NormalizationStage";
+export type { NormalizationTrace } from "./NormalizationTrace";
+export type { NormalizationChange } from "./NormalizationChange";
 export type { ThemeMode } from "./ThemeMode";
 export type { FontFamily } from "./FontFamily";
 export type { FontWeight } from "./FontWeight";
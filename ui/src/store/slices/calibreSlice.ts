@@ -1,5 +1,5 @@
 import type { AppStore } from "../appStore";
-import { buildToast, finishTelemetry, toBridgeError, withBusy } from "./shared";
+import { buildOpenToast, buildToast, finishTelemetry, toBridgeError, withBusy } from "./shared";
 import type { SliceContext } from "./types";
 
 export function createCalibreSliceActions({ set, get, backend }: SliceContext): Pick<
@@ -35,7 +35,7 @@ export function createCalibreSliceActions({ set, get, backend }: SliceContext):
             session: result.session,
             reader: result.reader,
             recents,
-            toast: buildToast("success", "Book opened from calibre")
+            toast: buildOpenToast(result.reader, "Book opened from calibre")
           });
         } catch (error) {
           const bridgeError = toBridgeError(error);
@@ -1,6 +1,6 @@
 import type { AppStore } from "../appStore";
 import { ensureJobSubscriptions } from "./jobsSlice";
-import { buildToast, finishTelemetry, toBridgeError, toMessage, withBusy } from "./shared";
+import { buildOpenToast, buildToast, finishTelemetry, toBridgeError, toMessage, withBusy } from "./shared";
 import type { SliceContext } from "./types";
 
 export function createSessionSliceActions({ set, get, backend }: SliceContext): Pick<
@@ -106,7 +106,7 @@ export function createSessionSliceActions({ set, get, backend }: SliceContext):
             session: result.session,
             reader: result.reader,
             recents,
-            toast: buildToast("success", "Source opened")
+            toast: buildOpenToast(result.reader, "Source opened")
           });
         } catch (error) {
           const bridgeError = toBridgeError(error);
@@ -135,7 +135,7 @@ export function createSessionSliceActions({ set, get, backend }: SliceContext):
             session: result.session,
             reader: result.reader,
             recents,
-            toast: buildToast("success", "Clipboard text opened")
+            toast: buildOpenToast(result.reader, "Clipboard text opened")
           });
         } catch (error) {
           const bridgeError = toBridgeError(error);
@@ -164,7 +164,7 @@ export function createSessionSliceActions({ set, get, backend }: SliceContext):
             session: result.session,
             reader: result.reader,
             recents,
-            toast: buildToast("success", "Browser tab imported")
+            toast: buildOpenToast(result.reader, "Browser tab imported")
           });
         } catch (error) {
           const bridgeError = toBridgeError(error);
@@ -197,7 +197,7 @@ export function createSessionSliceActions({ set, get, backend }: SliceContext):
             session: result.session,
             reader: result.reader,
             recents,
-            toast: buildToast("success", "Browser tab refreshed")
+            toast: buildOpenToast(result.reader, "Browser tab refreshed")
           });
         } catch (error) {
           const bridgeError = toBridgeError(error);
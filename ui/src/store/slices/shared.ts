@@ -1,4 +1,4 @@
-import type { BridgeError, SessionState } from "../../types";
+import type { BridgeError, ReaderSnapshot, SessionState } from "../../types";
 import type { ActionTelemetry, ToastMessage } from "../appStore";
 import type { StoreGet, StoreSet } from "./types";
 
@@ -42,6 +42,19 @@ export function buildToast(severity: ToastSeverity, message: string): ToastMessa
   };
 }
 
+/** Success toast for an opened source, noting a re-anchored resume position. */
+export function buildOpenToast(reader: ReaderSnapshot, message: string): ToastMessage {
+  const reanchor = reader.bookmark_reanchor;
+  if (!reanchor) {
+    return buildToast("success", message);
+  }
+  const detail =
+    reanchor.score === null
+      ? "saved sentence not found, resumed at its old position"
+      : "book changed, resumed at the matching sentence";
+  return buildToast("info", `${message}; ${detail}`);
+}
+
 export function appendTelemetry(
   set: StoreSet,
   get: StoreGet,